@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use kameo::mailbox::unbounded::UnboundedMailbox;
+use kameo::request::MessageSend;
+use kameo::{
+    message::{Context, Message},
+    Actor,
+};
+
+/// A byte buffer moved through the mailbox.
+///
+/// The handler replies with the address of the buffer, which must match the address the buffer had before being sent,
+/// proving the payload bytes were moved rather than copied.
+struct Payload(Box<[u8]>);
+
+struct BinaryActor;
+
+impl Actor for BinaryActor {
+    type Mailbox = UnboundedMailbox<Self>;
+}
+
+impl Message<Payload> for BinaryActor {
+    type Reply = usize;
+
+    async fn handle(
+        &mut self,
+        Payload(bytes): Payload,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        bytes.as_ptr() as usize
+    }
+}
+
+fn payload(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .build()
+        .unwrap();
+    let _guard = rt.enter();
+
+    let actor_ref = kameo::actor::spawn_in_thread(BinaryActor);
+
+    let mut group = c.benchmark_group("threaded_actor_box_payload");
+    for size in [64, 64 * 1024, 1024 * 1024] {
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.to_async(&rt).iter_batched(
+                || vec![0u8; size].into_boxed_slice(),
+                |bytes| async {
+                    let addr = bytes.as_ptr() as usize;
+                    let received = actor_ref.ask(Payload(bytes)).send().await.unwrap();
+                    assert_eq!(addr, received, "payload bytes were copied");
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, payload);
+criterion_main!(benches);
@@ -0,0 +1,325 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use kameo::{
+    actor::ActorID,
+    error::{ActorStopReason, SendError},
+    mailbox::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox},
+    message::{Context, Message},
+    Actor,
+};
+use tokio::sync::mpsc;
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+// A mailbox which handles urgent messages before any others.
+//
+// Messages in lane 1 are urgent, along with startup and link signals. Everything else, including the stop signal,
+// shares the normal queue, so stopping gracefully still processes the messages sent before it.
+pub struct PriorityMailbox<A: Actor> {
+    urgent: mpsc::UnboundedSender<Signal<A>>,
+    normal: mpsc::UnboundedSender<Signal<A>>,
+    len: Arc<AtomicUsize>,
+}
+
+impl<A: Actor> PriorityMailbox<A> {
+    fn send_signal(&self, signal: Signal<A>) -> Result<(), Signal<A>> {
+        let urgent = match &signal {
+            Signal::Message { message, .. } => message.lane() == 1,
+            Signal::StartupFinished | Signal::LinkDied { .. } => true,
+            _ => false,
+        };
+        let tx = if urgent { &self.urgent } else { &self.normal };
+        self.len.fetch_add(1, Ordering::Relaxed);
+        tx.send(signal).map_err(|err| {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+            err.0
+        })
+    }
+}
+
+impl<A: Actor> Mailbox<A> for PriorityMailbox<A> {
+    type Receiver = PriorityMailboxReceiver<A>;
+    type WeakMailbox = WeakPriorityMailbox<A>;
+
+    fn default_mailbox() -> (Self, Self::Receiver) {
+        let (urgent_tx, urgent_rx) = mpsc::unbounded_channel();
+        let (normal_tx, normal_rx) = mpsc::unbounded_channel();
+        let len = Arc::new(AtomicUsize::new(0));
+        (
+            PriorityMailbox {
+                urgent: urgent_tx,
+                normal: normal_tx,
+                len: len.clone(),
+            },
+            PriorityMailboxReceiver {
+                urgent: urgent_rx,
+                normal: normal_rx,
+                len,
+            },
+        )
+    }
+
+    async fn send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal).map_err(SendError::ActorNotRunning)
+    }
+
+    fn try_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal).map_err(SendError::ActorNotRunning)
+    }
+
+    fn blocking_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal).map_err(SendError::ActorNotRunning)
+    }
+
+    async fn closed(&self) {
+        self.normal.closed().await
+    }
+
+    fn is_closed(&self) -> bool {
+        self.normal.is_closed()
+    }
+
+    fn downgrade(&self) -> Self::WeakMailbox {
+        WeakPriorityMailbox {
+            urgent: self.urgent.downgrade(),
+            normal: self.normal.downgrade(),
+            len: self.len.clone(),
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        self.normal.strong_count()
+    }
+
+    fn weak_count(&self) -> usize {
+        self.normal.weak_count()
+    }
+
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+}
+
+impl<A: Actor> SignalMailbox for PriorityMailbox<A> {
+    fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        let res = self
+            .send_signal(Signal::StartupFinished)
+            .map_err(|_| SendError::ActorNotRunning(()));
+        async move { res }.boxed()
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        let res = self
+            .send_signal(Signal::LinkDied { id, reason })
+            .map_err(|_| SendError::ActorNotRunning(()));
+        async move { res }.boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        let res = self
+            .send_signal(Signal::Stop { reason: None })
+            .map_err(|_| SendError::ActorNotRunning(()));
+        async move { res }.boxed()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<A: Actor> Clone for PriorityMailbox<A> {
+    fn clone(&self) -> Self {
+        PriorityMailbox {
+            urgent: self.urgent.clone(),
+            normal: self.normal.clone(),
+            len: self.len.clone(),
+        }
+    }
+}
+
+pub struct PriorityMailboxReceiver<A: Actor> {
+    urgent: mpsc::UnboundedReceiver<Signal<A>>,
+    normal: mpsc::UnboundedReceiver<Signal<A>>,
+    len: Arc<AtomicUsize>,
+}
+
+impl<A: Actor> MailboxReceiver<A> for PriorityMailboxReceiver<A> {
+    async fn recv(&mut self) -> Option<Signal<A>> {
+        let signal = tokio::select! {
+            biased;
+            Some(signal) = self.urgent.recv() => signal,
+            // Both queues share the same senders, so the normal queue closing means there are no more urgent signals
+            signal = self.normal.recv() => signal?,
+        };
+        self.len.fetch_sub(1, Ordering::Relaxed);
+        Some(signal)
+    }
+
+    fn try_recv(&mut self) -> Option<Signal<A>> {
+        let signal = self
+            .urgent
+            .try_recv()
+            .or_else(|_| self.normal.try_recv())
+            .ok()?;
+        self.len.fetch_sub(1, Ordering::Relaxed);
+        Some(signal)
+    }
+}
+
+pub struct WeakPriorityMailbox<A: Actor> {
+    urgent: mpsc::WeakUnboundedSender<Signal<A>>,
+    normal: mpsc::WeakUnboundedSender<Signal<A>>,
+    len: Arc<AtomicUsize>,
+}
+
+impl<A: Actor> WeakMailbox for WeakPriorityMailbox<A> {
+    type StrongMailbox = PriorityMailbox<A>;
+
+    fn upgrade(&self) -> Option<Self::StrongMailbox> {
+        Some(PriorityMailbox {
+            urgent: self.urgent.upgrade()?,
+            normal: self.normal.upgrade()?,
+            len: self.len.clone(),
+        })
+    }
+
+    fn strong_count(&self) -> usize {
+        self.normal.strong_count()
+    }
+
+    fn weak_count(&self) -> usize {
+        self.normal.weak_count()
+    }
+}
+
+impl<A: Actor> SignalMailbox for WeakPriorityMailbox<A> {
+    fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_startup_finished().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_link_died(id, reason).await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_stop().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+}
+
+impl<A: Actor> Clone for WeakPriorityMailbox<A> {
+    fn clone(&self) -> Self {
+        WeakPriorityMailbox {
+            urgent: self.urgent.clone(),
+            normal: self.normal.clone(),
+            len: self.len.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Logger {
+    lines: Vec<String>,
+}
+
+impl Actor for Logger {
+    type Mailbox = PriorityMailbox<Self>;
+
+    fn name() -> &'static str {
+        "Logger"
+    }
+}
+
+pub struct Log(String);
+
+impl Message<Log> for Logger {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        Log(line): Log,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        info!("{line}");
+        self.lines.push(line);
+    }
+}
+
+// An urgent message, which is handled before any queued log lines
+pub struct Flush;
+
+impl Message<Flush> for Logger {
+    type Reply = Vec<String>;
+
+    fn lane() -> usize {
+        1
+    }
+
+    async fn handle(&mut self, _msg: Flush, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+        std::mem::take(&mut self.lines)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter("info".parse::<EnvFilter>().unwrap())
+        .without_time()
+        .with_target(false)
+        .init();
+
+    let prepared = kameo::actor::prepare(Logger::default());
+    let logger = prepared.actor_ref_owned();
+
+    // Queued before the actor starts, so the flush overtakes the log lines
+    logger.tell(Log("first".to_string())).await?;
+    logger.tell(Log("second".to_string())).await?;
+    let flush = tokio::spawn({
+        let logger = logger.clone();
+        async move { logger.ask(Flush).await }
+    });
+    tokio::task::yield_now().await;
+    prepared.spawn();
+
+    let flushed = flush.await??;
+    info!("flushed before logging: {flushed:?}");
+    assert!(flushed.is_empty());
+
+    logger.stop_gracefully().await?;
+    logger.wait_for_stop().await;
+
+    Ok(())
+}
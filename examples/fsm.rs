@@ -0,0 +1,122 @@
+use kameo::actor::fsm::{Fsm, StateContext, StateHandler, StateMachine};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Clone, Debug, PartialEq)]
+enum TcpState {
+    Closed,
+    Listen,
+    SynReceived,
+    Established,
+}
+
+#[derive(Default)]
+struct TcpConnection {
+    received: Vec<u8>,
+}
+
+impl StateMachine for TcpConnection {
+    type State = TcpState;
+
+    async fn on_transition(&mut self, from: &TcpState, to: &TcpState) {
+        println!("{from:?} -> {to:?}");
+    }
+}
+
+struct Listen;
+
+impl StateHandler<Listen> for TcpConnection {
+    type Reply = ();
+
+    fn allowed(state: &TcpState) -> bool {
+        *state == TcpState::Closed
+    }
+
+    async fn handle(&mut self, _: Listen, mut ctx: StateContext<'_, Self>) {
+        ctx.transition(TcpState::Listen);
+    }
+}
+
+struct Syn;
+
+impl StateHandler<Syn> for TcpConnection {
+    type Reply = ();
+
+    fn allowed(state: &TcpState) -> bool {
+        *state == TcpState::Listen
+    }
+
+    async fn handle(&mut self, _: Syn, mut ctx: StateContext<'_, Self>) {
+        ctx.transition(TcpState::SynReceived);
+    }
+}
+
+struct Ack;
+
+impl StateHandler<Ack> for TcpConnection {
+    type Reply = ();
+
+    fn allowed(state: &TcpState) -> bool {
+        *state == TcpState::SynReceived
+    }
+
+    async fn handle(&mut self, _: Ack, mut ctx: StateContext<'_, Self>) {
+        ctx.transition(TcpState::Established);
+    }
+}
+
+struct Data(Vec<u8>);
+
+impl StateHandler<Data> for TcpConnection {
+    type Reply = usize;
+
+    fn allowed(state: &TcpState) -> bool {
+        *state == TcpState::Established
+    }
+
+    async fn handle(&mut self, Data(data): Data, _ctx: StateContext<'_, Self>) -> usize {
+        self.received.extend(data);
+        self.received.len()
+    }
+}
+
+struct Fin;
+
+impl StateHandler<Fin> for TcpConnection {
+    type Reply = ();
+
+    fn allowed(state: &TcpState) -> bool {
+        *state != TcpState::Closed
+    }
+
+    async fn handle(&mut self, _: Fin, mut ctx: StateContext<'_, Self>) {
+        self.received.clear();
+        ctx.transition(TcpState::Closed);
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter("warn".parse::<EnvFilter>().unwrap())
+        .without_time()
+        .with_target(false)
+        .init();
+
+    let conn = kameo::spawn(Fsm::new(TcpConnection::default(), TcpState::Closed));
+
+    // Data is rejected until the handshake completes
+    if let Err(err) = conn.ask(Data(b"too early".to_vec())).await {
+        println!("rejected: {err}");
+    }
+
+    conn.ask(Listen).await?;
+    conn.ask(Syn).await?;
+    conn.ask(Ack).await?;
+
+    let received = conn.ask(Data(b"hello".to_vec())).await?;
+    println!("received {received} bytes");
+
+    conn.ask(Fin).await?;
+
+    Ok(())
+}
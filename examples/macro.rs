@@ -1,6 +1,6 @@
 use std::fmt;
 
-use kameo::{messages, request::MessageSendSync, Actor};
+use kameo::{message::Context, messages, request::MessageSendSync, Actor};
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
@@ -9,6 +9,8 @@ pub struct MyActor {
     count: i64,
 }
 
+pub struct Reset;
+
 #[messages]
 impl MyActor {
     fn new() -> Self {
@@ -21,6 +23,13 @@ impl MyActor {
         self.count
     }
 
+    /// Handles an existing message type, returning the previous count
+    #[handler]
+    async fn reset(&mut self, _msg: Reset, ctx: Context<'_, Self, i64>) -> i64 {
+        info!("Resetting {}", ctx.actor_ref().id());
+        std::mem::take(&mut self.count)
+    }
+
     #[message]
     fn force_err(&self) -> Result<(), i32> {
         Err(3)
@@ -53,6 +62,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let count = my_actor_ref.ask(Inc { amount: 3 }).await?;
     info!("Count is {count}");
 
+    // Reset the count with a handler for an existing message type
+    let count = my_actor_ref.ask(Reset).await?;
+    info!("Count was {count}");
+
     // Increment the count by 50 in the background
     my_actor_ref.tell(Inc { amount: 50 }).send_sync()?;
 
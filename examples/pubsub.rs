@@ -49,8 +49,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let pubsub = kameo::spawn(PubSub::<PrintActorID>::new());
     let actor_a = kameo::spawn(ActorA);
     let actor_b = kameo::spawn(ActorB);
-    pubsub.ask(Subscribe(actor_a)).await?;
-    pubsub.ask(Subscribe(actor_b)).await?;
+    pubsub.ask(Subscribe::new(actor_a)).await?;
+    pubsub.ask(Subscribe::new(actor_b)).await?;
     pubsub.ask(Publish(PrintActorID)).await?;
 
     Ok(())
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use kameo::{
+    actor::ActorRef,
+    error::BoxError,
+    mailbox::unbounded::UnboundedMailbox,
+    message::{Context, Message},
+    Actor,
+};
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+// An actor which ticks once a second, by rescheduling a tick to itself
+#[derive(Default)]
+pub struct Heartbeat {
+    beats: u32,
+}
+
+impl Actor for Heartbeat {
+    type Mailbox = UnboundedMailbox<Self>;
+
+    async fn on_start(&mut self, actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+        actor_ref
+            .scheduled()
+            .tell_after(Beat, Duration::from_secs(1));
+        Ok(())
+    }
+}
+
+pub struct Beat;
+
+impl Message<Beat> for Heartbeat {
+    type Reply = ();
+
+    async fn handle(&mut self, _: Beat, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+        self.beats += 1;
+        ctx.actor_ref()
+            .scheduled()
+            .tell_after(Beat, Duration::from_secs(1));
+    }
+}
+
+pub struct Beats;
+
+impl Message<Beats> for Heartbeat {
+    type Reply = u32;
+
+    async fn handle(&mut self, _: Beats, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+        self.beats
+    }
+}
+
+// The clock starts paused, so an hour of heartbeats runs instantly
+#[tokio::main(flavor = "current_thread", start_paused = true)]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter("info".parse::<EnvFilter>().unwrap())
+        .without_time()
+        .with_target(false)
+        .init();
+
+    let real_start = std::time::Instant::now();
+    let heartbeat = kameo::spawn(Heartbeat::default());
+    heartbeat.wait_startup().await;
+
+    kameo::test::advance_time(Duration::from_millis(500)).await;
+    assert_eq!(heartbeat.ask(Beats).await?, 0);
+
+    for second in 1..=3600 {
+        kameo::test::advance_time(Duration::from_secs(1)).await;
+        assert_eq!(heartbeat.ask(Beats).await?, second);
+    }
+
+    info!(
+        "counted an hour of heartbeats in {:?}",
+        real_start.elapsed()
+    );
+
+    Ok(())
+}
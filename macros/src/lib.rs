@@ -18,10 +18,22 @@ use syn::parse_macro_input;
 /// Methods on the impl block are marked with `#[message]`.
 /// This generates a struct for the message, allowing it to be sent to the actor.
 ///
+/// Methods can instead be marked with `#[handler]` to handle an existing message type, taking the message
+/// and optionally the [Context](https://docs.rs/kameo/latest/kameo/message/struct.Context.html) as parameters.
+/// The reply type is inferred from the return type, defaulting to `()`.
+///
 /// # Example
 ///
 /// ```
 /// use kameo::messages;
+/// # use kameo::Actor;
+/// #
+/// # #[derive(Actor, Default)]
+/// # struct Counter {
+/// #     count: i64,
+/// # }
+/// #
+/// # struct Reset;
 ///
 /// #[messages]
 /// impl Counter {
@@ -34,19 +46,54 @@ use syn::parse_macro_input;
 ///
 ///     /// Derives on the message
 ///     #[message(derive(Clone, Copy))]
-///     pub fn dec(&self, amount: u32) {
+///     pub fn dec(&mut self, amount: u32) {
 ///         self.count -= amount as i64;
 ///     }
+///
+///     /// Handles an existing message type
+///     #[handler]
+///     pub async fn reset(&mut self, _msg: Reset) -> i64 {
+///         std::mem::take(&mut self.count)
+///     }
 /// }
 ///
+/// # tokio_test::block_on(async {
+/// # let counter_ref = kameo::spawn(Counter::default());
 /// counter_ref.ask(Inc { amount: 5 }).await?;
 /// counter_ref.ask(Dec { amount: 2 }.clone()).await?;
+/// counter_ref.ask(Reset).await?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
 /// ```
 ///
 /// <details>
 /// <summary>See expanded code</summary>
 ///
 /// ```
+/// # use kameo::Actor;
+/// #
+/// # #[derive(Actor)]
+/// # struct Counter {
+/// #     count: i64,
+/// # }
+/// #
+/// # impl Counter {
+/// #     pub fn inc(&mut self, amount: u32) -> i64 {
+/// #         self.count += amount as i64;
+/// #         self.count
+/// #     }
+/// #
+/// #     pub fn dec(&mut self, amount: u32) {
+/// #         self.count -= amount as i64;
+/// #     }
+/// #
+/// #     pub async fn reset(&mut self, _msg: Reset) -> i64 {
+/// #         std::mem::take(&mut self.count)
+/// #     }
+/// # }
+/// #
+/// # struct Reset;
+/// #
 /// pub struct Inc {
 ///     pub amount: u32,
 /// }
@@ -54,23 +101,31 @@ use syn::parse_macro_input;
 /// impl kameo::message::Message<Inc> for Counter {
 ///     type Reply = i64;
 ///
-///     async fn handle(&mut self, msg: Counter, _ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
+///     async fn handle(&mut self, msg: Inc, _ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
 ///         self.inc(msg.amount)
 ///     }
 /// }
 ///
-/// pub struct Count;
-///
 /// #[derive(Clone, Copy)]
-/// pub struct Dec;
+/// pub struct Dec {
+///     pub amount: u32,
+/// }
 ///
 /// impl kameo::message::Message<Dec> for Counter {
 ///     type Reply = ();
 ///
-///     async fn handle(&mut self, msg: Counter, _ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
+///     async fn handle(&mut self, msg: Dec, _ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
 ///         self.dec(msg.amount)
 ///     }
 /// }
+///
+/// impl kameo::message::Message<Reset> for Counter {
+///     type Reply = i64;
+///
+///     async fn handle(&mut self, msg: Reset, _ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.reset(msg).await
+///     }
+/// }
 /// ```
 /// </details>
 #[proc_macro_attribute]
@@ -82,14 +137,14 @@ pub fn messages(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Derive macro implementing the [Actor](https://docs.rs/kameo/latest/kameo/actor/trait.Actor.html) trait with default behaviour.
 ///
 /// The `#[actor(name = "...")]` attribute can be specified to change the actors [Actor::name](https://docs.rs/kameo/latest/kameo/actor/trait.Actor.html#method.name).
-/// The default value is the actor's ident.
+/// The default value is the actor's ident, without any generic parameters.
+/// The name can also be overridden at runtime with [set_name](https://docs.rs/kameo/latest/kameo/actor/fn.set_name.html).
 ///
 /// The `#[actor(mailbox = ...)]` attribute can be specified to change the actors [Actor::Mailbox](https://docs.rs/kameo/latest/kameo/actor/trait.Actor.html#associatedtype.Mailbox).
 /// The values can be one of:
 ///  - `bounded` (default capacity of 1000)
 ///  - `bounded(64)` (custom capacity of 64)
 ///  - `unbounded`
-
 ///
 /// # Example
 ///
@@ -100,7 +155,7 @@ pub fn messages(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// #[actor(name = "my_amazing_actor", mailbox = bounded(256))]
 /// struct MyActor { }
 ///
-/// assert_eq!(MyActor { }.name(), "MyActor");
+/// assert_eq!(MyActor::name(), "my_amazing_actor");
 /// ```
 #[proc_macro_derive(Actor, attributes(actor))]
 pub fn derive_actor(input: TokenStream) -> TokenStream {
@@ -132,12 +187,12 @@ pub fn derive_reply(input: TokenStream) -> TokenStream {
 /// # Example
 ///
 /// ```
-/// use kameo::RemoteActor;
+/// use kameo::{remote::RemoteActor, RemoteActor};
 ///
 /// #[derive(RemoteActor)]
 /// struct MyActor { }
 ///
-/// assert_eq!(MyActor::REMOTE_ID, "my_crate::module::MyActor");
+/// assert_eq!(MyActor::REMOTE_ID, concat!(module_path!(), "::MyActor"));
 /// ```
 #[proc_macro_derive(RemoteActor, attributes(remote_actor))]
 pub fn derive_remote_actor(input: TokenStream) -> TokenStream {
@@ -150,13 +205,42 @@ pub fn derive_remote_actor(input: TokenStream) -> TokenStream {
 /// # Example
 ///
 /// ```
-/// use kameo::{remote_message, message::Message};
+/// use kameo::{remote_message, message::{Context, Message}, Actor, RemoteActor};
+/// use serde::{Deserialize, Serialize};
 ///
+/// #[derive(Actor, RemoteActor)]
 /// struct MyActor { }
+///
+/// #[derive(Serialize, Deserialize)]
 /// struct MyMessage { }
 ///
 /// #[remote_message("c6fa9f76-8818-4000-96f4-50c2ebd52408")]
-/// impl Message<MyMessage> for MyActor { ... }
+/// impl Message<MyMessage> for MyActor {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, msg: MyMessage, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// }
+/// ```
+///
+/// Messages are serialized with MessagePack by default. A different codec can be specified with `codec = ...`:
+///
+/// ```
+/// use kameo::remote::Json;
+/// # use kameo::{remote_message, message::{Context, Message}, Actor, RemoteActor};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// # #[derive(Actor, RemoteActor)]
+/// # struct MyActor { }
+/// #
+/// # #[derive(Serialize, Deserialize)]
+/// # struct MyMessage { }
+///
+/// #[remote_message("c6fa9f76-8818-4000-96f4-50c2ebd52408", codec = Json)]
+/// impl Message<MyMessage> for MyActor {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, msg: MyMessage, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn remote_message(attrs: TokenStream, input: TokenStream) -> TokenStream {
@@ -14,9 +14,62 @@ pub struct Messages {
     item_impl: ItemImpl,
     ident: Ident,
     messages: Vec<Message>,
+    handlers: Vec<Handler>,
     errors: Option<syn::Error>,
 }
 
+/// A method marked with `#[handler]`, which handles an existing message type.
+struct Handler {
+    sig: Signature,
+    msg_ty: Box<Type>,
+    takes_ctx: bool,
+}
+
+impl TryFrom<Signature> for Handler {
+    type Error = syn::Error;
+
+    fn try_from(sig: Signature) -> Result<Self, Self::Error> {
+        if !matches!(sig.inputs.first(), Some(FnArg::Receiver(_))) {
+            return Err(syn::Error::new(
+                sig.span(),
+                "handlers must take &mut self or &self",
+            ));
+        }
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                sig.generics.span(),
+                "handlers cannot have generic parameters",
+            ));
+        }
+
+        let params: Vec<_> = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Receiver(_) => None,
+                FnArg::Typed(pat_type) => Some(pat_type),
+            })
+            .collect();
+        let (msg_ty, takes_ctx) = match params.as_slice() {
+            [msg] => (msg.ty.clone(), false),
+            [msg, _ctx] => (msg.ty.clone(), true),
+            _ => {
+                return Err(syn::Error::new(
+                    sig.inputs.span(),
+                    "handlers must take the message, and optionally the context, as parameters",
+                ))
+            }
+        };
+        validate_param(&msg_ty)?;
+
+        Ok(Handler {
+            sig,
+            msg_ty,
+            takes_ctx,
+        })
+    }
+}
+
 #[derive(Clone)]
 struct Message {
     vis: Visibility,
@@ -82,6 +135,42 @@ impl
 }
 
 impl Messages {
+    fn extract_handlers(item_impl: &mut ItemImpl) -> (Vec<Handler>, Vec<syn::Error>) {
+        let mut handlers = Vec::new();
+        let mut errors = Vec::new();
+        for item in &mut item_impl.items {
+            let ImplItem::Fn(impl_item_fn) = item else {
+                continue;
+            };
+            let attrs_len = impl_item_fn.attrs.len();
+            impl_item_fn
+                .attrs
+                .retain(|attr| !matches!(&attr.meta, Meta::Path(path) if path.is_ident("handler")));
+            if impl_item_fn.attrs.len() == attrs_len {
+                continue;
+            }
+
+            let is_message = impl_item_fn
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("message"));
+            if is_message {
+                errors.push(syn::Error::new(
+                    impl_item_fn.sig.span(),
+                    "methods cannot be both a message and a handler",
+                ));
+                continue;
+            }
+
+            match Handler::try_from(impl_item_fn.sig.clone()) {
+                Ok(handler) => handlers.push(handler),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        (handlers, errors)
+    }
+
     fn extract_messages(item_impl: &mut ItemImpl) -> (Vec<Message>, Option<syn::Error>) {
         let mut errors = Vec::new();
         let messages = item_impl
@@ -369,6 +458,55 @@ impl Messages {
             #( #msg_impls )*
         }
     }
+
+    fn expand_handler_impls(&self) -> proc_macro2::TokenStream {
+        let Self {
+            item_impl,
+            ident: actor_ident,
+            handlers,
+            ..
+        } = self;
+        let (impl_generics, actor_ty_generics, where_clause) = item_impl.generics.split_for_impl();
+
+        let handler_impls = handlers.iter().map(
+            |Handler {
+                 sig,
+                 msg_ty,
+                 takes_ctx,
+             }| {
+                let fn_ident = &sig.ident;
+                let reply = match sig.output.clone() {
+                    ReturnType::Default => parse_quote_spanned! {sig.output.span()=>
+                        ()
+                    },
+                    ReturnType::Type(_, ty) => ty,
+                };
+                let await_tokens = sig.asyncness.map(|_| quote_spanned! {sig.asyncness.span()=>
+                    .await
+                });
+                let (ctx_param, ctx_arg) = if *takes_ctx {
+                    (quote! { ctx }, Some(quote! { , ctx }))
+                } else {
+                    (quote! { _ctx }, None)
+                };
+
+                quote_spanned! {sig.span()=>
+                    #[automatically_derived]
+                    impl #impl_generics ::kameo::message::Message<#msg_ty> for #actor_ident #actor_ty_generics #where_clause {
+                        type Reply = #reply;
+
+                        async fn handle(&mut self, msg: #msg_ty, #ctx_param: ::kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
+                            self.#fn_ident(msg #ctx_arg) #await_tokens
+                        }
+                    }
+                }
+            },
+        );
+
+        quote! {
+            #( #handler_impls )*
+        }
+    }
 }
 
 impl ToTokens for Messages {
@@ -376,6 +514,7 @@ impl ToTokens for Messages {
         let item_impl = &self.item_impl;
         let msg_enum = self.expand_msgs();
         let msg_impl_message = self.expand_msg_impls();
+        let handler_impls = self.expand_handler_impls();
         let errors = self.errors.clone().map(|err| err.into_compile_error());
 
         tokens.extend(quote! {
@@ -383,6 +522,7 @@ impl ToTokens for Messages {
 
             #msg_enum
             #msg_impl_message
+            #handler_impls
             #errors
         });
     }
@@ -408,12 +548,21 @@ impl Parse for Messages {
                 ))
             }
         };
+        let (handlers, handler_errors) = Messages::extract_handlers(&mut item_impl);
         let (messages, errors) = Messages::extract_messages(&mut item_impl);
+        let errors = handler_errors
+            .into_iter()
+            .chain(errors)
+            .reduce(|mut errors, err| {
+                errors.combine(err);
+                errors
+            });
 
         Ok(Messages {
             item_impl,
             ident,
             messages,
+            handlers,
             errors,
         })
     }
@@ -4,13 +4,14 @@ use syn::{
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
-    AngleBracketedGenericArguments, GenericArgument, Generics, ItemImpl, LitStr, PathArguments,
-    PathSegment, Token, Type,
+    AngleBracketedGenericArguments, GenericArgument, Generics, Ident, ItemImpl, LitStr, Path,
+    PathArguments, PathSegment, Token, Type,
 };
 use uuid::Uuid;
 
 pub struct RemoteMessageAttrs {
     id: LitStr,
+    codec: Option<Path>,
 }
 
 impl Parse for RemoteMessageAttrs {
@@ -22,7 +23,17 @@ impl Parse for RemoteMessageAttrs {
                 format!("expected remote message id\nhere's a random uuid you can use:\n  #[remote_message(\"{random_uuid}\")]"),
             ));
         }
-        Ok(RemoteMessageAttrs { id: input.parse()? })
+        let id = input.parse()?;
+        let mut codec = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "codec" {
+                return Err(syn::Error::new(key.span(), "expected `codec`"));
+            }
+            input.parse::<Token![=]>()?;
+            codec = Some(input.parse()?);
+        }
+        Ok(RemoteMessageAttrs { id, codec })
     }
 }
 
@@ -35,7 +46,10 @@ pub struct RemoteMessage {
 
 impl RemoteMessage {
     pub fn into_tokens(self, attrs: RemoteMessageAttrs) -> TokenStream {
-        let RemoteMessageAttrs { id } = attrs;
+        let RemoteMessageAttrs { id, codec } = attrs;
+        let codec = codec
+            .map(|codec| quote! { #codec })
+            .unwrap_or_else(|| quote! { ::kameo::remote::MessagePack });
         let Self {
             item_impl,
             actor_ty,
@@ -51,6 +65,7 @@ impl RemoteMessage {
             #[automatically_derived]
             impl #impl_generics ::kameo::remote::RemoteMessage<#message_generics> for #actor_ty #ty_generics #where_clause {
                 const REMOTE_ID: &'static str = #id;
+                type Codec = #codec;
             }
 
             const _: () = {
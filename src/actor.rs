@@ -22,24 +22,60 @@
 //! [`on_panic`]: Actor::on_panic
 
 mod actor_ref;
+mod cached;
+mod closure;
+pub mod fsm;
+pub mod group;
+mod guard;
 mod id;
 mod kind;
+mod limit;
+mod local;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod name;
+mod pipe;
 pub mod pool;
 pub mod pubsub;
+mod registry;
+mod schedule;
+mod scope;
 mod spawn;
+mod task;
+mod team;
+#[cfg(feature = "message-trace")]
+mod trace;
 
-use std::any;
+use std::{any, time::Duration};
 
 use futures::Future;
 
 use crate::{
     error::{ActorStopReason, BoxError, PanicError},
-    mailbox::Mailbox,
+    mailbox::{Mailbox, UndeliveredMessage},
 };
 
 pub use actor_ref::*;
+pub use cached::CachedActorRef;
+pub use closure::{spawn_fn, FnActor};
+pub use guard::{spawn_guarded, SpawnGuard};
 pub use id::*;
+pub(crate) use kind::StateWaiter;
+pub use limit::{set_spawn_limit, try_spawn_limited};
+pub use local::LocalRunner;
+pub use name::{name, set_name};
+pub use pipe::pipe_into;
+#[cfg(feature = "test-util")]
+pub(crate) use registry::shutdown_where;
+pub use registry::{join, join_all_detached, shutdown_all};
+pub use schedule::{ScheduledMessage, ScheduledMessageId, ScheduledMessages};
+pub(crate) use scope::sibling;
+pub use scope::{scope, scope_with_policy, ChildPanicPolicy, Scope};
 pub use spawn::*;
+pub use task::{supervise_task, TaskActor};
+pub use team::link_team;
+#[cfg(feature = "message-trace")]
+pub use trace::RecordedMessage;
 
 /// Core behavior of an actor, including its lifecycle events and how it processes messages.
 ///
@@ -119,12 +155,29 @@ pub trait Actor: Sized + Send + 'static {
 
     /// The name of the actor, which can be useful for logging or debugging.
     ///
+    /// The name can be overridden at runtime with [`set_name`].
+    ///
+    /// With the `metrics` feature enabled, the name is used as the `actor` label of the metrics emitted for
+    /// each actor: `kameo_actors_alive`, `kameo_messages_processed_total`, `kameo_handler_duration_seconds`
+    /// and `kameo_mailbox_depth`.
+    ///
     /// # Default Implementation
     /// By default, this returns the type name of the actor.
     fn name() -> &'static str {
         any::type_name::<Self>()
     }
 
+    /// Returns a human readable summary of what the actor is currently doing, such as its state or progress.
+    ///
+    /// This is called between messages when requested with [`ActorRef::describe`], for example to power an admin
+    /// dashboard. It should be cheap, since the actor doesn't handle any messages while it runs.
+    ///
+    /// # Default Implementation
+    /// By default, this returns the actor's [`name`](Actor::name). [`Fsm`](fsm::Fsm) actors return their current state.
+    fn describe(&self) -> String {
+        name::<Self>().to_string()
+    }
+
     /// Creates a new mailbox for the actor. This sets up the message queue and receiver for the actor.
     ///
     /// # Returns
@@ -135,6 +188,414 @@ pub trait Actor: Sized + Send + 'static {
         Self::Mailbox::default_mailbox()
     }
 
+    /// The duration after which a single message handler is considered slow.
+    ///
+    /// When a handler takes longer than this threshold, a warning is logged with the message type name
+    /// and elapsed time. The handler is never aborted, this is purely for observability, and is useful for
+    /// finding handlers which accidentally block.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`, disabling slow handler detection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::Actor;
+    /// use kameo::mailbox::bounded::BoundedMailbox;
+    ///
+    /// struct MyActor;
+    ///
+    /// impl Actor for MyActor {
+    ///     type Mailbox = BoundedMailbox<Self>;
+    ///
+    ///     fn slow_handler_threshold() -> Option<Duration> {
+    ///         Some(Duration::from_millis(100))
+    ///     }
+    /// }
+    /// ```
+    fn slow_handler_threshold() -> Option<Duration> {
+        None
+    }
+
+    /// Whether each message handled by the actor is traced.
+    ///
+    /// When `true`, each handler runs in a `handle_message` span at the `TRACE` level, with the actor's id and name,
+    /// the message type name, and the sending actor's id if it was sent by an actor. Once the handler finishes, a
+    /// trace is logged within the span with the elapsed time. This is much finer than the lifecycle traces, and is
+    /// intended for debugging rather than production.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::mailbox::bounded::BoundedMailbox;
+    ///
+    /// struct MyActor;
+    ///
+    /// impl Actor for MyActor {
+    ///     type Mailbox = BoundedMailbox<Self>;
+    ///
+    ///     fn trace_messages() -> bool {
+    ///         cfg!(debug_assertions)
+    ///     }
+    /// }
+    /// ```
+    fn trace_messages() -> bool {
+        false
+    }
+
+    /// The duration after which the actor's [`on_start`](Actor::on_start) hook is considered slow.
+    ///
+    /// When startup takes longer than this threshold, a warning is logged with the elapsed time.
+    /// Regardless of this threshold, the startup duration is available in [`ActorStats`](crate::actor::ActorStats::startup_duration).
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`, disabling slow startup warnings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::Actor;
+    /// use kameo::actor::ActorRef;
+    /// use kameo::error::BoxError;
+    /// use kameo::mailbox::bounded::BoundedMailbox;
+    ///
+    /// struct Connection;
+    ///
+    /// impl Actor for Connection {
+    ///     type Mailbox = BoundedMailbox<Self>;
+    ///
+    ///     async fn on_start(&mut self, _: ActorRef<Self>) -> Result<(), BoxError> {
+    ///         tokio::time::sleep(Duration::from_millis(20)).await; // Establishing a connection
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn slow_startup_threshold() -> Option<Duration> {
+    ///         Some(Duration::from_millis(10))
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Connection);
+    /// actor_ref.wait_startup().await;
+    /// assert!(actor_ref.stats().startup_duration >= Some(Duration::from_millis(20)));
+    /// # });
+    /// ```
+    fn slow_startup_threshold() -> Option<Duration> {
+        None
+    }
+
+    /// Whether the actor should stop when it is no longer referenced.
+    ///
+    /// When `true`, the actor is stopped gracefully once all [`ActorRef`]s have been dropped, since no one can
+    /// send it messages anymore. [`WeakActorRef`]s do not keep the actor alive. This models actors which
+    /// automatically despawn when orphaned.
+    ///
+    /// When `false`, the actor keeps running until it is explicitly stopped or killed, even if only
+    /// weak references remain.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `true`.
+    fn stop_when_unreferenced() -> bool {
+        true
+    }
+
+    /// The maximum [`size_hint`](crate::message::Message::size_hint) of messages accepted by the actor,
+    /// including read messages with [`ReadMessage::size_hint`](crate::message::ReadMessage::size_hint).
+    ///
+    /// Sending a larger message fails with [`SendError::TooLarge`](crate::error::SendError::TooLarge), returning
+    /// the message without it being queued. Messages received from remote peers are also rejected with
+    /// [`RemoteSendError::TooLarge`](crate::error::RemoteSendError::TooLarge) if their encoded payload
+    /// is larger than this, before being deserialized.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`, accepting messages of any size.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::error::SendError;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// struct Store;
+    ///
+    /// impl Actor for Store {
+    ///     type Mailbox = kameo::mailbox::bounded::BoundedMailbox<Self>;
+    ///
+    ///     fn max_message_size() -> Option<usize> {
+    ///         Some(1024)
+    ///     }
+    /// }
+    ///
+    /// struct Put(Vec<u8>);
+    ///
+    /// impl Message<Put> for Store {
+    ///     type Reply = usize;
+    ///
+    ///     fn size_hint(msg: &Put) -> usize {
+    ///         msg.0.len()
+    ///     }
+    ///
+    ///     async fn handle(&mut self, msg: Put, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         msg.0.len()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let store = kameo::spawn(Store);
+    ///
+    /// assert_eq!(store.ask(Put(vec![0; 16])).await?, 16);
+    /// match store.ask(Put(vec![0; 4096])).await {
+    ///     Err(SendError::TooLarge(Put(bytes))) => assert_eq!(bytes.len(), 4096),
+    ///     other => panic!("unexpected result: {other:?}"),
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn max_message_size() -> Option<usize> {
+        None
+    }
+
+    /// The maximum number of [`ReadMessage`](crate::message::ReadMessage)s handled concurrently.
+    ///
+    /// Once this many reads are running, the actor waits for one to finish before receiving more signals.
+    ///
+    /// # Default Implementation
+    /// By default, up to 16 reads run at a time.
+    fn max_concurrent_reads() -> usize {
+        16
+    }
+
+    /// Thresholds for shedding load when the actor's mailbox stays too full.
+    ///
+    /// Once the mailbox has held at least [`high_watermark`](OverloadConfig::high_watermark) signals for
+    /// [`duration`](OverloadConfig::duration), new messages are rejected with
+    /// [`SendError::Overloaded`](crate::error::SendError::Overloaded), returning the message without it being queued.
+    /// Messages are accepted again once the mailbox has drained to [`low_watermark`](OverloadConfig::low_watermark).
+    /// Callers can then back off, or route the message elsewhere.
+    ///
+    /// The mailbox length is only checked when messages are sent, so the duration is measured from the first send
+    /// which saw the mailbox above the high watermark. Messages the actor sends to itself are never rejected.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`, never shedding load.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::actor::OverloadConfig;
+    /// use kameo::error::SendError;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    /// use kameo::Actor;
+    ///
+    /// #[derive(Default)]
+    /// struct Worker {
+    ///     processed: u32,
+    /// }
+    ///
+    /// impl Actor for Worker {
+    ///     type Mailbox = kameo::mailbox::unbounded::UnboundedMailbox<Self>;
+    ///
+    ///     fn overload_config() -> Option<OverloadConfig> {
+    ///         Some(OverloadConfig {
+    ///             high_watermark: 2,
+    ///             low_watermark: 0,
+    ///             duration: Duration::ZERO,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// struct Work;
+    ///
+    /// impl Message<Work> for Worker {
+    ///     type Reply = u32;
+    ///
+    ///     async fn handle(&mut self, _: Work, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.processed += 1;
+    ///         self.processed
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// // Messages queue up until the actor is spawned
+    /// let prepared_actor = kameo::actor::prepare(Worker::default());
+    /// let actor_ref = prepared_actor.actor_ref_owned();
+    /// actor_ref.tell(Work).send().await?;
+    /// actor_ref.tell(Work).send().await?;
+    /// assert!(matches!(actor_ref.tell(Work).send().await, Err(SendError::Overloaded(Work))));
+    ///
+    /// // Once drained, messages are accepted again
+    /// prepared_actor.spawn();
+    /// actor_ref.await_state(|worker| worker.processed == 2, Duration::from_secs(1)).await?;
+    /// assert_eq!(actor_ref.ask(Work).await?, 3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn overload_config() -> Option<OverloadConfig> {
+        None
+    }
+
+    /// Whether the actor's lifecycle events are logged.
+    ///
+    /// When `false`, the traces logged when the actor starts and stops are skipped, which is useful for silencing
+    /// chatty short-lived actors. Actors which stop due to a panic are still logged, at the
+    /// [`panic_log_level`](Actor::panic_log_level).
+    ///
+    /// Lifecycle events are logged with the actor's [`name`](Actor::name) as a field, which can also be used
+    /// to filter them in a subscriber.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `true`.
+    fn log_lifecycle() -> bool {
+        true
+    }
+
+    /// The level at which the actor stopping due to a panic is logged.
+    ///
+    /// Actors which panic as part of an expected recovery flow, such as restarting from [`on_panic`](Actor::on_panic)
+    /// or under a supervisor, can lower this to keep the panics out of error dashboards.
+    ///
+    /// # Default Implementation
+    /// By default, this returns [`Level::ERROR`](tracing::Level::ERROR).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    /// use tracing::Level;
+    ///
+    /// struct Flaky;
+    ///
+    /// impl Actor for Flaky {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     fn panic_log_level() -> Level {
+    ///         Level::WARN
+    ///     }
+    /// }
+    /// ```
+    fn panic_log_level() -> tracing::Level {
+        tracing::Level::ERROR
+    }
+
+    /// Wraps the actor's entire task, including `on_start`, every message handler, and `on_stop`.
+    ///
+    /// This is useful for installing task-locals, tracing spans, or other instrumentation around all of the
+    /// actor's execution, such as propagating a request or tenant context. It is called by both
+    /// [`spawn`] and [`PreparedActor::run`], and is inlined away when not overridden.
+    ///
+    /// # Default Implementation
+    /// By default, the future is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::Future;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::mailbox::bounded::BoundedMailbox;
+    /// use kameo::Actor;
+    ///
+    /// tokio::task_local! {
+    ///     static TENANT: &'static str;
+    /// }
+    ///
+    /// struct MyActor;
+    ///
+    /// impl Actor for MyActor {
+    ///     type Mailbox = BoundedMailbox<Self>;
+    ///
+    ///     fn task_wrapper<F>(fut: F) -> impl Future<Output = F::Output> + Send
+    ///     where
+    ///         F: Future + Send,
+    ///     {
+    ///         TENANT.scope("acme", fut)
+    ///     }
+    /// }
+    ///
+    /// struct Tenant;
+    ///
+    /// impl Message<Tenant> for MyActor {
+    ///     type Reply = &'static str;
+    ///
+    ///     async fn handle(&mut self, _: Tenant, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         TENANT.get()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// assert_eq!(actor_ref.ask(Tenant).await?, "acme");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    ///
+    /// [`PreparedActor::run`]: crate::actor::PreparedActor::run
+    #[inline]
+    fn task_wrapper<F>(fut: F) -> impl Future<Output = F::Output> + Send
+    where
+        F: Future + Send,
+    {
+        fut
+    }
+
+    /// The number of recent messages recorded by the actor for debugging.
+    ///
+    /// Recent messages can be retrieved with [`ActorRef::recent_messages`], and are attached to the
+    /// [`PanicError`] if the actor panics, making it easier to see what led to a crash.
+    ///
+    /// # Default Implementation
+    /// By default, the 16 most recent messages are recorded. Returning `0` disables recording.
+    #[cfg(feature = "message-trace")]
+    fn message_trace_capacity() -> usize {
+        16
+    }
+
+    /// Which parts of the actor's lifecycle are wrapped in `catch_unwind`.
+    ///
+    /// Panics caught in `on_start` or a message handler stop the actor with [`ActorStopReason::Panicked`],
+    /// giving [`on_panic`] a chance to recover. Disabling a boundary lets panics propagate to the actor's task instead,
+    /// which avoids the `catch_unwind` overhead for actors which never panic or run with `panic = "abort"`,
+    /// and for runtimes which do not support unwinding across `.await` points.
+    ///
+    /// # Default Implementation
+    /// By default, this returns [`UnwindPolicy::DEFAULT`], catching panics in `on_start` and message handlers,
+    /// but not in `on_stop`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::UnwindPolicy;
+    /// use kameo::mailbox::bounded::BoundedMailbox;
+    /// use kameo::Actor;
+    ///
+    /// struct MyActor;
+    ///
+    /// impl Actor for MyActor {
+    ///     type Mailbox = BoundedMailbox<Self>;
+    ///
+    ///     fn unwind_policy() -> UnwindPolicy {
+    ///         UnwindPolicy::NONE
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`on_panic`]: Actor::on_panic
+    fn unwind_policy() -> UnwindPolicy {
+        UnwindPolicy::DEFAULT
+    }
+
     /// Called when the actor starts, before it processes any messages.
     ///
     /// Messages sent internally by the actor during `on_start` are prioritized and processed
@@ -157,6 +618,9 @@ pub trait Actor: Sized + Send + 'static {
     /// # Parameters
     /// - `err`: The panic or error that occurred.
     ///
+    /// If `err` is a panic, its [`location`](PanicError::location) depends on kameo's panic hook still being
+    /// installed, which replacing the hook with [`std::panic::set_hook`] after actors have started prevents.
+    ///
     /// # Returns
     /// - `Some(ActorStopReason)`: Stops the actor.
     /// - `None`: Allows the actor to continue processing messages.
@@ -174,8 +638,76 @@ pub trait Actor: Sized + Send + 'static {
     /// By default, the actor will stop if the reason for the linked actor's death is anything other
     /// than `Normal`. You can customize this behavior in the implementation.
     ///
+    /// The returned reason becomes this actor's own stop reason: it is passed to [`on_stop`](Actor::on_stop),
+    /// and sent to this actor's links in turn. Supervisors can return a transformed reason rather than the linked
+    /// actor's raw one, such as a [custom](ActorStopReason::custom) reason adding context about the failure,
+    /// so failures gain context as they propagate up a tree of linked actors.
+    /// The original failure can still be found with [`ActorStopReason::root_cause`].
+    ///
     /// # Returns
     /// Whether the actor should stop or continue processing messages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{ActorID, ActorRef, WeakActorRef};
+    /// use kameo::error::{ActorStopReason, BoxError};
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::Actor;
+    ///
+    /// #[derive(Debug)]
+    /// struct ServiceFailed {
+    ///     service: &'static str,
+    ///     cause: ActorStopReason,
+    /// }
+    ///
+    /// struct Supervisor;
+    ///
+    /// impl Actor for Supervisor {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_link_died(
+    ///         &mut self,
+    ///         _: WeakActorRef<Self>,
+    ///         _: ActorID,
+    ///         reason: ActorStopReason,
+    ///     ) -> Result<Option<ActorStopReason>, BoxError> {
+    ///         Ok(Some(ActorStopReason::custom(ServiceFailed {
+    ///             service: "database",
+    ///             cause: reason,
+    ///         })))
+    ///     }
+    /// }
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Database;
+    ///
+    /// struct Crash;
+    ///
+    /// impl Message<Crash> for Database {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Crash, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         panic!("connection lost");
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared = kameo::actor::prepare(Supervisor);
+    /// let supervisor = prepared.actor_ref().clone();
+    /// let supervisor_handle = prepared.spawn();
+    /// let database = kameo::spawn(Database);
+    /// supervisor.link(&database).await;
+    ///
+    /// database.tell(Crash).await?;
+    /// let (_, reason) = supervisor_handle.await?;
+    /// let failure = reason.downcast_custom_ref::<ServiceFailed>().unwrap();
+    /// assert_eq!(failure.service, "database");
+    /// assert!(matches!(failure.cause.root_cause(), ActorStopReason::Panicked(_)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
     #[allow(unused_variables)]
     fn on_link_died(
         &mut self,
@@ -187,8 +719,10 @@ pub trait Actor: Sized + Send + 'static {
             match &reason {
                 ActorStopReason::Normal => Ok(None),
                 ActorStopReason::Killed
+                | ActorStopReason::KilledImmediately
                 | ActorStopReason::Panicked(_)
-                | ActorStopReason::LinkDied { .. } => Ok(Some(ActorStopReason::LinkDied {
+                | ActorStopReason::LinkDied { .. }
+                | ActorStopReason::Custom(_) => Ok(Some(ActorStopReason::LinkDied {
                     id,
                     reason: Box::new(reason),
                 })),
@@ -196,12 +730,187 @@ pub trait Actor: Sized + Send + 'static {
         }
     }
 
+    /// Called for each message still queued in the actor's mailbox or left in its
+    /// [stash](crate::message::Context::stash) when it stops, before [`Actor::on_stop`].
+    ///
+    /// By default, undelivered messages are dropped. This hook can be used to log, persist, or re-route them.
+    /// Callers of `ask` requests are sent [`SendError::ActorStopped`](crate::error::SendError::ActorStopped)
+    /// once the message is dropped, unless it is handed off to another instance of the actor with
+    /// [`UndeliveredMessage::redeliver`]. Messages sent while the mailbox is being drained may still be dropped,
+    /// and the hook is not called if the actor is killed with [`ActorRef::kill_immediate`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use kameo::mailbox::{unbounded::UnboundedMailbox, UndeliveredMessage};
+    /// use kameo::message::{Context, Message};
+    /// use kameo::Actor;
+    /// use tokio::sync::oneshot;
+    ///
+    /// struct Worker {
+    ///     busy: Option<oneshot::Sender<()>>,
+    ///     undelivered: Arc<Mutex<Vec<u32>>>,
+    /// }
+    ///
+    /// impl Actor for Worker {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_undelivered(&mut self, msg: UndeliveredMessage<Self>) {
+    ///         if let Some(Job(n)) = msg.downcast() {
+    ///             self.undelivered.lock().unwrap().push(n);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct Job(u32);
+    ///
+    /// impl Message<Job> for Worker {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Job, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let _ = self.busy.take().unwrap().send(());
+    ///         std::future::pending().await // A job which never finishes
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (busy_tx, busy_rx) = oneshot::channel();
+    /// let undelivered = Arc::new(Mutex::new(Vec::new()));
+    /// let prepared = kameo::actor::prepare(Worker {
+    ///     busy: Some(busy_tx),
+    ///     undelivered: undelivered.clone(),
+    /// });
+    /// let actor_ref = prepared.actor_ref().clone();
+    /// let join_handle = prepared.spawn();
+    ///
+    /// for n in 0..3 {
+    ///     actor_ref.tell(Job(n)).await?;
+    /// }
+    /// busy_rx.await?;
+    /// actor_ref.kill();
+    /// join_handle.await?;
+    /// assert_eq!(*undelivered.lock().unwrap(), [1, 2]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[allow(unused_variables)]
+    fn on_undelivered(&mut self, msg: UndeliveredMessage<Self>) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Called with the number of messages discarded by a lossy mailbox since the last call.
+    ///
+    /// Mailboxes which drop messages instead of queueing them, such as when they're full, report each dropped message
+    /// with [`Signal::discard`](crate::mailbox::Signal::discard). The drops are counted, and this hook is called
+    /// before the actor handles its next message, so it never runs concurrently with a handler.
+    /// By default, it does nothing. This is useful for telemetry on streams where some loss is acceptable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::mailbox::{unbounded::UnboundedMailbox, Signal};
+    /// use kameo::message::{Context, Message};
+    /// use kameo::Actor;
+    ///
+    /// #[derive(Default)]
+    /// struct Telemetry {
+    ///     lost: u64,
+    /// }
+    ///
+    /// impl Actor for Telemetry {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     fn on_message_dropped(&mut self, count: u64) {
+    ///         self.lost += count;
+    ///     }
+    /// }
+    ///
+    /// struct Sample(f64);
+    ///
+    /// impl Message<Sample> for Telemetry {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Sample, _: Context<'_, Self, Self::Reply>) -> Self::Reply {}
+    /// }
+    ///
+    /// struct Lost;
+    ///
+    /// impl Message<Lost> for Telemetry {
+    ///     type Reply = u64;
+    ///
+    ///     async fn handle(&mut self, _: Lost, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.lost
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Telemetry::default());
+    ///
+    /// // What a lossy mailbox does with a message it has no room for
+    /// let signal = Signal::Message {
+    ///     message: Box::new(Sample(0.5)),
+    ///     actor_ref: actor_ref.clone(),
+    ///     reply: None,
+    ///     sent_within_actor: false,
+    ///     options: None,
+    /// };
+    /// signal.discard();
+    ///
+    /// assert_eq!(actor_ref.ask(Lost).await?, 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[allow(unused_variables)]
+    fn on_message_dropped(&mut self, count: u64) {}
+
     /// Called before the actor stops.
     ///
     /// This allows the actor to perform any necessary cleanup or release resources before being fully stopped.
     ///
+    /// `on_stop` runs however the actor stops, including when it is killed with [`ActorRef::kill`].
+    /// Dropping the `JoinHandle` returned when spawning an actor does not stop it.
+    /// However, if the actor's task itself is cancelled, such as with [`JoinHandle::abort`](tokio::task::JoinHandle::abort)
+    /// or by shutting down its runtime, `on_stop` cannot run. The actor is still marked as killed,
+    /// so anything waiting for it to stop is notified, and the actor is dropped. Cleanup that must always happen
+    /// belongs in a [`Drop`] impl.
+    ///
     /// # Parameters
     /// - `reason`: The reason why the actor is being stopped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+    ///
+    /// use kameo::actor::AnyActorRef;
+    /// use kameo::error::ActorStopReason;
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Connection {
+    ///     closed: Arc<AtomicBool>,
+    /// }
+    ///
+    /// impl Drop for Connection {
+    ///     fn drop(&mut self) {
+    ///         // Runs even when the actor's task is aborted
+    ///         self.closed.store(true, Ordering::SeqCst);
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let closed = Arc::new(AtomicBool::new(false));
+    /// let prepared = kameo::actor::prepare(Connection { closed: closed.clone() });
+    /// let actor_ref = AnyActorRef::from(prepared.actor_ref().clone());
+    /// let join_handle = prepared.spawn();
+    ///
+    /// join_handle.abort();
+    /// assert!(matches!(join_handle.await, Err(err) if err.is_cancelled()));
+    /// assert!(closed.load(Ordering::SeqCst));
+    /// assert!(matches!(actor_ref.wait_for_stop().await, ActorStopReason::Killed));
+    /// # });
+    /// ```
     #[allow(unused_variables)]
     fn on_stop(
         &mut self,
@@ -211,3 +920,107 @@ pub trait Actor: Sized + Send + 'static {
         async { Ok(()) }
     }
 }
+
+/// An actor which is created from initialization arguments, spawned with [`spawn_with_args`].
+///
+/// This separates the data needed to build an actor from the actor itself, such as a connection string
+/// which becomes a live connection. Unlike [`Actor::on_start`], which runs on an actor that already exists,
+/// [`init`](ActorInit::init) builds the actor, so its fields don't need to be wrapped in `Option` until startup.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::ActorInit;
+/// use kameo::error::BoxError;
+/// use kameo::Actor;
+///
+/// # struct Connection;
+/// # impl Connection {
+/// #     async fn connect(_: &str) -> Result<Self, BoxError> { Ok(Connection) }
+/// # }
+/// #[derive(Actor)]
+/// struct Database {
+///     conn: Connection,
+/// }
+///
+/// impl ActorInit for Database {
+///     type Args = String;
+///
+///     async fn init(url: Self::Args) -> Result<Self, BoxError> {
+///         let conn = Connection::connect(&url).await?;
+///         Ok(Database { conn })
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let db = kameo::actor::spawn_with_args::<Database>("postgres://localhost".to_string()).await?;
+/// # Ok::<(), BoxError>(())
+/// # });
+/// ```
+pub trait ActorInit: Actor {
+    /// The arguments the actor is created from.
+    type Args: Send + 'static;
+
+    /// Creates the actor from its arguments.
+    ///
+    /// An error is returned by [`spawn_with_args`], without the actor being spawned.
+    fn init(args: Self::Args) -> impl Future<Output = Result<Self, BoxError>> + Send;
+}
+
+/// Controls which parts of an actor's lifecycle are individually wrapped in `catch_unwind`.
+///
+/// See [`Actor::unwind_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnwindPolicy {
+    /// Catch panics in [`Actor::on_start`], stopping the actor with [`ActorStopReason::Panicked`].
+    pub on_start: bool,
+    /// Catch panics in message handlers and [`Actor::on_link_died`], stopping the actor with [`ActorStopReason::Panicked`].
+    pub handle: bool,
+    /// Catch panics in [`Actor::on_stop`].
+    ///
+    /// When caught, the actor's stop reason is still published to anyone waiting on it
+    /// before the panic is resumed.
+    pub on_stop: bool,
+}
+
+impl UnwindPolicy {
+    /// The default policy, catching panics in `on_start` and message handlers, but not `on_stop`.
+    pub const DEFAULT: Self = UnwindPolicy {
+        on_start: true,
+        handle: true,
+        on_stop: false,
+    };
+
+    /// Catches panics at every boundary.
+    pub const ALL: Self = UnwindPolicy {
+        on_start: true,
+        handle: true,
+        on_stop: true,
+    };
+
+    /// Never catches panics, letting them propagate to the actor's task.
+    pub const NONE: Self = UnwindPolicy {
+        on_start: false,
+        handle: false,
+        on_stop: false,
+    };
+}
+
+impl Default for UnwindPolicy {
+    fn default() -> Self {
+        UnwindPolicy::DEFAULT
+    }
+}
+
+/// Thresholds for shedding load when an actor's mailbox stays too full.
+///
+/// See [`Actor::overload_config`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OverloadConfig {
+    /// The mailbox length at or above which the actor is considered overloaded.
+    pub high_watermark: usize,
+    /// The mailbox length at or below which an overloaded actor accepts messages again.
+    pub low_watermark: usize,
+    /// How long the mailbox must stay at or above the high watermark before messages are rejected.
+    pub duration: Duration,
+}
@@ -1,10 +1,23 @@
-use std::{cell::Cell, collections::HashMap, fmt, ops, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    hash::{Hash, Hasher},
+    ops,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, OnceLock, PoisonError, Weak,
+    },
+    time::Duration,
+};
 
 use futures::{stream::AbortHandle, Stream, StreamExt};
 use tokio::{
-    sync::{Mutex, Semaphore},
+    sync::{oneshot, watch, Mutex, Notify, Semaphore},
     task::JoinHandle,
     task_local,
+    time::Instant,
 };
 
 #[cfg(feature = "remote")]
@@ -14,9 +27,9 @@ use std::marker::PhantomData;
 
 use crate::{
     error::{self, SendError},
-    mailbox::{Mailbox, SignalMailbox, WeakMailbox},
-    message::{Message, StreamMessage},
-    reply::Reply,
+    mailbox::{Mailbox, MessageOptions, Signal, SignalMailbox, WeakMailbox},
+    message::{Message, ReadMessage, StreamMessage},
+    reply::{Reply, ReplyOptions},
     request::{
         self, AskRequest, LocalAskRequest, LocalTellRequest, MessageSend, TellRequest,
         WithoutRequestTimeout,
@@ -24,10 +37,21 @@ use crate::{
     Actor,
 };
 
-use super::id::ActorID;
+use super::{
+    id::ActorID,
+    kind::{LoopState, StateWaiter},
+    OverloadConfig,
+};
 
 task_local! {
     pub(crate) static CURRENT_ACTOR_ID: ActorID;
+    /// The deadline of the message currently being handled, see [`Context::deadline`](crate::message::Context::deadline).
+    pub(crate) static CURRENT_DEADLINE: Option<std::time::Instant>;
+    /// The reply options of the message currently being handled, see [`Context::reply_options`](crate::message::Context::reply_options).
+    pub(crate) static CURRENT_REPLY_OPTIONS: ReplyOptions;
+    /// The actor which sent the message currently being handled, see [`Context::sender_id`](crate::message::Context::sender_id).
+    pub(crate) static CURRENT_SENDER_ID: Option<ActorID>;
+    pub(crate) static CURRENT_EXECUTION_MODE: super::ExecutionMode;
 }
 thread_local! {
     pub(crate) static CURRENT_THREAD_ACTOR_ID: Cell<Option<ActorID>> = const { Cell::new(None) };
@@ -38,12 +62,41 @@ thread_local! {
 /// An `ActorRef` allows interaction with an actor through message passing, both for asking (waiting for a reply)
 /// and telling (without waiting for a reply). It also provides utilities for managing the actor's state,
 /// such as checking if the actor is alive, registering the actor under a name, and stopping the actor gracefully.
+///
+/// Equality and hashing are by the actor's [`ActorID`], so refs compare equal if and only if they point to the same
+/// actor, and can be used as keys in a `HashMap` or `HashSet`. An `ActorRef` is also equal to a [`WeakActorRef`]
+/// to the same actor.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// # #[derive(kameo::Actor)]
+/// # struct MyActor;
+/// #
+/// # tokio_test::block_on(async {
+/// let a = kameo::spawn(MyActor);
+/// let b = kameo::spawn(MyActor);
+///
+/// let set = HashSet::from([a.clone(), a.clone(), b.clone()]);
+/// assert_eq!(set.len(), 2);
+/// assert_ne!(a, b);
+/// assert_eq!(a, a.downgrade());
+/// # });
+/// ```
 pub struct ActorRef<A: Actor> {
     id: ActorID,
     mailbox: A::Mailbox,
-    abort_handle: AbortHandle,
+    pub(crate) abort_handle: AbortHandle,
     pub(crate) links: Links,
     pub(crate) startup_semaphore: Arc<Semaphore>,
+    pub(crate) stop_reason: Arc<watch::Sender<Option<error::ActorStopReason>>>,
+    pub(crate) counters: Arc<ActorCounters>,
+    pub(crate) loop_state: Arc<LoopState<A>>,
+    pub(crate) kill_after_current: Arc<Notify>,
+    #[cfg(feature = "message-trace")]
+    pub(crate) message_trace: super::trace::MessageTrace,
 }
 
 impl<A> ActorRef<A>
@@ -63,6 +116,12 @@ where
             abort_handle,
             links,
             startup_semaphore,
+            stop_reason: Arc::new(watch::Sender::new(None)),
+            counters: Arc::default(),
+            loop_state: Arc::default(),
+            kill_after_current: Arc::default(),
+            #[cfg(feature = "message-trace")]
+            message_trace: super::trace::MessageTrace::new(A::message_trace_capacity()),
         }
     }
 
@@ -119,6 +178,12 @@ where
             abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_notify: self.startup_semaphore.clone(),
+            stop_reason: self.stop_reason.clone(),
+            counters: self.counters.clone(),
+            loop_state: self.loop_state.clone(),
+            kill_after_current: self.kill_after_current.clone(),
+            #[cfg(feature = "message-trace")]
+            message_trace: self.message_trace.clone(),
         }
     }
 
@@ -134,6 +199,180 @@ where
         self.mailbox.weak_count()
     }
 
+    /// Returns the number of messages and signals currently queued in the actor's mailbox.
+    ///
+    /// This can be used to observe backpressure, such as for adaptive pool sizing.
+    /// Custom mailboxes which don't implement [`Mailbox::len`] always report `0`.
+    #[inline]
+    pub fn mailbox_len(&self) -> usize {
+        self.mailbox.len()
+    }
+
+    /// Returns the number of messages processed by the actor so far.
+    #[inline]
+    pub fn processed_count(&self) -> u64 {
+        self.counters.processed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of asks sent to the actor which are still awaiting a reply.
+    ///
+    /// An ask is counted from when it is sent until its reply is received, or the request is dropped, such as after
+    /// a reply timeout. A count that keeps growing indicates the actor is falling behind on request/response load.
+    ///
+    /// The count is approximate under concurrency, since asks may be sent or replied to while it's being read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Slow;
+    ///
+    /// struct Query;
+    ///
+    /// impl Message<Query> for Slow {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Query, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         tokio::time::sleep(Duration::from_millis(50)).await;
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Slow);
+    /// let ask = tokio::spawn({
+    ///     let actor_ref = actor_ref.clone();
+    ///     async move { actor_ref.ask(Query).await }
+    /// });
+    ///
+    /// tokio::time::sleep(Duration::from_millis(10)).await;
+    /// assert_eq!(actor_ref.pending_replies(), 1);
+    ///
+    /// ask.await??;
+    /// assert_eq!(actor_ref.pending_replies(), 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn pending_replies(&self) -> usize {
+        self.counters.pending_replies.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn pending_reply(&self) -> PendingReply {
+        self.counters
+            .pending_replies
+            .fetch_add(1, Ordering::Relaxed);
+        PendingReply(self.counters.clone())
+    }
+
+    /// Returns a snapshot of the actor's mailbox length and processed message count.
+    #[inline]
+    pub fn stats(&self) -> ActorStats {
+        ActorStats {
+            mailbox_len: self.mailbox_len(),
+            processed: self.processed_count(),
+            pending_replies: self.pending_replies(),
+            startup_duration: self.counters.startup_duration.get().copied(),
+        }
+    }
+
+    /// Retains the last message of type `M` handled by the actor, so its handler can access it with
+    /// [`Context::previous`](crate::message::Context::previous).
+    ///
+    /// This is useful for handlers which diff consecutive messages, such as debouncing or change detection,
+    /// without copying each message into the actor's state. Each message is cloned before it's handled,
+    /// and only one message is kept per retained type. Messages handled before this is called aren't retained,
+    /// so it's usually called from [`Actor::on_start`].
+    ///
+    /// Only messages sent with [`tell`](ActorRef::tell) or [`ask`](ActorRef::ask) are retained, not reads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ActorRef;
+    /// use kameo::error::BoxError;
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::Actor;
+    ///
+    /// struct Thermometer;
+    ///
+    /// impl Actor for Thermometer {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_start(&mut self, actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+    ///         actor_ref.retain_previous::<Reading>();
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct Reading(i32);
+    ///
+    /// impl Message<Reading> for Thermometer {
+    ///     type Reply = Option<i32>;
+    ///
+    ///     async fn handle(&mut self, Reading(value): Reading, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         // The change since the previous reading
+    ///         ctx.previous::<Reading>().map(|Reading(previous)| value - previous)
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let thermometer = kameo::spawn(Thermometer);
+    ///
+    /// assert_eq!(thermometer.ask(Reading(20)).await?, None);
+    /// assert_eq!(thermometer.ask(Reading(23)).await?, Some(3));
+    /// assert_eq!(thermometer.ask(Reading(21)).await?, Some(-2));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn retain_previous<M>(&self)
+    where
+        A: Message<M>,
+        M: Clone + Send + Sync + 'static,
+    {
+        self.counters.previous.retain::<M>();
+    }
+
+    /// Returns the most recent messages received by the actor, oldest first.
+    ///
+    /// The number of messages recorded is configured by [`Actor::message_trace_capacity`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kameo::Actor;
+    /// # use kameo::message::{Context, Message};
+    /// #
+    /// # #[derive(Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # struct Msg;
+    /// #
+    /// # impl Message<Msg> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Msg, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// actor_ref.ask(Msg).await?;
+    ///
+    /// let recent = actor_ref.recent_messages();
+    /// assert!(recent[0].type_name().ends_with("Msg"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg(feature = "message-trace")]
+    pub fn recent_messages(&self) -> Vec<super::RecordedMessage> {
+        self.message_trace.snapshot()
+    }
+
     /// Returns `true` if the current task is the actor itself.
     ///
     /// This is useful when checking if certain code is being executed from within the actor's own context.
@@ -154,6 +393,84 @@ where
         self.mailbox.signal_stop().await
     }
 
+    /// Signals the actor to stop gracefully with an application specific reason.
+    ///
+    /// This behaves like [`stop_gracefully`](ActorRef::stop_gracefully), but the actor stops with `reason`
+    /// instead of [`ActorStopReason::Normal`](error::ActorStopReason::Normal). The reason is passed to [`Actor::on_stop`], and linked actors
+    /// receive it in [`Actor::on_link_died`], where it can be inspected with [`ActorStopReason::downcast_custom_ref`](error::ActorStopReason::downcast_custom_ref).
+    ///
+    /// The reason is carried by the stop signal itself, so if the actor is asked to stop multiple times, the first
+    /// stop signal it receives determines the reason.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{Actor, ActorRef, WeakActorRef};
+    /// use kameo::error::{ActorStopReason, BoxError};
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// struct QuotaExceeded {
+    ///     used: u64,
+    /// }
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Worker;
+    ///
+    /// #[derive(Default)]
+    /// struct Supervisor {
+    ///     quota_used: Option<u64>,
+    /// }
+    ///
+    /// impl Actor for Supervisor {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_link_died(
+    ///         &mut self,
+    ///         _: WeakActorRef<Self>,
+    ///         _: kameo::actor::ActorID,
+    ///         reason: ActorStopReason,
+    ///     ) -> Result<Option<ActorStopReason>, BoxError> {
+    ///         self.quota_used = reason.downcast_custom_ref::<QuotaExceeded>().map(|quota| quota.used);
+    ///         Ok(None)
+    ///     }
+    /// }
+    ///
+    /// struct QuotaUsed;
+    ///
+    /// impl Message<QuotaUsed> for Supervisor {
+    ///     type Reply = Option<u64>;
+    ///
+    ///     async fn handle(&mut self, _: QuotaUsed, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.quota_used
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let supervisor = kameo::spawn(Supervisor::default());
+    /// let worker = kameo::actor::spawn_link(&supervisor, Worker).await;
+    ///
+    /// worker.stop_with_reason(ActorStopReason::custom(QuotaExceeded { used: 120 })).await?;
+    /// worker.wait_for_stop().await;
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    ///
+    /// assert_eq!(supervisor.ask(QuotaUsed).await?, Some(120));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn stop_with_reason(
+        &self,
+        reason: error::ActorStopReason,
+    ) -> Result<(), error::SendError> {
+        self.mailbox
+            .send::<error::Infallible>(Signal::Stop {
+                reason: Some(reason),
+            })
+            .await
+            .map_err(|err| err.map_msg(|_| ()))
+    }
+
     /// Kills the actor immediately.
     ///
     /// This method aborts the actor immediately. Messages in the mailbox will be ignored and dropped.
@@ -161,15 +478,389 @@ where
     /// The actors on_stop hook will still be called.
     ///
     /// Note: If the actor is in the middle of processing a message, it will abort processing of that message.
+    ///
+    /// See [`kill_immediate`](ActorRef::kill_immediate) for killing the actor without running `on_stop`.
     #[inline]
     pub fn kill(&self) {
         self.abort_handle.abort()
     }
 
+    /// Kills the actor immediately, without running its [`on_stop`](Actor::on_stop) hook.
+    ///
+    /// Unlike [`kill`](ActorRef::kill), no cleanup is performed by the actor, which is useful for emergency teardown
+    /// where the cleanup itself might hang. Linked actors are still notified, and the actor stops with
+    /// [`ActorStopReason::KilledImmediately`](error::ActorStopReason::KilledImmediately).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{Actor, ActorRef, WeakActorRef};
+    /// use kameo::error::{ActorStopReason, BoxError};
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    ///
+    /// struct Flusher;
+    ///
+    /// impl Actor for Flusher {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_stop(&mut self, _: WeakActorRef<Self>, _: ActorStopReason) -> Result<(), BoxError> {
+    ///         unreachable!("on_stop is not run when killed immediately");
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared = kameo::actor::prepare(Flusher);
+    /// let actor_ref = prepared.actor_ref().clone();
+    /// let handle = prepared.spawn();
+    ///
+    /// actor_ref.kill_immediate();
+    /// let (_, reason) = handle.await?;
+    /// assert!(matches!(reason, ActorStopReason::KilledImmediately));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn kill_immediate(&self) {
+        self.loop_state.stop.lock().unwrap().killed_immediately = true;
+        self.abort_handle.abort()
+    }
+
+    /// Kills the actor once the message it is currently handling has been processed.
+    ///
+    /// This is a middle ground between [`stop_gracefully`](ActorRef::stop_gracefully), which processes every message
+    /// already in the mailbox, and [`kill`](ActorRef::kill), which may abort a handler in the middle of an `.await`.
+    /// The current handler runs to completion, then the actor stops with [`ActorStopReason::Killed`](error::ActorStopReason::Killed)
+    /// without processing the rest of its mailbox. If the actor is idle, it stops immediately.
+    ///
+    /// The actors on_stop hook will still be called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Writer {
+    ///     written: Vec<u32>,
+    /// }
+    ///
+    /// struct Write(u32);
+    ///
+    /// impl Message<Write> for Writer {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, Write(n): Write, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         tokio::time::sleep(Duration::from_millis(50)).await;
+    ///         self.written.push(n);
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared = kameo::actor::prepare(Writer::default());
+    /// let actor_ref = prepared.actor_ref().clone();
+    /// let join_handle = prepared.spawn();
+    /// actor_ref.wait_startup().await;
+    ///
+    /// for n in 0..3 {
+    ///     actor_ref.tell(Write(n)).await?;
+    /// }
+    /// tokio::time::sleep(Duration::from_millis(10)).await;
+    /// actor_ref.kill_after_current();
+    ///
+    /// // The first write completes, and the rest are never handled
+    /// let (writer, _) = join_handle.await?;
+    /// assert_eq!(writer.written, [0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn kill_after_current(&self) {
+        self.kill_after_current.notify_one()
+    }
+
+    /// Pauses the actor, so it stops receiving messages from its mailbox until [`resume`](ActorRef::resume) is called.
+    ///
+    /// The pause takes effect once the current message, or batch of concurrent reads, has been handled.
+    /// Messages keep being queued while the actor is paused, and are handled in order once it resumes,
+    /// so a bounded mailbox may fill up and apply backpressure to senders.
+    ///
+    /// A graceful stop is queued like any other message and only handled after the actor resumes,
+    /// but [`kill`](ActorRef::kill) and [`kill_after_current`](ActorRef::kill_after_current) still stop a paused actor.
+    #[inline]
+    pub fn pause(&self) {
+        self.counters.paused.set(true);
+    }
+
+    /// Resumes an actor paused with [`pause`](ActorRef::pause).
+    #[inline]
+    pub fn resume(&self) {
+        self.counters.paused.set(false);
+    }
+
+    /// Returns whether the actor has been paused with [`pause`](ActorRef::pause).
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.counters.paused.is_paused()
+    }
+
+    /// Pauses the actor and every actor transitively linked to it, returning the ids of the paused actors.
+    ///
+    /// This is for coordinated maintenance of a subsystem, such as pausing a supervision tree while a shared resource
+    /// is swapped out. Each actor is paused as described in [`pause`](ActorRef::pause), and is visited once,
+    /// even if the links form a cycle.
+    ///
+    /// Links created with [`link`](ActorRef::link) are bidirectional, so pausing any actor in a group of linked actors
+    /// pauses the whole group, including its supervisors. Only links created with
+    /// [`link_child`](ActorRef::link_child) are followed in a single direction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor, Default)]
+    /// struct Worker {
+    ///     handled: u32,
+    /// }
+    ///
+    /// struct Work;
+    ///
+    /// impl Message<Work> for Worker {
+    ///     type Reply = u32;
+    ///
+    ///     async fn handle(&mut self, _: Work, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.handled += 1;
+    ///         self.handled
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let parent = kameo::spawn(Worker::default());
+    /// let child = kameo::spawn(Worker::default());
+    /// let grandchild = kameo::spawn(Worker::default());
+    /// parent.link(&child).await;
+    /// child.link(&grandchild).await;
+    /// # grandchild.wait_startup().await;
+    ///
+    /// let paused = parent.pause_subtree().await;
+    /// assert_eq!(paused.len(), 3);
+    /// assert!(grandchild.is_paused());
+    ///
+    /// // Messages are queued without being handled
+    /// grandchild.tell(Work).await?;
+    /// tokio::time::sleep(Duration::from_millis(10)).await;
+    /// assert_eq!(grandchild.mailbox_len(), 1);
+    ///
+    /// parent.resume_subtree().await;
+    /// assert_eq!(grandchild.ask(Work).await?, 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn pause_subtree(&self) -> Vec<ActorID> {
+        self.links
+            .walk(self.id, &self.counters, |counters| {
+                counters.paused.set(true)
+            })
+            .await
+    }
+
+    /// Resumes the actor and every actor transitively linked to it, returning the ids of the resumed actors.
+    ///
+    /// This follows the same links as [`pause_subtree`](ActorRef::pause_subtree), so actors linked since the subtree
+    /// was paused are resumed too.
+    pub async fn resume_subtree(&self) -> Vec<ActorID> {
+        self.links
+            .walk(self.id, &self.counters, |counters| {
+                counters.paused.set(false)
+            })
+            .await
+    }
+
+    /// Stops the actor after its current message, handing every message still queued in its mailbox to `other`.
+    ///
+    /// This is for swapping an actor for a replacement without losing messages, such as in a blue-green deployment.
+    /// Once the current message has been handled, messages which were never processed are redelivered to `other`
+    /// in the order they were received, along with their reply senders, so callers waiting on an `ask` get the reply
+    /// from `other`. The actor then stops with [`ActorStopReason::Normal`](error::ActorStopReason::Normal), and
+    /// this returns once it has stopped. Messages `other` no longer accepts are passed to
+    /// [`Actor::on_undelivered`] instead.
+    ///
+    /// Messages sent to the actor after it has stopped are not transferred, so senders should be switched over
+    /// to `other`. When called from within the actor's own handler, this returns without waiting for the actor
+    /// to stop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is the same actor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::error::ActorStopReason;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Recorder {
+    ///     received: Vec<u32>,
+    /// }
+    ///
+    /// struct Record(u32);
+    ///
+    /// impl Message<Record> for Recorder {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, Record(n): Record, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.received.push(n);
+    ///     }
+    /// }
+    ///
+    /// struct Received;
+    ///
+    /// impl Message<Received> for Recorder {
+    ///     type Reply = Vec<u32>;
+    ///
+    ///     async fn handle(&mut self, _: Received, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.received.clone()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared_actor = kameo::actor::prepare(Recorder::default());
+    /// let blue = prepared_actor.actor_ref_owned();
+    /// for n in 1..=3 {
+    ///     blue.tell(Record(n)).send().await?;
+    /// }
+    /// let join_handle = prepared_actor.spawn();
+    ///
+    /// let green = kameo::spawn(Recorder::default());
+    /// blue.transfer_mailbox_to(&green).await?;
+    ///
+    /// let (blue_state, reason) = join_handle.await?;
+    /// assert!(blue_state.received.is_empty());
+    /// assert!(matches!(reason, ActorStopReason::Normal));
+    /// assert_eq!(green.ask(Received).await?, vec![1, 2, 3]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn transfer_mailbox_to(&self, other: &ActorRef<A>) -> Result<(), error::SendError> {
+        assert_ne!(
+            self.id, other.id,
+            "an actor's mailbox cannot be transferred to itself"
+        );
+        if !self.is_alive() {
+            return Err(error::SendError::ActorNotRunning(()));
+        }
+
+        self.loop_state.stop.lock().unwrap().transfer_to = Some(other.clone());
+        self.kill_after_current();
+        if !self.is_current() {
+            let mut stop_reason = self.stop_reason.subscribe();
+            let _ = stop_reason.wait_for(Option::is_some).await;
+        }
+
+        Ok(())
+    }
+
+    /// Moves the actor to another tokio runtime, keeping the same `ActorRef`.
+    ///
+    /// The actor finishes handling the messages sent before the migration, then its message loop resumes in a task
+    /// spawned on `handle`. No messages are lost or reordered, and the actor's state, links, and mailbox move with it.
+    /// This returns once the actor is running on the new runtime. When called by the actor itself,
+    /// the migration happens after the current message is handled and this returns immediately.
+    ///
+    /// The task the actor was originally spawned in stays alive until the actor stops, and returns its
+    /// result as usual. If the target runtime is shut down while the actor is running on it, that task panics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Recorder {
+    ///     received: Vec<(u32, Option<String>)>,
+    /// }
+    ///
+    /// struct Push(u32);
+    ///
+    /// impl Message<Push> for Recorder {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, Push(n): Push, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let thread = std::thread::current().name().map(ToString::to_string);
+    ///         self.received.push((n, thread));
+    ///     }
+    /// }
+    ///
+    /// struct Received;
+    ///
+    /// impl Message<Received> for Recorder {
+    ///     type Reply = Vec<(u32, Option<String>)>;
+    ///
+    ///     async fn handle(&mut self, _: Received, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.received.clone()
+    ///     }
+    /// }
+    ///
+    /// let pinned = tokio::runtime::Builder::new_multi_thread()
+    ///     .worker_threads(1)
+    ///     .thread_name("pinned")
+    ///     .build()?;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Recorder::default());
+    /// for n in 0..100 {
+    ///     actor_ref.tell(Push(n)).await?;
+    /// }
+    /// // Messages sent during the migration are handled once the actor has moved
+    /// let (migrated, sent) = tokio::join!(actor_ref.migrate_to(pinned.handle().clone()), async {
+    ///     for n in 100..200 {
+    ///         actor_ref.tell(Push(n)).await?;
+    ///     }
+    ///     Ok::<_, kameo::error::SendError<Push>>(())
+    /// });
+    /// migrated?;
+    /// sent?;
+    ///
+    /// let received = actor_ref.ask(Received).await?;
+    /// assert!(received.iter().map(|(n, _)| *n).eq(0..200));
+    /// assert_eq!(received.last().unwrap().1.as_deref(), Some("pinned"));
+    ///
+    /// actor_ref.stop_gracefully().await?;
+    /// actor_ref.wait_for_stop().await;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # })?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub async fn migrate_to(&self, handle: tokio::runtime::Handle) -> Result<(), error::SendError> {
+        let (migrated, ack) = oneshot::channel();
+        let signal = Signal::Migrate { handle, migrated };
+        if self.is_current() {
+            return self
+                .mailbox
+                .try_send(signal)
+                .map_err(|err| err.map_msg(|_| ()));
+        }
+        self.mailbox
+            .send(signal)
+            .await
+            .map_err(|err| err.map_msg(|_| ()))?;
+        ack.await.map_err(|_| error::SendError::ActorNotRunning(()))
+    }
+
     /// Waits for the actor to finish startup and become ready to process messages.
     ///
     /// This method ensures the actors on_start lifecycle hook has been fully processed.
     /// If `wait_startup` is called after the actor has already started up, this will return immediately.
+    /// It also returns if `on_start` fails. Use [`try_spawn`](crate::actor::try_spawn) to get the startup result.
     ///
     /// # Example
     ///
@@ -219,6 +910,116 @@ where
         self.mailbox.closed().await
     }
 
+    /// Waits until the actor's state satisfies a predicate, or the timeout elapses.
+    ///
+    /// The predicate is checked on the actor's task when it receives the request, and again after every message
+    /// handled from then on, resolving as soon as it returns `true`.
+    /// This avoids polling the actor with queries, such as when waiting for a cache to be warmed up.
+    ///
+    /// Fails with [`SendError::Timeout`] if the predicate does not hold within the timeout,
+    /// or [`SendError::ActorStopped`] if the actor stops first or the predicate panics.
+    /// Awaiting this from within the actor's own message handler always times out,
+    /// since no other messages are handled in the meantime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Cache {
+    ///     entries: Vec<u32>,
+    /// }
+    ///
+    /// struct Insert(u32);
+    ///
+    /// impl Message<Insert> for Cache {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, Insert(n): Insert, _: Context<'_, Self, Self::Reply>) {
+    ///         self.entries.push(n);
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let cache = kameo::spawn(Cache::default());
+    ///
+    /// let warm = cache.await_state(|cache| cache.entries.len() >= 3, Duration::from_secs(5));
+    /// let fill = async {
+    ///     for n in 0..3 {
+    ///         cache.tell(Insert(n)).await?;
+    ///     }
+    ///     Ok::<_, kameo::error::SendError<Insert>>(())
+    /// };
+    /// let (warm, fill) = tokio::join!(warm, fill);
+    /// warm?;
+    /// fill?;
+    ///
+    /// let never = cache.await_state(|cache| cache.entries.is_empty(), Duration::from_millis(10));
+    /// assert!(matches!(never.await, Err(kameo::error::SendError::Timeout(None))));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn await_state<F>(&self, predicate: F, timeout: Duration) -> Result<(), SendError>
+    where
+        F: Fn(&A) -> bool + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let signal = Signal::AwaitState {
+            waiter: StateWaiter::new(predicate, tx),
+        };
+        tokio::time::timeout(timeout, async {
+            self.mailbox
+                .send(signal)
+                .await
+                .map_err(|err| err.map_msg(|_| ()))?;
+            rx.await.map_err(|_| SendError::ActorStopped)
+        })
+        .await
+        .map_err(|_| SendError::Timeout(None))?
+    }
+
+    /// Returns a human readable summary of the actor's state, as reported by [`Actor::describe`].
+    ///
+    /// The summary is taken between messages, so it never observes a handler partway through.
+    /// Unlike a message, no handler needs to be implemented.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    ///
+    /// struct Downloader {
+    ///     completed: usize,
+    ///     total: usize,
+    /// }
+    ///
+    /// impl Actor for Downloader {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     fn describe(&self) -> String {
+    ///         format!("downloaded {}/{} files", self.completed, self.total)
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let downloader = kameo::spawn(Downloader { completed: 3, total: 10 });
+    /// assert_eq!(downloader.describe().await?, "downloaded 3/10 files");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn describe(&self) -> Result<String, SendError> {
+        let (tx, rx) = oneshot::channel();
+        self.mailbox
+            .send(Signal::Describe { reply: tx })
+            .await
+            .map_err(|err| err.map_msg(|_| ()))?;
+        rx.await.map_err(|_| SendError::ActorStopped)
+    }
+
     /// Sends a message to the actor and waits for a reply.
     ///
     /// The `ask` pattern is used when you expect a response from the actor. This method returns
@@ -264,43 +1065,320 @@ where
         AskRequest::new(self, msg)
     }
 
+    /// Sends a message to the actor and waits for a reply, with options for the reply channel.
+    ///
+    /// The options are available to the handler through [`Context::reply_options`](crate::message::Context::reply_options),
+    /// and are used by streaming replies such as [`Progress`](crate::reply::Progress) to size their buffer.
+    /// A smaller buffer applies backpressure to the handler sooner, bounding memory when it produces faster than the
+    /// caller consumes, while a larger buffer allows more throughput. By default, the buffer is unbounded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::reply::{DelegatedReply, Progress, ReplyOptions};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Producer;
+    ///
+    /// struct Produce;
+    ///
+    /// impl Message<Produce> for Producer {
+    ///     type Reply = DelegatedReply<Progress<u32, ()>>;
+    ///
+    ///     async fn handle(&mut self, _: Produce, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let (progress, tx) = Progress::with_options(ctx.reply_options());
+    ///         let (delegated, reply_sender) = ctx.reply_sender();
+    ///         if let Some(reply_sender) = reply_sender {
+    ///             reply_sender.send(progress);
+    ///         }
+    ///
+    ///         tokio::spawn(async move {
+    ///             for i in 0..100 {
+    ///                 tx.send(i).await; // Waits while the caller is behind
+    ///             }
+    ///             tx.finish(());
+    ///         });
+    ///
+    ///         delegated
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Producer);
+    /// let progress = actor_ref.ask_with(Produce, ReplyOptions { buffer: Some(8) }).await?;
+    /// let (updates, result) = progress.into_parts();
+    /// assert_eq!(updates.count().await, 100);
+    /// assert_eq!(result.await, Some(()));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn ask_with<M>(
+        &self,
+        msg: M,
+        options: ReplyOptions,
+    ) -> AskRequest<
+        LocalAskRequest<'_, A, A::Mailbox>,
+        A::Mailbox,
+        M,
+        WithoutRequestTimeout,
+        WithoutRequestTimeout,
+    >
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        AskRequest::new(self, msg).with_reply_options(options)
+    }
+
+    /// Sends a read-only message to the actor and waits for a reply.
+    ///
+    /// Reads are handled concurrently with other reads, but never while a [`Message`] is being handled.
+    /// See [`ReadMessage`](crate::message::ReadMessage) for more information.
+    pub async fn ask_read<M>(
+        &self,
+        msg: M,
+    ) -> Result<
+        <<A as ReadMessage<M>>::Reply as Reply>::Ok,
+        SendError<M, <<A as ReadMessage<M>>::Reply as Reply>::Error>,
+    >
+    where
+        A: ReadMessage<M>,
+        M: Send + 'static,
+    {
+        let (reply, rx) = oneshot::channel();
+        let _pending = self.pending_reply();
+        let signal = Signal::Read {
+            message: Box::new(msg),
+            actor_ref: self.clone(),
+            reply: Some(reply),
+            sent_within_actor: self.is_current(),
+            options: MessageOptions::boxed(
+                CURRENT_DEADLINE
+                    .try_with(|deadline| *deadline)
+                    .ok()
+                    .flatten(),
+                ReplyOptions::default(),
+                CURRENT_ACTOR_ID.try_with(Clone::clone).ok(),
+            ),
+        };
+        if signal.is_too_large() {
+            return Err(SendError::TooLarge(signal.downcast_message().unwrap()));
+        }
+        if signal.is_overloaded() {
+            return Err(SendError::Overloaded(signal.downcast_message().unwrap()));
+        }
+        self.mailbox
+            .send(signal)
+            .await
+            .map_err(|err| err.map_msg(|signal| signal.downcast_message().unwrap()))?;
+        match rx.await? {
+            Ok(val) => Ok(*val.downcast().unwrap()),
+            Err(err) => Err(err.downcast()),
+        }
+    }
+
+    /// Sends many messages to the actor, returning a stream of their replies in the order the messages were sent.
+    ///
+    /// Messages are sent lazily as the stream is polled, without waiting for the previous reply, so many requests
+    /// are in flight at once. Replies which have already arrived are yielded before more messages are sent.
+    /// This is more efficient than awaiting each [`ask`](ActorRef::ask) in turn for bulk queries against a single actor.
+    ///
+    /// # Ordering
+    ///
+    /// The messages of a pipeline are enqueued in order, and each reply is yielded in the position of its message.
+    /// Messages from other senders may be interleaved with the pipeline's messages in the mailbox,
+    /// so the pipeline does not observe a consistent snapshot of the actor's state.
+    ///
+    /// If a message fails to send, its position in the stream yields the error and the remaining messages are still sent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Squarer;
+    ///
+    /// struct Square(u64);
+    ///
+    /// impl Message<Square> for Squarer {
+    ///     type Reply = u64;
+    ///
+    ///     async fn handle(&mut self, Square(n): Square, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         n * n
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Squarer);
+    /// let squares: Vec<u64> = actor_ref
+    ///     .ask_pipeline((1..=5).map(Square))
+    ///     .map(|res| res.unwrap())
+    ///     .collect()
+    ///     .await;
+    /// assert_eq!(squares, [1, 4, 9, 16, 25]);
+    /// # });
+    /// ```
+    pub fn ask_pipeline<M, I>(
+        &self,
+        msgs: I,
+    ) -> impl Stream<Item = crate::request::PipelineReply<A, M>> + Send + 'static
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        I: IntoIterator<Item = M>,
+        I::IntoIter: Send + 'static,
+    {
+        crate::request::ask_pipeline(self.clone(), msgs.into_iter())
+    }
+
+    /// Converts the actor ref into a [`tower_service::Service`], which handles requests by sending them with `ask`.
+    ///
+    /// See [`ActorService`](crate::request::ActorService) for more information.
+    #[cfg(feature = "tower")]
+    pub fn into_service<M>(self) -> crate::request::ActorService<A, M>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        crate::request::ActorService::new(self)
+    }
+
     /// Sends a message to the actor without waiting for a reply.
     ///
     /// The `tell` pattern is used for one-way communication, where no response is expected from the actor. This method
     /// returns a `TellRequest`, which can be awaited asynchronously, or configured using one of the [`request`](crate::request) traits.
     ///
-    /// # Example
+    /// When an actor tells itself a message and its bounded mailbox is full, the send fails with
+    /// [`SendError::MailboxFull`](crate::error::SendError::MailboxFull) rather than waiting forever for capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ActorRef;
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # struct Msg;
+    /// #
+    /// # impl kameo::message::Message<Msg> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Msg, ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// # let msg = Msg;
+    /// actor_ref.tell(msg).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn tell<M>(
+        &self,
+        msg: M,
+    ) -> TellRequest<LocalTellRequest<'_, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        TellRequest::new(self, msg)
+    }
+
+    /// Sends the actor a message which is handled before any other message once it finishes starting up.
+    ///
+    /// This is intended to be called from [`Actor::on_start`](crate::actor::Actor::on_start), such as to
+    /// deterministically start a periodic loop. Messages sent this way are the first messages handled after startup,
+    /// in the order they were sent, ahead of messages sent by other actors while the actor was starting.
+    /// Since the message is queued without using the mailbox, this never waits and cannot fail.
+    /// If the actor fails to start, the message is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the actor has already finished starting up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{Actor, ActorRef};
+    /// use kameo::error::BoxError;
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// #[derive(Default)]
+    /// struct Poller {
+    ///     log: Vec<&'static str>,
+    /// }
+    ///
+    /// impl Actor for Poller {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_start(&mut self, actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+    ///         actor_ref.tell_self_priority("poll");
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// impl Message<&'static str> for Poller {
+    ///     type Reply = Vec<&'static str>;
     ///
-    /// ```
-    /// use kameo::actor::ActorRef;
+    ///     async fn handle(&mut self, msg: &'static str, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.log.push(msg);
+    ///         self.log.clone()
+    ///     }
+    /// }
     ///
-    /// # #[derive(kameo::Actor)]
-    /// # struct MyActor;
-    /// #
-    /// # struct Msg;
-    /// #
-    /// # impl kameo::message::Message<Msg> for MyActor {
-    /// #     type Reply = ();
-    /// #     async fn handle(&mut self, msg: Msg, ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply { }
-    /// # }
-    /// #
     /// # tokio_test::block_on(async {
-    /// let actor_ref = kameo::spawn(MyActor);
-    /// # let msg = Msg;
-    /// actor_ref.tell(msg).await?;
+    /// let prepared = kameo::actor::prepare(Poller::default());
+    /// // Sent before the actor starts, but handled after its priority message
+    /// prepared.actor_ref().tell("external").await?;
+    /// let actor_ref = prepared.actor_ref().clone();
+    /// prepared.spawn();
+    ///
+    /// assert_eq!(actor_ref.ask("check").await?, ["poll", "external", "check"]);
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// # });
     /// ```
-    #[inline]
-    pub fn tell<M>(
-        &self,
-        msg: M,
-    ) -> TellRequest<LocalTellRequest<'_, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>
+    pub fn tell_self_priority<M>(&self, msg: M)
     where
         A: Message<M>,
         M: Send + 'static,
     {
-        TellRequest::new(self, msg)
+        if self
+            .loop_state
+            .stash
+            .lock()
+            .unwrap()
+            .push_startup(Box::new(msg))
+            .is_err()
+        {
+            panic!("tell_self_priority can only be called while the actor is starting up");
+        }
+    }
+
+    /// Returns a handle for scheduling delayed messages to the actor, and inspecting or cancelling them.
+    ///
+    /// See [`ScheduledMessages`](super::ScheduledMessages) for more information.
+    #[inline]
+    pub fn scheduled(&self) -> super::ScheduledMessages<A> {
+        super::ScheduledMessages::new(self.clone())
+    }
+
+    /// Returns a handle to the actor's links, for building custom supervision structures.
+    ///
+    /// See [`ActorLinks`] for more information.
+    #[inline]
+    pub fn links(&self) -> ActorLinks {
+        ActorLinks {
+            id: self.id,
+            links: self.links.clone(),
+        }
     }
 
     /// Links two actors as siblings, ensuring they notify each other if either one dies.
@@ -337,8 +1415,8 @@ where
 
         let (mut this_links, mut sibbling_links) =
             tokio::join!(self.links.lock(), sibbling_ref.links.lock());
-        this_links.insert(sibbling_ref.id(), sibbling_ref.weak_signal_mailbox());
-        sibbling_links.insert(self.id, self.weak_signal_mailbox());
+        this_links.insert(sibbling_ref.id(), sibbling_ref.as_link());
+        sibbling_links.insert(self.id, self.as_link());
     }
 
     /// Unlinks two previously linked sibling actors.
@@ -417,9 +1495,7 @@ where
             return;
         }
 
-        let child_id = child.id();
-        let child: Box<dyn SignalMailbox> = child.weak_signal_mailbox();
-        self.links.lock().await.insert(child_id, child);
+        self.links.lock().await.insert(child.id(), child.as_link());
     }
 
     /// Unlinks a previously linked child actor.
@@ -498,8 +1574,8 @@ where
 
         let (mut this_links, mut sibbling_links) =
             tokio::join!(self.links.lock(), sibbling_ref.links.lock());
-        this_links.insert(sibbling_ref.id(), sibbling_ref.weak_signal_mailbox());
-        sibbling_links.insert(self.id, self.weak_signal_mailbox());
+        this_links.insert(sibbling_ref.id(), sibbling_ref.as_link());
+        sibbling_links.insert(self.id, self.as_link());
     }
 
     /// Unlinks two previously linked sibling actors.
@@ -585,6 +1661,7 @@ where
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// # });
     /// ```
+    #[allow(clippy::type_complexity)]
     pub fn attach_stream<M, S, T, F>(
         &self,
         mut stream: S,
@@ -648,6 +1725,15 @@ where
     pub(crate) fn weak_signal_mailbox(&self) -> Box<dyn SignalMailbox> {
         Box::new(self.mailbox.downgrade())
     }
+
+    #[inline]
+    pub(crate) fn as_link(&self) -> Link {
+        Link {
+            mailbox: self.weak_signal_mailbox(),
+            counters: self.counters.clone(),
+            links: Arc::downgrade(&self.links.0),
+        }
+    }
 }
 
 impl<A: Actor> Clone for ActorRef<A> {
@@ -658,6 +1744,12 @@ impl<A: Actor> Clone for ActorRef<A> {
             abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_semaphore: self.startup_semaphore.clone(),
+            stop_reason: self.stop_reason.clone(),
+            counters: self.counters.clone(),
+            loop_state: self.loop_state.clone(),
+            kill_after_current: self.kill_after_current.clone(),
+            #[cfg(feature = "message-trace")]
+            message_trace: self.message_trace.clone(),
         }
     }
 }
@@ -678,6 +1770,26 @@ impl<A: Actor> fmt::Debug for ActorRef<A> {
     }
 }
 
+impl<A: Actor> PartialEq for ActorRef<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A: Actor> PartialEq<WeakActorRef<A>> for ActorRef<A> {
+    fn eq(&self, other: &WeakActorRef<A>) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A: Actor> Eq for ActorRef<A> {}
+
+impl<A: Actor> Hash for ActorRef<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 impl<A: Actor> AsRef<Links> for ActorRef<A> {
     fn as_ref(&self) -> &Links {
         &self.links
@@ -692,6 +1804,7 @@ impl<A: Actor> AsRef<Links> for ActorRef<A> {
 pub struct RemoteActorRef<A: Actor> {
     id: ActorID,
     swarm_tx: remote::SwarmSender,
+    reconnect: Option<Arc<remote::Reconnect>>,
     phantom: PhantomData<A::Mailbox>,
 }
 
@@ -701,6 +1814,7 @@ impl<A: Actor> RemoteActorRef<A> {
         RemoteActorRef {
             id,
             swarm_tx,
+            reconnect: None,
             phantom: PhantomData,
         }
     }
@@ -710,6 +1824,14 @@ impl<A: Actor> RemoteActorRef<A> {
         self.id
     }
 
+    /// Returns the actor ref with requests retried according to the reconnect policy when the peer is unreachable.
+    ///
+    /// See [`ReconnectPolicy`](remote::ReconnectPolicy) for more information.
+    pub fn with_reconnect(mut self, policy: remote::ReconnectPolicy) -> Self {
+        self.reconnect = Some(Arc::new(remote::Reconnect::new(policy)));
+        self
+    }
+
     /// Looks up an actor registered by name across the distributed network.
     ///
     /// Returns `Some` if the actor is found, or `None` if no actor with the given name is registered.
@@ -814,8 +1936,26 @@ impl<A: Actor> RemoteActorRef<A> {
         TellRequest::new_remote(self, msg)
     }
 
-    pub(crate) fn send_to_swarm(&self, msg: remote::SwarmCommand) {
-        self.swarm_tx.send(msg)
+    /// Sends a request to the swarm, retrying while disconnected if a reconnect policy is set.
+    pub(crate) async fn request_swarm(&self, req: remote::SwarmReq) -> remote::SwarmResp {
+        let peer_id = self
+            .id
+            .peer_id_intern()
+            .cloned()
+            .unwrap_or_else(|| *remote::ActorSwarm::get().unwrap().local_peer_id_intern());
+        let send = || {
+            let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+            self.swarm_tx.send(remote::SwarmCommand::Req {
+                peer_id,
+                req: req.clone(),
+                reply: reply_tx,
+            });
+            async move { reply_rx.await.unwrap() }
+        };
+        match &self.reconnect {
+            Some(reconnect) => reconnect.send(send).await,
+            None => send().await,
+        }
     }
 }
 
@@ -825,6 +1965,7 @@ impl<A: Actor> Clone for RemoteActorRef<A> {
         RemoteActorRef {
             id: self.id,
             swarm_tx: self.swarm_tx.clone(),
+            reconnect: self.reconnect.clone(),
             phantom: PhantomData,
         }
     }
@@ -842,17 +1983,26 @@ impl<A: Actor> fmt::Debug for RemoteActorRef<A> {
 /// A actor ref that does not prevent the actor from being stopped.
 ///
 /// If all [`ActorRef`] instances of an actor were dropped and only
-/// `WeakActorRef` instances remain, the actor is stopped.
+/// `WeakActorRef` instances remain, the actor is stopped,
+/// unless [`Actor::stop_when_unreferenced`] returns `false`.
 ///
 /// In order to send messages to an actor, the `WeakActorRef` needs to be upgraded using
 /// [`WeakActorRef::upgrade`], which returns `Option<ActorRef>`. It returns `None`
 /// if all `ActorRef`s have been dropped, and otherwise it returns an `ActorRef`.
+///
+/// Like [`ActorRef`], equality and hashing are by the actor's [`ActorID`].
 pub struct WeakActorRef<A: Actor> {
     id: ActorID,
     mailbox: <A::Mailbox as Mailbox<A>>::WeakMailbox,
     abort_handle: AbortHandle,
-    links: Links,
+    pub(crate) links: Links,
     startup_notify: Arc<Semaphore>,
+    pub(crate) stop_reason: Arc<watch::Sender<Option<error::ActorStopReason>>>,
+    pub(crate) counters: Arc<ActorCounters>,
+    pub(crate) loop_state: Arc<LoopState<A>>,
+    pub(crate) kill_after_current: Arc<Notify>,
+    #[cfg(feature = "message-trace")]
+    pub(crate) message_trace: super::trace::MessageTrace,
 }
 
 impl<A: Actor> WeakActorRef<A> {
@@ -870,6 +2020,12 @@ impl<A: Actor> WeakActorRef<A> {
             abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_semaphore: self.startup_notify.clone(),
+            stop_reason: self.stop_reason.clone(),
+            counters: self.counters.clone(),
+            loop_state: self.loop_state.clone(),
+            kill_after_current: self.kill_after_current.clone(),
+            #[cfg(feature = "message-trace")]
+            message_trace: self.message_trace.clone(),
         })
     }
 
@@ -892,6 +2048,12 @@ impl<A: Actor> Clone for WeakActorRef<A> {
             abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_notify: self.startup_notify.clone(),
+            stop_reason: self.stop_reason.clone(),
+            counters: self.counters.clone(),
+            loop_state: self.loop_state.clone(),
+            kill_after_current: self.kill_after_current.clone(),
+            #[cfg(feature = "message-trace")]
+            message_trace: self.message_trace.clone(),
         }
     }
 }
@@ -912,17 +2074,524 @@ impl<A: Actor> fmt::Debug for WeakActorRef<A> {
     }
 }
 
+impl<A: Actor> PartialEq for WeakActorRef<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A: Actor> PartialEq<ActorRef<A>> for WeakActorRef<A> {
+    fn eq(&self, other: &ActorRef<A>) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A: Actor> Eq for WeakActorRef<A> {}
+
+impl<A: Actor> Hash for WeakActorRef<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// A snapshot of an actor's mailbox and processing statistics.
+///
+/// Returned by [`ActorRef::stats`], and for linked actors by
+/// [`Context::link_stats`](crate::message::Context::link_stats).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ActorStats {
+    /// The number of messages and signals currently queued in the actor's mailbox.
+    pub mailbox_len: usize,
+    /// The number of messages processed by the actor so far.
+    pub processed: u64,
+    /// The number of asks awaiting a reply, see [`ActorRef::pending_replies`].
+    pub pending_replies: usize,
+    /// How long the actor's [`on_start`](Actor::on_start) took, or `None` if it has not finished starting up.
+    pub startup_duration: Option<Duration>,
+}
+
+/// Counters shared between an actor and its references, used to build [`ActorStats`].
+#[derive(Debug, Default)]
+pub(crate) struct ActorCounters {
+    pub(crate) processed: AtomicU64,
+    pub(crate) pending_replies: AtomicUsize,
+    pub(crate) startup_duration: OnceLock<Duration>,
+    pub(crate) overload: OverloadState,
+    pub(crate) scheduled: super::schedule::ScheduledState,
+    /// Messages discarded by a lossy mailbox, not yet reported to [`Actor::on_message_dropped`].
+    pub(crate) dropped: AtomicU64,
+    /// The actor's place in its spawn category, handed to the actor when it starts running.
+    pub(crate) spawn_permit: std::sync::Mutex<Option<super::limit::SpawnPermit>>,
+    pub(crate) paused: PauseState,
+    pub(crate) previous: PreviousMessages,
+}
+
+/// Tracks whether an actor is shedding load, see [`Actor::overload_config`].
+#[derive(Debug, Default)]
+pub(crate) struct OverloadState {
+    shedding: AtomicBool,
+    above_high_since: std::sync::Mutex<Option<Instant>>,
+}
+
+impl OverloadState {
+    /// Returns whether a message should be rejected, given the current length of the mailbox.
+    pub(crate) fn should_shed(&self, len: usize, config: &OverloadConfig) -> bool {
+        if self.shedding.load(Ordering::Relaxed) {
+            if len > config.low_watermark {
+                return true;
+            }
+            *self.lock() = None;
+            self.shedding.store(false, Ordering::Relaxed);
+            return false;
+        }
+
+        let mut above_high_since = self.lock();
+        if len < config.high_watermark {
+            *above_high_since = None;
+            return false;
+        }
+        let since = *above_high_since.get_or_insert_with(Instant::now);
+        if since.elapsed() >= config.duration {
+            self.shedding.store(true, Ordering::Relaxed);
+            return true;
+        }
+
+        false
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Instant>> {
+        self.above_high_since
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Whether an actor has been paused with [`ActorRef::pause`], watched by its message loop.
+#[derive(Debug)]
+pub(crate) struct PauseState(watch::Sender<bool>);
+
+impl PauseState {
+    pub(crate) fn set(&self, paused: bool) {
+        self.0.send_replace(paused);
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<bool> {
+        self.0.subscribe()
+    }
+}
+
+impl Default for PauseState {
+    fn default() -> Self {
+        PauseState(watch::channel(false).0)
+    }
+}
+
+/// The last message of each type handled by an actor, for types retained with [`ActorRef::retain_previous`].
+#[derive(Debug, Default)]
+pub(crate) struct PreviousMessages {
+    // Checked first, so actors which don't retain any messages never take the lock
+    enabled: AtomicBool,
+    messages: std::sync::Mutex<HashMap<TypeId, RetainedMessage>>,
+}
+
+#[derive(Debug)]
+struct RetainedMessage {
+    clone: fn(&dyn Any) -> Box<dyn Any + Send + Sync>,
+    last: Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl PreviousMessages {
+    fn retain<M: Clone + Send + Sync + 'static>(&self) {
+        fn clone<M: Clone + Send + Sync + 'static>(msg: &dyn Any) -> Box<dyn Any + Send + Sync> {
+            Box::new(msg.downcast_ref::<M>().unwrap().clone())
+        }
+
+        self.lock()
+            .entry(TypeId::of::<M>())
+            .or_insert(RetainedMessage {
+                clone: clone::<M>,
+                last: None,
+            });
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Retains a copy of `msg` if its type is retained, returning the previous message of the same type.
+    pub(crate) fn replace<M: Send + 'static>(&self, msg: &M) -> Option<Box<dyn Any + Send + Sync>> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut messages = self.lock();
+        let retained = messages.get_mut(&TypeId::of::<M>())?;
+        let msg = (retained.clone)(msg);
+        retained.last.replace(msg)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<TypeId, RetainedMessage>> {
+        self.messages.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Counts an ask as awaiting a reply until it is dropped.
+#[derive(Debug)]
+pub(crate) struct PendingReply(Arc<ActorCounters>);
+
+impl Drop for PendingReply {
+    fn drop(&mut self) {
+        self.0.pending_replies.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A type erased reference to an actor, allowing actors of different types to be managed together.
+///
+/// An `AnyActorRef` cannot be used to send messages, but supports managing the actor's lifecycle,
+/// such as stopping the actor and waiting for it to stop with its stop reason.
+///
+/// It can be created from any [`ActorRef`] using [`From`].
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::AnyActorRef;
+/// use kameo::error::ActorStopReason;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = AnyActorRef::from(kameo::spawn(MyActor));
+/// actor_ref.stop_gracefully().await?;
+/// assert!(matches!(actor_ref.wait_for_stop().await, ActorStopReason::Normal));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct AnyActorRef {
+    id: ActorID,
+    mailbox: Box<dyn SignalMailbox>,
+    abort_handle: AbortHandle,
+    kill_after_current: Arc<Notify>,
+    stop_reason: Arc<watch::Sender<Option<error::ActorStopReason>>>,
+}
+
+impl AnyActorRef {
+    /// Returns the unique identifier of the actor.
+    #[inline]
+    pub fn id(&self) -> ActorID {
+        self.id
+    }
+
+    /// Returns whether the actor is currently alive.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.stop_reason.borrow().is_none()
+    }
+
+    /// Signals the actor to stop after processing all messages currently in its mailbox.
+    ///
+    /// See [`ActorRef::stop_gracefully`] for more information.
+    #[inline]
+    pub async fn stop_gracefully(&self) -> Result<(), error::SendError> {
+        self.mailbox.signal_stop().await
+    }
+
+    /// Kills the actor immediately.
+    ///
+    /// See [`ActorRef::kill`] for more information.
+    #[inline]
+    pub fn kill(&self) {
+        self.abort_handle.abort()
+    }
+
+    /// Kills the actor once the message it is currently handling has been processed.
+    ///
+    /// See [`ActorRef::kill_after_current`] for more information.
+    #[inline]
+    pub fn kill_after_current(&self) {
+        self.kill_after_current.notify_one()
+    }
+
+    /// Returns a handle which can kill the actor and wait for it to stop, without keeping its mailbox open.
+    pub(crate) fn monitor(&self) -> Monitor {
+        Monitor {
+            id: self.id,
+            abort_handle: self.abort_handle.clone(),
+            stop_reason: self.stop_reason.subscribe(),
+        }
+    }
+
+    /// Waits for the actor to stop, returning the reason it stopped.
+    ///
+    /// The reason is available once the actor's [`on_stop`](crate::actor::Actor::on_stop) hook has completed.
+    /// If the actor has already stopped, this will return immediately.
+    /// An actor which was prepared but dropped without ever running is reported as [`Killed`](error::ActorStopReason::Killed).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::actor::AnyActorRef;
+    /// use kameo::error::ActorStopReason;
+    ///
+    /// #[derive(Actor)]
+    /// struct MyActor;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared_actor = kameo::actor::prepare(MyActor);
+    /// let actor_ref = AnyActorRef::from(prepared_actor.actor_ref().clone());
+    ///
+    /// drop(prepared_actor);
+    /// assert!(!actor_ref.is_alive());
+    /// assert!(matches!(actor_ref.wait_for_stop().await, ActorStopReason::Killed));
+    /// # });
+    /// ```
+    pub async fn wait_for_stop(&self) -> error::ActorStopReason {
+        wait_for_stop_reason(self.stop_reason.subscribe()).await
+    }
+}
+
+/// A handle to an actor which doesn't keep its mailbox open, see [`AnyActorRef::monitor`].
+pub(crate) struct Monitor {
+    pub(crate) id: ActorID,
+    pub(crate) abort_handle: AbortHandle,
+    pub(crate) stop_reason: watch::Receiver<Option<error::ActorStopReason>>,
+}
+
+impl Monitor {
+    /// Waits for the actor to stop, returning the reason it stopped.
+    pub(crate) async fn wait_for_stop(&mut self) -> error::ActorStopReason {
+        wait_for_stop_reason(self.stop_reason.clone()).await
+    }
+}
+
+/// Waits for a stop reason to be published, treating the actor as killed if it never will be.
+async fn wait_for_stop_reason(
+    mut stop_reason: watch::Receiver<Option<error::ActorStopReason>>,
+) -> error::ActorStopReason {
+    stop_reason
+        .wait_for(Option::is_some)
+        .await
+        .ok()
+        .and_then(|reason| reason.clone())
+        .unwrap_or(error::ActorStopReason::Killed)
+}
+
+impl<A: Actor> From<ActorRef<A>> for AnyActorRef {
+    fn from(actor_ref: ActorRef<A>) -> Self {
+        AnyActorRef {
+            id: actor_ref.id,
+            mailbox: Box::new(actor_ref.mailbox),
+            abort_handle: actor_ref.abort_handle,
+            kill_after_current: actor_ref.kill_after_current,
+            stop_reason: actor_ref.stop_reason,
+        }
+    }
+}
+
+impl Clone for AnyActorRef {
+    fn clone(&self) -> Self {
+        AnyActorRef {
+            id: self.id,
+            mailbox: dyn_clone::clone_box(&*self.mailbox),
+            abort_handle: self.abort_handle.clone(),
+            kill_after_current: self.kill_after_current.clone(),
+            stop_reason: self.stop_reason.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for AnyActorRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyActorRef")
+            .field("id", &self.id)
+            .field("is_alive", &self.is_alive())
+            .finish()
+    }
+}
+
+/// A handle to an actor's links, for building custom supervision structures.
+///
+/// Returned by [`ActorRef::links`]. When an actor stops, every actor it is linked to is sent a link died signal,
+/// which is handled by [`Actor::on_link_died`](crate::actor::Actor::on_link_died).
+/// Unlike [`ActorRef::link`], links added with [`add_link`](ActorLinks::add_link) only go one way,
+/// so a supervisor can be notified of its children dying without the children being notified of the supervisor.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(kameo::Actor)]
+/// struct Supervisor;
+///
+/// #[derive(kameo::Actor)]
+/// struct Worker;
+///
+/// # tokio_test::block_on(async {
+/// let supervisor = kameo::spawn(Supervisor);
+/// let worker = kameo::spawn(Worker);
+///
+/// let died = Arc::new(Mutex::new(Vec::new()));
+/// supervisor.links().on_any_link_died({
+///     let died = died.clone();
+///     move |id, _reason| died.lock().unwrap().push(id)
+/// });
+/// // Only the supervisor is notified when the worker stops
+/// worker.links().add_link(&supervisor).await;
+/// assert!(worker.links().is_linked(supervisor.id()).await);
+///
+/// worker.stop_gracefully().await?;
+/// worker.wait_for_stop().await;
+/// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+/// assert_eq!(*died.lock().unwrap(), [worker.id()]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct ActorLinks {
+    id: ActorID,
+    links: Links,
+}
+
+impl ActorLinks {
+    /// Returns the identifier of the actor these links belong to.
+    #[inline]
+    pub fn id(&self) -> ActorID {
+        self.id
+    }
+
+    /// Links `other` to this actor, so it is sent a link died signal when this actor stops.
+    ///
+    /// The link only goes one way. Linking an actor to itself does nothing.
+    pub async fn add_link<B: Actor>(&self, other: &ActorRef<B>) {
+        if self.id == other.id() {
+            return;
+        }
+
+        self.links.lock().await.insert(other.id(), other.as_link());
+    }
+
+    /// Removes the link to an actor, returning whether it was linked.
+    pub async fn remove_link(&self, id: ActorID) -> bool {
+        self.links.lock().await.remove(&id).is_some()
+    }
+
+    /// Returns whether an actor is sent a link died signal when this actor stops.
+    pub async fn is_linked(&self, id: ActorID) -> bool {
+        self.links.lock().await.contains_key(&id)
+    }
+
+    /// Returns the identifiers of the actors sent a link died signal when this actor stops.
+    pub async fn linked(&self) -> Vec<ActorID> {
+        self.links.lock().await.keys().copied().collect()
+    }
+
+    /// Registers a callback run whenever this actor receives a link died signal.
+    ///
+    /// Callbacks run in the actor's task, in the order they were registered, before
+    /// [`Actor::on_link_died`](crate::actor::Actor::on_link_died) is called.
+    /// They should not block, and a callback which panics is handled like a panic in `on_link_died`.
+    pub fn on_any_link_died<F>(&self, f: F)
+    where
+        F: Fn(ActorID, &error::ActorStopReason) + Send + Sync + 'static,
+    {
+        self.links
+            .0
+            .link_died_hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Arc::new(f));
+    }
+}
+
+impl fmt::Debug for ActorLinks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActorLinks").field("id", &self.id).finish()
+    }
+}
+
+pub(crate) type LinkDiedHook = Arc<dyn Fn(ActorID, &error::ActorStopReason) + Send + Sync>;
+
 /// A collection of links to other actors that are notified when the actor dies.
 ///
 /// Links are used for parent-child or sibling relationships, allowing actors to observe each other's lifecycle.
 #[derive(Clone, Default)]
 #[allow(missing_debug_implementations)]
-pub(crate) struct Links(Arc<Mutex<HashMap<ActorID, Box<dyn SignalMailbox>>>>);
+pub(crate) struct Links(Arc<LinksInner>);
+
+// A single `Arc` keeps `ActorRef`, and so `Signal`, small
+#[derive(Default)]
+struct LinksInner {
+    links: Mutex<HashMap<ActorID, Link>>,
+    link_died_hooks: std::sync::Mutex<Vec<LinkDiedHook>>,
+}
+
+impl Links {
+    /// Returns the callbacks registered with [`ActorLinks::on_any_link_died`].
+    pub(crate) fn link_died_hooks(&self) -> Vec<LinkDiedHook> {
+        self.0
+            .link_died_hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Calls `f` with the counters of the actor and each actor transitively linked to it, returning their ids
+    /// in the order they were visited.
+    async fn walk(
+        &self,
+        id: ActorID,
+        counters: &ActorCounters,
+        f: impl Fn(&ActorCounters),
+    ) -> Vec<ActorID> {
+        f(counters);
+        let mut visited = HashSet::from([id]);
+        let mut order = vec![id];
+        let mut queue = VecDeque::from([self.0.clone()]);
+        while let Some(links) = queue.pop_front() {
+            for (id, link) in links.links.lock().await.iter() {
+                if !visited.insert(*id) {
+                    continue;
+                }
+                f(&link.counters);
+                order.push(*id);
+                // Links to actors which have stopped can't be followed any further
+                if let Some(links) = link.links.upgrade() {
+                    queue.push_back(links);
+                }
+            }
+        }
+        order
+    }
+}
 
 impl ops::Deref for Links {
-    type Target = Mutex<HashMap<ActorID, Box<dyn SignalMailbox>>>;
+    type Target = Mutex<HashMap<ActorID, Link>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.0.links
+    }
+}
+
+/// A link to another actor, used to signal it when the actor dies and to observe its stats.
+#[allow(missing_debug_implementations)]
+pub(crate) struct Link {
+    pub(crate) mailbox: Box<dyn SignalMailbox>,
+    pub(crate) counters: Arc<ActorCounters>,
+    /// The linked actor's own links, held weakly so links between actors don't keep each other alive.
+    links: Weak<LinksInner>,
+}
+
+impl Link {
+    pub(crate) fn stats(&self) -> ActorStats {
+        ActorStats {
+            mailbox_len: self.mailbox.mailbox_len(),
+            processed: self.counters.processed.load(Ordering::Relaxed),
+            pending_replies: self.counters.pending_replies.load(Ordering::Relaxed),
+            startup_duration: self.counters.startup_duration.get().copied(),
+        }
     }
 }
@@ -0,0 +1,188 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::{error::SendError, message::Message, request::MessageSend, Actor, Reply};
+
+use super::ActorRef;
+
+type Entries<M, T> = HashMap<M, (Instant, T)>;
+
+/// An actor reference which caches replies to a query message for a fixed time to live.
+///
+/// Asking a `CachedActorRef` returns the cached reply for an equal message if one was received within the TTL,
+/// without sending anything to the actor. Otherwise the message is sent to the actor, and a successful reply is cached.
+/// This is only suitable for read-only queries whose reply is allowed to be stale for up to the TTL.
+///
+/// Entries can be removed before they expire with [`invalidate`](CachedActorRef::invalidate),
+/// such as after sending the actor a message which changes the query's result.
+/// Clones share the same cache.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::actor::CachedActorRef;
+/// use kameo::message::{Context, Message};
+/// use kameo::request::MessageSend;
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Prices {
+///     lookups: u32,
+/// }
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// struct GetPrice(&'static str);
+///
+/// impl Message<GetPrice> for Prices {
+///     type Reply = u32;
+///
+///     async fn handle(&mut self, _: GetPrice, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.lookups += 1;
+///         42
+///     }
+/// }
+///
+/// struct Lookups;
+///
+/// impl Message<Lookups> for Prices {
+///     type Reply = u32;
+///
+///     async fn handle(&mut self, _: Lookups, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.lookups
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Prices::default());
+/// let prices = CachedActorRef::new(actor_ref.clone(), Duration::from_secs(60));
+///
+/// assert_eq!(prices.ask(GetPrice("apple")).await?, 42);
+/// assert_eq!(prices.ask(GetPrice("apple")).await?, 42); // Served from the cache
+/// assert_eq!(actor_ref.ask(Lookups).await?, 1);
+///
+/// prices.invalidate(&GetPrice("apple"));
+/// assert_eq!(prices.ask(GetPrice("apple")).await?, 42);
+/// assert_eq!(actor_ref.ask(Lookups).await?, 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct CachedActorRef<A, M>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+{
+    actor_ref: ActorRef<A>,
+    ttl: Duration,
+    cache: Arc<Mutex<Entries<M, <A::Reply as Reply>::Ok>>>,
+}
+
+impl<A, M> CachedActorRef<A, M>
+where
+    A: Actor + Message<M>,
+    M: Hash + Eq + Clone + Send + 'static,
+    <A::Reply as Reply>::Ok: Clone,
+{
+    /// Creates a cached actor reference, caching replies for `ttl`.
+    pub fn new(actor_ref: ActorRef<A>, ttl: Duration) -> Self {
+        CachedActorRef {
+            actor_ref,
+            ttl,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Returns the underlying actor reference.
+    pub fn actor_ref(&self) -> &ActorRef<A> {
+        &self.actor_ref
+    }
+
+    /// Returns the time to live of cached replies.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Asks the actor, returning the cached reply if an equal message was answered within the TTL.
+    ///
+    /// Errors are never cached.
+    pub async fn ask(
+        &self,
+        msg: M,
+    ) -> Result<<A::Reply as Reply>::Ok, SendError<M, <A::Reply as Reply>::Error>> {
+        if let Some(reply) = self.get(&msg) {
+            return Ok(reply);
+        }
+
+        let reply = self.actor_ref.ask(msg.clone()).send().await?;
+        self.insert(msg, reply.clone());
+        Ok(reply)
+    }
+
+    /// Removes the cached reply for a message, so the next ask is sent to the actor.
+    pub fn invalidate(&self, msg: &M) {
+        self.lock().remove(msg);
+    }
+
+    /// Removes all cached replies.
+    pub fn invalidate_all(&self) {
+        self.lock().clear();
+    }
+
+    fn get(&self, msg: &M) -> Option<<A::Reply as Reply>::Ok> {
+        let mut cache = self.lock();
+        match cache.get(msg) {
+            Some((expires_at, reply)) if *expires_at > Instant::now() => Some(reply.clone()),
+            Some(_) => {
+                cache.remove(msg);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, msg: M, reply: <A::Reply as Reply>::Ok) {
+        let now = Instant::now();
+        let mut cache = self.lock();
+        // Expired entries are only otherwise removed when asked for again
+        cache.retain(|_, (expires_at, _)| *expires_at > now);
+        cache.insert(msg, (now + self.ttl, reply));
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries<M, <A::Reply as Reply>::Ok>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<A, M> Clone for CachedActorRef<A, M>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+{
+    fn clone(&self) -> Self {
+        CachedActorRef {
+            actor_ref: self.actor_ref.clone(),
+            ttl: self.ttl,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<A, M> fmt::Debug for CachedActorRef<A, M>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedActorRef")
+            .field("actor_ref", &self.actor_ref)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
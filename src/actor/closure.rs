@@ -0,0 +1,84 @@
+use std::{fmt, future::Future, marker::PhantomData};
+
+use crate::{
+    mailbox::unbounded::UnboundedMailbox,
+    message::{Context, Message},
+    Actor, Reply,
+};
+
+use super::{spawn, ActorRef};
+
+/// Spawns an actor which handles messages of type `M` by calling `f`, without defining an actor struct.
+///
+/// This is convenient for stateless glue, such as adapters which forward or transform messages. The closure is
+/// the actor's state, so anything it captures lives as long as the actor, and it's called once per message in
+/// the order they're received. Its future's output is the reply, which may be any [`Reply`] type, so a returned
+/// error stops the actor just like an error from any other handler would.
+///
+/// The actor handles a single message type, and uses an [`UnboundedMailbox`]. For anything more, define an actor.
+///
+/// # Example
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// struct Double(u32);
+///
+/// let doubler = kameo::spawn_fn(|Double(n)| async move { n * 2 });
+/// assert_eq!(doubler.ask(Double(21)).await?, 42);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub fn spawn_fn<M, F, Fut>(f: F) -> ActorRef<FnActor<M, F>>
+where
+    M: Send + 'static,
+    F: FnMut(M) -> Fut + Send + 'static,
+    Fut: Future + Send,
+    Fut::Output: Reply,
+{
+    spawn(FnActor {
+        f,
+        phantom: PhantomData,
+    })
+}
+
+/// The actor spawned by [`spawn_fn`], which handles messages of type `M` with a closure.
+pub struct FnActor<M, F> {
+    f: F,
+    phantom: PhantomData<fn(M)>,
+}
+
+impl<M, F> Actor for FnActor<M, F>
+where
+    M: 'static,
+    F: Send + 'static,
+{
+    type Mailbox = UnboundedMailbox<Self>;
+
+    fn name() -> &'static str {
+        "FnActor"
+    }
+}
+
+impl<M, F, Fut> Message<M> for FnActor<M, F>
+where
+    M: Send + 'static,
+    F: FnMut(M) -> Fut + Send + 'static,
+    Fut: Future + Send,
+    Fut::Output: Reply,
+{
+    type Reply = Fut::Output;
+
+    fn handle(
+        &mut self,
+        msg: M,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        (self.f)(msg)
+    }
+}
+
+impl<M, F> fmt::Debug for FnActor<M, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnActor").finish_non_exhaustive()
+    }
+}
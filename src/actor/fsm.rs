@@ -0,0 +1,312 @@
+//! Provides a finite state machine actor, which only handles messages in allowed states.
+//!
+//! The `fsm` module offers the [`StateMachine`] trait for declaring an actor's states, and the [`StateHandler`] trait
+//! for declaring which states each message is handled in. A state machine is spawned by wrapping it in an [`Fsm`],
+//! which rejects messages sent in a disallowed state with an [`InvalidStateError`], without calling the handler.
+//!
+//! Handlers change the state with [`StateContext::transition`], which takes effect once the handler returns.
+//!
+//! # Features
+//! - **Explicit States**: The current state is owned by the framework, and can only change through transitions.
+//! - **Automatic Rejection**: Messages are only handled in the states they declare, others are rejected with a typed error.
+//! - **Transition Hook**: [`StateMachine::on_transition`] is called for every state change.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::actor::fsm::{Fsm, StateContext, StateHandler, StateMachine};
+//!
+//! #[derive(Clone, Debug, PartialEq)]
+//! enum TcpState {
+//!     Closed,
+//!     Listen,
+//!     Established,
+//! }
+//!
+//! #[derive(Default)]
+//! struct TcpConnection {
+//!     received: Vec<u8>,
+//! }
+//!
+//! impl StateMachine for TcpConnection {
+//!     type State = TcpState;
+//! }
+//!
+//! struct Listen;
+//! struct Accept;
+//! struct Recv(Vec<u8>);
+//! struct Close;
+//!
+//! impl StateHandler<Listen> for TcpConnection {
+//!     type Reply = ();
+//!
+//!     fn allowed(state: &TcpState) -> bool {
+//!         *state == TcpState::Closed
+//!     }
+//!
+//!     async fn handle(&mut self, _: Listen, mut ctx: StateContext<'_, Self>) {
+//!         ctx.transition(TcpState::Listen);
+//!     }
+//! }
+//!
+//! impl StateHandler<Accept> for TcpConnection {
+//!     type Reply = ();
+//!
+//!     fn allowed(state: &TcpState) -> bool {
+//!         *state == TcpState::Listen
+//!     }
+//!
+//!     async fn handle(&mut self, _: Accept, mut ctx: StateContext<'_, Self>) {
+//!         ctx.transition(TcpState::Established);
+//!     }
+//! }
+//!
+//! impl StateHandler<Recv> for TcpConnection {
+//!     type Reply = usize;
+//!
+//!     fn allowed(state: &TcpState) -> bool {
+//!         *state == TcpState::Established
+//!     }
+//!
+//!     async fn handle(&mut self, Recv(data): Recv, _ctx: StateContext<'_, Self>) -> usize {
+//!         self.received.extend(data);
+//!         self.received.len()
+//!     }
+//! }
+//!
+//! impl StateHandler<Close> for TcpConnection {
+//!     type Reply = ();
+//!
+//!     fn allowed(state: &TcpState) -> bool {
+//!         *state != TcpState::Closed
+//!     }
+//!
+//!     async fn handle(&mut self, _: Close, mut ctx: StateContext<'_, Self>) {
+//!         ctx.transition(TcpState::Closed);
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let conn = kameo::spawn(Fsm::new(TcpConnection::default(), TcpState::Closed));
+//!
+//! // Data can't be received before the connection is established
+//! assert!(conn.ask(Recv(vec![1, 2, 3])).await.is_err());
+//!
+//! conn.ask(Listen).await?;
+//! conn.ask(Accept).await?;
+//! assert_eq!(conn.ask(Recv(vec![1, 2, 3])).await?, 3);
+//! conn.ask(Close).await?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::{any, fmt};
+
+use futures::Future;
+use tracing::warn;
+
+use crate::{
+    error::InvalidStateError,
+    mailbox::bounded::BoundedMailbox,
+    message::{BoxDebug, Context, Message},
+    reply::Reply,
+    Actor,
+};
+
+use super::ActorRef;
+
+/// A finite state machine, whose states are managed by an [`Fsm`] actor.
+///
+/// Messages are handled by implementing [`StateHandler`] for each message type.
+pub trait StateMachine: Send + Sized + 'static {
+    /// The states of the machine.
+    type State: fmt::Debug + Clone + Send + Sync + 'static;
+
+    /// The name of the state machine, which can be useful for logging or debugging.
+    ///
+    /// # Default Implementation
+    /// By default, this returns the type name of the state machine.
+    fn name() -> &'static str {
+        any::type_name::<Self>()
+    }
+
+    /// Called after a handler transitions to a new state, before the next message is handled.
+    #[allow(unused_variables)]
+    fn on_transition(
+        &mut self,
+        from: &Self::State,
+        to: &Self::State,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+}
+
+/// A message handler for a [`StateMachine`], which is only called in allowed states.
+pub trait StateHandler<M>: StateMachine {
+    /// The reply sent back to the message caller.
+    type Reply: Send + 'static;
+
+    /// Returns `true` if the message can be handled in the given state.
+    ///
+    /// Messages received in a disallowed state are rejected with an [`InvalidStateError`].
+    fn allowed(state: &Self::State) -> bool;
+
+    /// Handler for this message.
+    fn handle(
+        &mut self,
+        msg: M,
+        ctx: StateContext<'_, Self>,
+    ) -> impl Future<Output = Self::Reply> + Send;
+}
+
+/// A context provided to [`StateHandler`]s, giving access to the current state.
+pub struct StateContext<'a, S: StateMachine> {
+    actor_ref: ActorRef<Fsm<S>>,
+    state: &'a S::State,
+    next: &'a mut Option<S::State>,
+}
+
+impl<S: StateMachine> StateContext<'_, S> {
+    /// Returns the current state.
+    pub fn state(&self) -> &S::State {
+        self.state
+    }
+
+    /// Transitions to a new state once the handler returns.
+    ///
+    /// If called multiple times, the last state is used.
+    pub fn transition(&mut self, state: S::State) {
+        *self.next = Some(state);
+    }
+
+    /// Returns the actor reference of the state machine.
+    pub fn actor_ref(&self) -> ActorRef<Fsm<S>> {
+        self.actor_ref.clone()
+    }
+}
+
+impl<S: StateMachine> fmt::Debug for StateContext<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateContext")
+            .field("state", &self.state)
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+/// An actor running a [`StateMachine`].
+pub struct Fsm<S: StateMachine> {
+    machine: S,
+    state: S::State,
+}
+
+impl<S: StateMachine> Fsm<S> {
+    /// Creates a new state machine actor in the initial state.
+    pub fn new(machine: S, initial_state: S::State) -> Self {
+        Fsm {
+            machine,
+            state: initial_state,
+        }
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> &S::State {
+        &self.state
+    }
+
+    /// Returns a reference to the state machine.
+    pub fn machine(&self) -> &S {
+        &self.machine
+    }
+}
+
+impl<S: StateMachine> fmt::Debug for Fsm<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fsm")
+            .field("name", &S::name())
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<S: StateMachine> Actor for Fsm<S> {
+    type Mailbox = BoundedMailbox<Self>;
+
+    fn name() -> &'static str {
+        S::name()
+    }
+
+    fn describe(&self) -> String {
+        format!("{:?}", self.state)
+    }
+}
+
+impl<S, M> Message<M> for Fsm<S>
+where
+    S: StateHandler<M>,
+    M: Send + 'static,
+{
+    type Reply = StateReply<S::Reply, S::State>;
+
+    async fn handle(&mut self, msg: M, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+        if !S::allowed(&self.state) {
+            let message = any::type_name::<M>();
+            warn!(name = %S::name(), state = ?self.state, message_type = %message, "message rejected in state");
+            return StateReply(Err(InvalidStateError::new(self.state.clone(), message)));
+        }
+
+        let mut next = None;
+        let reply = self
+            .machine
+            .handle(
+                msg,
+                StateContext {
+                    actor_ref: ctx.actor_ref(),
+                    state: &self.state,
+                    next: &mut next,
+                },
+            )
+            .await;
+        if let Some(next) = next {
+            self.machine.on_transition(&self.state, &next).await;
+            self.state = next;
+        }
+
+        StateReply(Ok(reply))
+    }
+}
+
+/// The reply of an [`Fsm`], either the handler's reply or an [`InvalidStateError`] if the message was rejected.
+///
+/// Unlike a `Result` reply, a rejected message sent with `tell` does not stop the actor.
+#[derive(Debug)]
+pub struct StateReply<R, St>(Result<R, InvalidStateError<St>>);
+
+impl<R, St> StateReply<R, St> {
+    /// Converts the reply into a `Result`.
+    pub fn into_result(self) -> Result<R, InvalidStateError<St>> {
+        self.0
+    }
+}
+
+impl<R, St> Reply for StateReply<R, St>
+where
+    R: Send + 'static,
+    St: Send + 'static,
+{
+    type Ok = R;
+    type Error = InvalidStateError<St>;
+    type Value = Self;
+
+    fn to_result(self) -> Result<R, InvalidStateError<St>> {
+        self.0
+    }
+
+    fn into_boxed_err(self) -> Option<BoxDebug> {
+        None
+    }
+
+    fn into_value(self) -> Self::Value {
+        self
+    }
+}
@@ -0,0 +1,132 @@
+//! Provides a way to manage the lifecycle of many actors together.
+//!
+//! The `group` module offers the [`ActorGroup`] type, which holds a collection of actor references,
+//! possibly of different actor types, and allows them to be stopped and awaited with a single call.
+//! This is particularly useful during application shutdown, where all actors should be torn down
+//! cleanly and their stop reasons logged.
+//!
+//! # Features
+//! - **Heterogeneous Actors**: Actors of any type can be added to the group through [`AnyActorRef`].
+//! - **Fan-out Stop**: Stop all actors in the group gracefully, or kill them immediately.
+//! - **Join**: Wait for all actors in the group to stop, collecting each actor's stop reason.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::Actor;
+//! use kameo::actor::group::ActorGroup;
+//!
+//! #[derive(Actor)]
+//! struct Foo;
+//!
+//! #[derive(Actor)]
+//! struct Bar;
+//!
+//! # tokio_test::block_on(async {
+//! let mut group = ActorGroup::new();
+//! group.insert(kameo::spawn(Foo));
+//! group.insert(kameo::spawn(Bar));
+//!
+//! group.stop_all().await;
+//! for (id, reason) in group.join_all().await {
+//!     println!("{id} stopped: {reason}");
+//! }
+//! # });
+//! ```
+
+use futures::future::join_all;
+
+use crate::error::ActorStopReason;
+
+use super::{ActorID, AnyActorRef};
+
+/// A group of actors which can be stopped and joined together.
+///
+/// Actors are stored in insertion order, which is also the order of results returned by [`ActorGroup::join_all`].
+#[derive(Clone, Debug, Default)]
+pub struct ActorGroup {
+    actors: Vec<AnyActorRef>,
+}
+
+impl ActorGroup {
+    /// Creates a new empty actor group.
+    pub fn new() -> Self {
+        ActorGroup { actors: Vec::new() }
+    }
+
+    /// Adds an actor to the group.
+    ///
+    /// Any [`ActorRef`](crate::actor::ActorRef) can be inserted, regardless of its actor type.
+    pub fn insert(&mut self, actor_ref: impl Into<AnyActorRef>) {
+        self.actors.push(actor_ref.into());
+    }
+
+    /// Removes an actor from the group by its ID, returning it if it was present.
+    pub fn remove(&mut self, id: ActorID) -> Option<AnyActorRef> {
+        let index = self.actors.iter().position(|actor| actor.id() == id)?;
+        Some(self.actors.remove(index))
+    }
+
+    /// Returns the number of actors in the group.
+    pub fn len(&self) -> usize {
+        self.actors.len()
+    }
+
+    /// Returns `true` if the group contains no actors.
+    pub fn is_empty(&self) -> bool {
+        self.actors.is_empty()
+    }
+
+    /// Returns an iterator over the actors in the group.
+    pub fn iter(&self) -> impl Iterator<Item = &AnyActorRef> {
+        self.actors.iter()
+    }
+
+    /// Signals all actors in the group to stop gracefully.
+    ///
+    /// Actors which are no longer running are ignored.
+    pub async fn stop_all(&self) {
+        join_all(self.actors.iter().map(|actor| actor.stop_gracefully())).await;
+    }
+
+    /// Kills all actors in the group immediately.
+    pub fn kill_all(&self) {
+        for actor in &self.actors {
+            actor.kill();
+        }
+    }
+
+    /// Waits for all actors in the group to stop, returning each actor's stop reason.
+    ///
+    /// The results are in the same order as the actors were inserted.
+    ///
+    /// Note: This method does not initiate the stop process, see [`ActorGroup::stop_all`].
+    pub async fn join_all(&self) -> Vec<(ActorID, ActorStopReason)> {
+        join_all(
+            self.actors
+                .iter()
+                .map(|actor| async move { (actor.id(), actor.wait_for_stop().await) }),
+        )
+        .await
+    }
+}
+
+impl<T> Extend<T> for ActorGroup
+where
+    T: Into<AnyActorRef>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.actors.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl<T> FromIterator<T> for ActorGroup
+where
+    T: Into<AnyActorRef>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        ActorGroup {
+            actors: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
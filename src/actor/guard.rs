@@ -0,0 +1,192 @@
+use std::{fmt, ops::Deref};
+
+use futures::FutureExt;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::warn;
+
+use crate::Actor;
+
+use super::{spawn, ActorRef};
+
+/// Spawns an actor, returning a [`SpawnGuard`] which stops it gracefully when dropped.
+///
+/// This ties the actor's lifetime to a scope, which is useful for actors owned by a struct or a test.
+/// By default dropping the guard only signals the actor to stop, without waiting for it.
+/// Use [`SpawnGuard::wait_on_drop`] to also block until it has stopped.
+///
+/// In async code, prefer [`SpawnGuard::stop`] or [`scope`](crate::actor::scope), which wait for the actor
+/// without blocking a thread.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = {
+///     let guard = kameo::actor::spawn_guarded(MyActor);
+///     guard.actor_ref().clone()
+/// };
+///
+/// // Dropping the guard signalled the actor to stop
+/// actor_ref.wait_for_stop().await;
+/// assert!(!actor_ref.is_alive());
+/// # })
+/// ```
+pub fn spawn_guarded<A>(actor: A) -> SpawnGuard<A>
+where
+    A: Actor,
+{
+    SpawnGuard::new(spawn(actor))
+}
+
+/// A guard which stops an actor gracefully when dropped, returned by [`spawn_guarded`].
+///
+/// The guard dereferences to the [`ActorRef`], and holds a strong reference until it's dropped.
+/// Other references may keep messaging the actor until then, after which they receive
+/// [`SendError::ActorNotRunning`](crate::error::SendError::ActorNotRunning).
+///
+/// # Blocking on drop
+///
+/// A guard created with [`wait_on_drop`](SpawnGuard::wait_on_drop) blocks the dropping thread until the actor has
+/// run [`Actor::on_stop`]. This is convenient in synchronous code, but has some caveats:
+///
+/// - Inside a multi threaded tokio runtime, the worker is moved off the runtime with
+///   `tokio::task::block_in_place` while waiting, so other tasks keep running. This requires the `rt-multi-thread`
+///   feature; without it, the guard logs a warning and only signals the actor to stop.
+/// - Inside a current thread runtime, waiting would deadlock, since the actor can't run while the only thread is blocked.
+///   The guard logs a warning and only signals the actor to stop instead.
+/// - Outside of a runtime, the thread is blocked until the actor stops,
+///   which requires the actor's runtime to be driven by another thread.
+/// - The actor handles all messages queued before the stop signal first, so a slow or stuck actor blocks the drop
+///   just as long.
+pub struct SpawnGuard<A: Actor> {
+    // Only taken when the guard is disarmed or dropped
+    actor_ref: Option<ActorRef<A>>,
+    wait_on_drop: bool,
+}
+
+impl<A: Actor> SpawnGuard<A> {
+    /// Creates a guard for an actor which is already running, stopping it when the guard is dropped.
+    pub fn new(actor_ref: ActorRef<A>) -> Self {
+        SpawnGuard {
+            actor_ref: Some(actor_ref),
+            wait_on_drop: false,
+        }
+    }
+
+    /// Makes the guard block until the actor has stopped when it's dropped.
+    ///
+    /// See [blocking on drop](SpawnGuard#blocking-on-drop) for the caveats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    ///
+    /// #[derive(Actor)]
+    /// struct MyActor;
+    ///
+    /// let runtime = tokio::runtime::Runtime::new()?;
+    /// let guard = runtime.block_on(async { kameo::actor::spawn_guarded(MyActor) }).wait_on_drop();
+    /// let actor_ref = guard.actor_ref().clone();
+    ///
+    /// // Blocks until the actor has stopped on the runtime's worker threads
+    /// drop(guard);
+    /// assert!(!actor_ref.is_alive());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn wait_on_drop(mut self) -> Self {
+        self.wait_on_drop = true;
+        self
+    }
+
+    /// Returns the reference to the guarded actor.
+    pub fn actor_ref(&self) -> &ActorRef<A> {
+        self.actor_ref.as_ref().unwrap()
+    }
+
+    /// Signals the actor to stop gracefully and waits for it to stop, without blocking the thread.
+    pub async fn stop(self) {
+        let actor_ref = self.into_inner();
+        let _ = actor_ref.stop_gracefully().await;
+        actor_ref.wait_for_stop().await;
+    }
+
+    /// Disarms the guard, returning the actor ref without stopping the actor.
+    pub fn into_inner(mut self) -> ActorRef<A> {
+        self.actor_ref.take().unwrap()
+    }
+}
+
+impl<A: Actor> Deref for SpawnGuard<A> {
+    type Target = ActorRef<A>;
+
+    fn deref(&self) -> &Self::Target {
+        self.actor_ref()
+    }
+}
+
+impl<A: Actor> Drop for SpawnGuard<A> {
+    fn drop(&mut self) {
+        let Some(actor_ref) = self.actor_ref.take() else {
+            return;
+        };
+
+        // The built in mailboxes send the stop signal immediately, so this only needs a runtime for custom mailboxes
+        let mut stopping = {
+            let actor_ref = actor_ref.clone();
+            async move {
+                let _ = actor_ref.stop_gracefully().await;
+            }
+            .boxed()
+        };
+        if stopping.as_mut().now_or_never().is_none() {
+            match Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn(stopping);
+                }
+                Err(_) => futures::executor::block_on(stopping),
+            }
+        }
+
+        if !self.wait_on_drop {
+            return;
+        }
+        match Handle::try_current() {
+            Ok(handle) if matches!(handle.runtime_flavor(), RuntimeFlavor::CurrentThread) => {
+                warn!(
+                    actor_id = %actor_ref.id(),
+                    "cannot wait for actor to stop on drop in a current thread runtime"
+                );
+            }
+            #[cfg(feature = "rt-multi-thread")]
+            Ok(handle) => {
+                tokio::task::block_in_place(|| handle.block_on(actor_ref.wait_for_stop()));
+            }
+            #[cfg(not(feature = "rt-multi-thread"))]
+            Ok(_) => {
+                warn!(
+                    actor_id = %actor_ref.id(),
+                    "cannot wait for actor to stop on drop without the rt-multi-thread feature"
+                );
+            }
+            Err(_) => futures::executor::block_on(actor_ref.wait_for_stop()),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for SpawnGuard<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpawnGuard")
+            .field(
+                "actor_id",
+                &self.actor_ref.as_ref().map(|actor_ref| actor_ref.id()),
+            )
+            .field("wait_on_drop", &self.wait_on_drop)
+            .finish()
+    }
+}
@@ -1,11 +1,11 @@
 use std::sync::atomic::Ordering;
-use std::{fmt, sync::atomic::AtomicU64};
+use std::{fmt, str::FromStr, sync::atomic::AtomicU64};
 
 #[cfg(feature = "remote")]
 use internment::Intern;
 use serde::{Deserialize, Serialize};
 
-use crate::error::ActorIDFromBytesError;
+use crate::error::{ActorIDFromBytesError, ParseActorIDError};
 #[cfg(feature = "remote")]
 use crate::remote::ActorSwarm;
 
@@ -14,8 +14,34 @@ static ACTOR_COUNTER: AtomicU64 = AtomicU64::new(0);
 /// A globally unique identifier for an actor within a distributed system.
 ///
 /// `ActorID` combines a locally sequential `sequence_id` with an optional `peer_id`
-/// to uniquely identify actors across a distributed network.#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// to uniquely identify actors across a distributed network.
+///
+/// `ActorID`s are ordered by their `sequence_id`, followed by their `peer_id`.
+///
+/// # Textual Format
+///
+/// The [`Display`](fmt::Display) format is stable across versions, and can be parsed back with [`FromStr`],
+/// allowing IDs to be stored in external systems:
+///
+/// - `ActorID(<sequence_id>)` for local actors when the `remote` feature is disabled.
+/// - `ActorID(<sequence_id>, local)` for local actors when the `remote` feature is enabled.
+/// - `ActorID(<sequence_id>, <peer_id>)` for actors associated with a peer.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::ActorID;
+///
+/// let id = ActorID::new(42);
+/// let parsed: ActorID = id.to_string().parse()?;
+/// assert_eq!(id, parsed);
+///
+/// assert_eq!("ActorID(7)".parse::<ActorID>()?.sequence_id(), 7);
+/// assert_eq!("ActorID(7, local)".parse::<ActorID>()?.sequence_id(), 7);
+/// assert!("ActorID(seven)".parse::<ActorID>().is_err());
+/// # Ok::<(), kameo::error::ParseActorIDError>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ActorID {
     sequence_id: u64,
     /// None indicates a local actor; the local peer ID should be used in this case.
@@ -40,7 +66,7 @@ impl ActorID {
         ActorID {
             sequence_id,
             #[cfg(feature = "remote")]
-            peer_id: ActorSwarm::get().map(|swarm| swarm.local_peer_id_intern().clone()),
+            peer_id: ActorSwarm::get().map(|swarm| *swarm.local_peer_id_intern()),
         }
     }
 
@@ -193,6 +219,39 @@ impl fmt::Display for ActorID {
     }
 }
 
+impl FromStr for ActorID {
+    type Err = ParseActorIDError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .strip_prefix("ActorID(")
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or(ParseActorIDError::InvalidFormat)?;
+        let (sequence_id, peer_id) = match inner.split_once(", ") {
+            Some((sequence_id, peer_id)) => (sequence_id, Some(peer_id)),
+            None => (inner, None),
+        };
+        let sequence_id = sequence_id
+            .parse()
+            .map_err(ParseActorIDError::InvalidSequenceID)?;
+
+        match peer_id {
+            None | Some("local") => Ok(ActorID {
+                sequence_id,
+                #[cfg(feature = "remote")]
+                peer_id: None,
+            }),
+            #[cfg(feature = "remote")]
+            Some(peer_id) => Ok(ActorID {
+                sequence_id,
+                peer_id: Some(Intern::new(peer_id.parse()?)),
+            }),
+            #[cfg(not(feature = "remote"))]
+            Some(_) => Err(ParseActorIDError::InvalidFormat),
+        }
+    }
+}
+
 impl fmt::Debug for ActorID {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         #[cfg(feature = "remote")]
@@ -1,16 +1,27 @@
-use std::{collections::VecDeque, mem, panic::AssertUnwindSafe};
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::VecDeque,
+    future, mem,
+    panic::{self, AssertUnwindSafe},
+    pin,
+    sync::{atomic::Ordering, Mutex, Once},
+    time::Instant,
+};
 
 use futures::{Future, FutureExt};
+use tokio::sync::oneshot;
+use tracing::{trace, trace_span, warn, Instrument, Span};
 
 use crate::{
     actor::{Actor, ActorRef, WeakActorRef},
-    error::{ActorStopReason, PanicError},
-    mailbox::Signal,
-    message::DynMessage,
+    error::{ActorStopReason, PanicError, PanicLocation},
+    mailbox::{MessageOptions, Signal, UndeliveredMessage},
+    message::{BoxDebug, DynMessage, DynReadMessage},
     reply::BoxReplySender,
 };
 
-use super::ActorID;
+use super::{ActorID, CURRENT_DEADLINE, CURRENT_REPLY_OPTIONS, CURRENT_SENDER_ID};
 
 pub(crate) trait ActorState<A: Actor>: Sized {
     fn new_from_actor(actor: A, actor_ref: WeakActorRef<A>) -> Self;
@@ -23,22 +34,54 @@ pub(crate) trait ActorState<A: Actor>: Sized {
         actor_ref: ActorRef<A>,
         reply: Option<BoxReplySender>,
         sent_within_actor: bool,
+        options: Option<Box<MessageOptions>>,
     ) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
+    /// Handles a read message on its own, or buffers it if the actor is still starting up.
+    fn handle_read(
+        &mut self,
+        message: Box<dyn DynReadMessage<A>>,
+        actor_ref: ActorRef<A>,
+        reply: Option<BoxReplySender>,
+        sent_within_actor: bool,
+        options: Option<Box<MessageOptions>>,
+    ) -> impl Future<Output = Option<ActorStopReason>> + Send;
+
+    /// Returns whether reads can be handled concurrently with [`read`](ActorState::read),
+    /// rather than needing to be buffered until the actor has started.
+    fn reads_concurrently(&self) -> bool;
+
+    /// Handles a read message with shared access, so it can run concurrently with other reads.
+    fn read(
+        &self,
+        message: Box<dyn DynReadMessage<A>>,
+        actor_ref: ActorRef<A>,
+        reply: Option<BoxReplySender>,
+        options: Option<Box<MessageOptions>>,
+    ) -> impl Future<Output = Option<ActorStopReason>> + Send + '_;
+
     fn handle_link_died(
         &mut self,
         id: ActorID,
         reason: ActorStopReason,
     ) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
-    fn handle_stop(&mut self) -> impl Future<Output = Option<ActorStopReason>> + Send;
+    fn handle_stop(
+        &mut self,
+        reason: Option<ActorStopReason>,
+    ) -> impl Future<Output = Option<ActorStopReason>> + Send;
+
+    fn handle_await_state(&mut self, waiter: StateWaiter<A>);
+
+    fn handle_describe(&self, reply: oneshot::Sender<String>);
 
     fn on_shutdown(
         &mut self,
         reason: ActorStopReason,
     ) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
-    fn shutdown(self) -> impl Future<Output = A> + Send;
+    /// Returns the actor, along with any messages it received but never handled.
+    fn shutdown(self) -> impl Future<Output = (A, VecDeque<Signal<A>>)> + Send;
 }
 
 pub(crate) struct ActorBehaviour<A: Actor> {
@@ -46,6 +89,7 @@ pub(crate) struct ActorBehaviour<A: Actor> {
     state: A,
     finished_startup: bool,
     startup_buffer: VecDeque<Signal<A>>,
+    state_waiters: Vec<StateWaiter<A>>,
 }
 
 impl<A> ActorState<A> for ActorBehaviour<A>
@@ -59,21 +103,56 @@ where
             state: actor,
             finished_startup: false,
             startup_buffer: VecDeque::new(),
+            state_waiters: Vec::new(),
         }
     }
 
     async fn handle_startup_finished(&mut self) -> Option<ActorStopReason> {
         self.finished_startup = true;
-        for signal in mem::take(&mut self.startup_buffer).drain(..) {
+        // Priority messages sent during startup are handled before any buffered messages
+        let priority = self
+            .actor_ref
+            .loop_state
+            .stash
+            .lock()
+            .unwrap()
+            .finish_startup();
+        if let Some(actor_ref) = self.actor_ref.upgrade() {
+            for (message, reply, options) in priority {
+                if let Some(reason) = self
+                    .handle_message(message, actor_ref.clone(), reply, true, options)
+                    .await
+                {
+                    return Some(reason);
+                }
+            }
+        }
+        // Popped one at a time so unhandled messages are still buffered if the actor is killed
+        while let Some(signal) = self.startup_buffer.pop_front() {
             match signal {
                 Signal::Message {
                     message,
                     actor_ref,
                     reply,
                     sent_within_actor,
+                    options,
+                } => {
+                    if let Some(reason) = self
+                        .handle_message(message, actor_ref, reply, sent_within_actor, options)
+                        .await
+                    {
+                        return Some(reason);
+                    }
+                }
+                Signal::Read {
+                    message,
+                    actor_ref,
+                    reply,
+                    sent_within_actor,
+                    options,
                 } => {
                     if let Some(reason) = self
-                        .handle_message(message, actor_ref, reply, sent_within_actor)
+                        .handle_read(message, actor_ref, reply, sent_within_actor, options)
                         .await
                     {
                         return Some(reason);
@@ -93,25 +172,97 @@ where
         actor_ref: ActorRef<A>,
         reply: Option<BoxReplySender>,
         sent_within_actor: bool,
+        options: Option<Box<MessageOptions>>,
     ) -> Option<ActorStopReason> {
-        if !sent_within_actor && !self.finished_startup {
+        self.report_dropped();
+
+        let skip_startup_gate = options.as_ref().is_some_and(|o| o.skip_startup_gate);
+        if !sent_within_actor && !self.finished_startup && !skip_startup_gate {
             // The actor is still starting up, so we'll push this message to a buffer to be processed upon startup
             self.startup_buffer.push_back(Signal::Message {
                 message,
                 actor_ref,
                 reply,
                 sent_within_actor,
+                options,
+            });
+            return None;
+        }
+
+        if let Some(reason) = self
+            .handle_single_message(message, actor_ref.clone(), reply, options)
+            .await
+        {
+            return Some(reason);
+        }
+
+        // Messages unstashed by the handler are processed before any new messages in the mailbox
+        loop {
+            let (message, reply, options) = self
+                .actor_ref
+                .loop_state
+                .stash
+                .lock()
+                .unwrap()
+                .pop_unstashed()?;
+            if let Some(reason) = self
+                .handle_single_message(message, actor_ref.clone(), reply, options)
+                .await
+            {
+                return Some(reason);
+            }
+        }
+    }
+
+    #[inline]
+    async fn handle_read(
+        &mut self,
+        message: Box<dyn DynReadMessage<A>>,
+        actor_ref: ActorRef<A>,
+        reply: Option<BoxReplySender>,
+        sent_within_actor: bool,
+        options: Option<Box<MessageOptions>>,
+    ) -> Option<ActorStopReason> {
+        self.report_dropped();
+
+        if !sent_within_actor && !self.finished_startup {
+            self.startup_buffer.push_back(Signal::Read {
+                message,
+                actor_ref,
+                reply,
+                sent_within_actor,
+                options,
             });
             return None;
         }
 
-        let res = AssertUnwindSafe(message.handle_dyn(&mut self.state, actor_ref, reply))
-            .catch_unwind()
-            .await;
-        match res {
-            Ok(None) => None,
-            Ok(Some(err)) => Some(ActorStopReason::Panicked(PanicError::new(err))), // The reply was an error
-            Err(err) => Some(ActorStopReason::Panicked(PanicError::new_boxed(err))), // The handler panicked
+        self.read(message, actor_ref, reply, options).await
+    }
+
+    #[inline]
+    fn reads_concurrently(&self) -> bool {
+        self.finished_startup
+    }
+
+    fn read(
+        &self,
+        message: Box<dyn DynReadMessage<A>>,
+        actor_ref: ActorRef<A>,
+        reply: Option<BoxReplySender>,
+        options: Option<Box<MessageOptions>>,
+    ) -> impl Future<Output = Option<ActorStopReason>> + Send + '_ {
+        let options = options.map_or_else(MessageOptions::default, |options| *options);
+        let message_name = (*message).type_name();
+        let span = self.message_span(message_name, &options);
+        let handler = message.handle_dyn_read(&self.state, actor_ref, reply);
+        // Only owned values are held across awaits, since the actor itself isn't required to be `Sync`
+        let actor_ref = self.actor_ref.clone();
+        async move {
+            let start = Instant::now();
+            let res = catch_unwind_if(A::unwind_policy().handle, scope_options(options, handler))
+                .instrument(span.clone())
+                .await;
+            handled(&actor_ref, message_name, start, &span, res)
         }
     }
 
@@ -121,23 +272,40 @@ where
         id: ActorID,
         reason: ActorStopReason,
     ) -> Option<ActorStopReason> {
-        match AssertUnwindSafe(
-            self.state
-                .on_link_died(self.actor_ref.clone(), id, reason.clone()),
-        )
-        .catch_unwind()
+        let hooks = self.actor_ref.links.link_died_hooks();
+        let actor_ref = self.actor_ref.clone();
+        match catch_unwind_if(A::unwind_policy().handle, async {
+            for hook in &hooks {
+                hook(id, &reason);
+            }
+            self.state.on_link_died(actor_ref, id, reason.clone()).await
+        })
         .await
         {
             Ok(Ok(Some(reason))) => Some(reason),
             Ok(Ok(None)) => None,
             Ok(Err(err)) => Some(ActorStopReason::Panicked(PanicError::new(err))),
-            Err(err) => Some(ActorStopReason::Panicked(PanicError::new_boxed(err))),
+            Err(err) => Some(ActorStopReason::Panicked(err.into())),
         }
     }
 
     #[inline]
-    async fn handle_stop(&mut self) -> Option<ActorStopReason> {
-        Some(ActorStopReason::Normal)
+    async fn handle_stop(&mut self, reason: Option<ActorStopReason>) -> Option<ActorStopReason> {
+        Some(reason.unwrap_or(ActorStopReason::Normal))
+    }
+
+    #[inline]
+    fn handle_await_state(&mut self, waiter: StateWaiter<A>) {
+        self.state_waiters.push(waiter);
+        self.resolve_state_waiters();
+    }
+
+    #[inline]
+    fn handle_describe(&self, reply: oneshot::Sender<String>) {
+        // A panicking summary drops the reply, which the caller sees as the actor having stopped
+        if let Ok(description) = panic::catch_unwind(AssertUnwindSafe(|| self.state.describe())) {
+            let _ = reply.send(description);
+        }
     }
 
     #[inline]
@@ -145,6 +313,7 @@ where
         match reason {
             ActorStopReason::Normal => Some(ActorStopReason::Normal),
             ActorStopReason::Killed => Some(ActorStopReason::Killed),
+            ActorStopReason::KilledImmediately => Some(ActorStopReason::KilledImmediately),
             ActorStopReason::Panicked(err) => {
                 match self.state.on_panic(self.actor_ref.clone(), err).await {
                     Ok(Some(reason)) => Some(reason),
@@ -155,11 +324,364 @@ where
             ActorStopReason::LinkDied { id, reason } => {
                 Some(ActorStopReason::LinkDied { id, reason })
             }
+            reason @ ActorStopReason::Custom(_) => Some(reason),
         }
     }
 
     #[inline]
-    async fn shutdown(self) -> A {
-        self.state
+    async fn shutdown(self) -> (A, VecDeque<Signal<A>>) {
+        (self.state, self.startup_buffer)
+    }
+}
+
+impl<A: Actor> ActorBehaviour<A> {
+    async fn handle_single_message(
+        &mut self,
+        message: Box<dyn DynMessage<A>>,
+        actor_ref: ActorRef<A>,
+        reply: Option<BoxReplySender>,
+        options: Option<Box<MessageOptions>>,
+    ) -> Option<ActorStopReason> {
+        let options = options.map_or_else(MessageOptions::default, |options| *options);
+        let message_name = (*message).type_name();
+        let span = self.message_span(message_name, &options);
+        let start = Instant::now();
+        let res = catch_unwind_if(
+            A::unwind_policy().handle,
+            scope_options(
+                options,
+                message.handle_dyn(&mut self.state, actor_ref, reply),
+            ),
+        )
+        .instrument(span.clone())
+        .await;
+        let reason = handled(&self.actor_ref, message_name, start, &span, res);
+        self.resolve_state_waiters();
+        reason
+    }
+
+    /// Records the message in the trace, and returns the span it's handled in.
+    fn message_span(&self, message_name: &'static str, options: &MessageOptions) -> Span {
+        #[cfg(feature = "message-trace")]
+        self.actor_ref.message_trace.record(message_name);
+        if A::trace_messages() {
+            trace_span!(
+                "handle_message",
+                id = %self.actor_ref.id(),
+                name = %super::name::<A>(),
+                message = %message_name,
+                sender = options.sender_id.map(tracing::field::display),
+            )
+        } else {
+            Span::none()
+        }
+    }
+
+    /// Calls [`Actor::on_message_dropped`] if a lossy mailbox discarded messages since the last call.
+    fn report_dropped(&mut self) {
+        let dropped = self.actor_ref.counters.dropped.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            self.state.on_message_dropped(dropped);
+        }
+    }
+}
+
+/// Runs a handler with the sender's options available to it.
+///
+/// This is called as the message starts being handled, which is recorded for asks sent with timing.
+fn scope_options<F: Future>(
+    options: MessageOptions,
+    handler: F,
+) -> impl Future<Output = F::Output> {
+    if let Some(handling_started) = &options.handling_started {
+        let _ = handling_started.set(tokio::time::Instant::now());
+    }
+    CURRENT_DEADLINE.scope(
+        options.deadline,
+        CURRENT_REPLY_OPTIONS.scope(
+            options.reply,
+            CURRENT_SENDER_ID.scope(options.sender_id, handler),
+        ),
+    )
+}
+
+/// Counts a handled message, warning if it was slow, and returns the reason to stop if its handler failed.
+fn handled<A: Actor>(
+    actor_ref: &WeakActorRef<A>,
+    message_name: &'static str,
+    start: Instant,
+    span: &Span,
+    res: Result<Option<BoxDebug>, CaughtPanic>,
+) -> Option<ActorStopReason> {
+    if A::trace_messages() {
+        trace!(parent: span, elapsed = ?start.elapsed(), "handled message");
+    }
+    actor_ref.counters.processed.fetch_add(1, Ordering::Relaxed);
+    #[cfg(feature = "metrics")]
+    super::metrics::message_handled::<A>(start.elapsed());
+    if let Some(threshold) = A::slow_handler_threshold() {
+        let elapsed = start.elapsed();
+        if elapsed > threshold {
+            warn!(
+                id = %actor_ref.id(),
+                name = %super::name::<A>(),
+                message = %message_name,
+                ?elapsed,
+                ?threshold,
+                "slow message handler"
+            );
+        }
+    }
+    let err = match res {
+        Ok(None) => return None,
+        Ok(Some(err)) => PanicError::new(err), // The reply was an error
+        Err(err) => err.into(),                // The handler panicked
+    };
+    #[cfg(feature = "message-trace")]
+    let err = err.with_recent_messages(actor_ref.message_trace.snapshot());
+    Some(ActorStopReason::Panicked(err))
+}
+
+impl<A: Actor> ActorBehaviour<A> {
+    pub(crate) fn actor(&self) -> &A {
+        &self.state
+    }
+
+    pub(crate) fn actor_mut(&mut self) -> &mut A {
+        &mut self.state
+    }
+
+    /// Resolves waiters registered with [`ActorRef::await_state`] whose predicate now holds.
+    ///
+    /// Waiters which are no longer being awaited, or whose predicate panics, are removed.
+    fn resolve_state_waiters(&mut self) {
+        if self.state_waiters.is_empty() {
+            return;
+        }
+        let waiters = mem::take(&mut self.state_waiters);
+        for waiter in waiters {
+            if waiter.tx.is_closed() {
+                continue;
+            }
+            match panic::catch_unwind(AssertUnwindSafe(|| (waiter.predicate)(&self.state))) {
+                Ok(true) => {
+                    let _ = waiter.tx.send(());
+                }
+                Ok(false) => self.state_waiters.push(waiter),
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+/// A predicate on an actor's state, resolved once a message handled makes it hold.
+#[allow(missing_debug_implementations)]
+pub struct StateWaiter<A> {
+    predicate: Box<dyn Fn(&A) -> bool + Send>,
+    tx: oneshot::Sender<()>,
+}
+
+impl<A> StateWaiter<A> {
+    pub(crate) fn new(
+        predicate: impl Fn(&A) -> bool + Send + 'static,
+        tx: oneshot::Sender<()>,
+    ) -> Self {
+        StateWaiter {
+            predicate: Box::new(predicate),
+            tx,
+        }
+    }
+}
+
+/// Awaits the future, catching any panic if `catch` is `true`.
+pub(crate) async fn catch_unwind_if<F: Future>(
+    catch: bool,
+    fut: F,
+) -> Result<F::Output, CaughtPanic> {
+    if !catch {
+        return Ok(fut.await);
+    }
+
+    INSTALL_PANIC_HOOK.call_once(install_panic_hook);
+    let mut fut = pin::pin!(fut);
+    AssertUnwindSafe(future::poll_fn(|cx| {
+        let _capturing = CapturingPanics::enter();
+        fut.as_mut().poll(cx)
+    }))
+    .catch_unwind()
+    .await
+    .map_err(|payload| CaughtPanic {
+        payload,
+        // Panics are caught within the same poll, so the location is still on this thread
+        location: PANIC_LOCATION.with(|location| location.take()),
+    })
+}
+
+/// A panic caught by [`catch_unwind_if`], along with where it was raised.
+pub(crate) struct CaughtPanic {
+    pub(crate) payload: Box<dyn Any + Send>,
+    location: Option<PanicLocation>,
+}
+
+impl From<CaughtPanic> for PanicError {
+    fn from(panic: CaughtPanic) -> Self {
+        PanicError::new_boxed(panic.payload).with_location(panic.location)
+    }
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    /// Whether the current thread is polling an actor, and so should record the location of panics.
+    static CAPTURING_PANICS: Cell<bool> = const { Cell::new(false) };
+    /// The location of the last panic on this thread while capturing.
+    static PANIC_LOCATION: Cell<Option<PanicLocation>> = const { Cell::new(None) };
+}
+
+/// Wraps the current panic hook with one recording panic locations, only on threads which are polling an actor.
+///
+/// This is installed once for the whole process rather than around each `catch_unwind`, since the hook is global and
+/// swapping it while other threads may be panicking would lose their panics. See [`PanicError::location`].
+fn install_panic_hook() {
+    let prev_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CAPTURING_PANICS.with(Cell::get) {
+            let location = info.location().map(PanicLocation::from);
+            PANIC_LOCATION.with(|prev| prev.set(location));
+        }
+        prev_hook(info);
+    }));
+}
+
+/// Marks the current thread as capturing panic locations until dropped, including while unwinding.
+struct CapturingPanics {
+    was_capturing: bool,
+}
+
+impl CapturingPanics {
+    fn enter() -> Self {
+        // Clears any location left by a panic which was caught elsewhere, since `resume_unwind` doesn't call the hook
+        PANIC_LOCATION.with(|location| location.set(None));
+        CapturingPanics {
+            was_capturing: CAPTURING_PANICS.with(|capturing| capturing.replace(true)),
+        }
+    }
+}
+
+impl Drop for CapturingPanics {
+    fn drop(&mut self) {
+        CAPTURING_PANICS.with(|capturing| capturing.set(self.was_capturing));
+    }
+}
+
+/// State which an actor's refs share with its message loop.
+pub(crate) struct LoopState<A: Actor> {
+    pub(crate) stash: Mutex<Stash<A>>,
+    pub(crate) stop: Mutex<StopState<A>>,
+}
+
+impl<A: Actor> Default for LoopState<A> {
+    fn default() -> Self {
+        LoopState {
+            stash: Mutex::default(),
+            stop: Mutex::default(),
+        }
+    }
+}
+
+/// Messages stashed by an actor to be processed later, see [`Context::stash`](crate::message::Context::stash).
+pub(crate) struct Stash<A: Actor> {
+    stashed: VecDeque<StashedMessage<A>>,
+    unstashed: VecDeque<StashedMessage<A>>,
+    /// Messages sent with [`ActorRef::tell_self_priority`](crate::actor::ActorRef::tell_self_priority),
+    /// or `None` once startup has finished.
+    startup: Option<VecDeque<StashedMessage<A>>>,
+}
+
+type StashedMessage<A> = (
+    Box<dyn DynMessage<A>>,
+    Option<BoxReplySender>,
+    Option<Box<MessageOptions>>,
+);
+
+impl<A: Actor> Stash<A> {
+    pub(crate) fn push(
+        &mut self,
+        message: Box<dyn DynMessage<A>>,
+        reply: Option<BoxReplySender>,
+        options: Option<Box<MessageOptions>>,
+    ) {
+        self.stashed.push_back((message, reply, options));
+    }
+
+    pub(crate) fn unstash_all(&mut self) {
+        self.unstashed.append(&mut self.stashed);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.stashed.len()
+    }
+
+    fn pop_unstashed(&mut self) -> Option<StashedMessage<A>> {
+        self.unstashed.pop_front()
+    }
+
+    /// Queues a message to be handled first once startup finishes, returning it if startup has already finished.
+    pub(crate) fn push_startup(
+        &mut self,
+        message: Box<dyn DynMessage<A>>,
+    ) -> Result<(), Box<dyn DynMessage<A>>> {
+        match &mut self.startup {
+            Some(startup) => {
+                startup.push_back((message, None, None));
+                Ok(())
+            }
+            None => Err(message),
+        }
+    }
+
+    fn finish_startup(&mut self) -> VecDeque<StashedMessage<A>> {
+        self.startup.take().unwrap_or_default()
+    }
+
+    /// Takes every message left in the stash once the actor stops, in the order they would have been handled.
+    pub(crate) fn drain_undelivered(&mut self) -> VecDeque<UndeliveredMessage<A>> {
+        self.finish_startup()
+            .into_iter()
+            .chain(self.unstashed.drain(..))
+            .chain(self.stashed.drain(..))
+            .map(|(message, reply, options)| {
+                UndeliveredMessage::from_stashed(message, reply, options)
+            })
+            .collect()
+    }
+}
+
+impl<A: Actor> Default for Stash<A> {
+    fn default() -> Self {
+        Stash {
+            stashed: VecDeque::new(),
+            unstashed: VecDeque::new(),
+            startup: Some(VecDeque::new()),
+        }
+    }
+}
+
+/// How an actor was asked to stop, which its message loop reads once it stops.
+pub(crate) struct StopState<A: Actor> {
+    /// Whether the actor was killed with [`ActorRef::kill_immediate`](crate::actor::ActorRef::kill_immediate),
+    /// skipping its `on_stop` hook.
+    pub(crate) killed_immediately: bool,
+    /// Actor given to [`ActorRef::transfer_mailbox_to`](crate::actor::ActorRef::transfer_mailbox_to),
+    /// which unprocessed messages are redelivered to once the actor stops.
+    pub(crate) transfer_to: Option<ActorRef<A>>,
+}
+
+impl<A: Actor> Default for StopState<A> {
+    fn default() -> Self {
+        StopState {
+            killed_immediately: false,
+            transfer_to: None,
+        }
     }
 }
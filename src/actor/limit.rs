@@ -0,0 +1,149 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{error::SpawnError, Actor};
+
+use super::{prepare, ActorRef};
+
+/// The spawn categories used with [`try_spawn_limited`], created when first used.
+///
+/// Categories without a limit are removed once they have no alive actors, so using arbitrary category names such as
+/// request ids doesn't grow this forever.
+static SPAWN_CATEGORIES: Lazy<Mutex<HashMap<String, Arc<SpawnCategory>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Sets the maximum number of alive actors spawned with [`try_spawn_limited`] in `category`.
+///
+/// Categories are arbitrary strings, such as a tenant id or the kind of work an actor does, and have no limit until
+/// one is set. To limit every actor spawned with [`try_spawn_limited`], use the same category each time.
+///
+/// The limit can be changed at any time. Lowering it below the number of alive actors doesn't stop any of them,
+/// but rejects new actors until enough have stopped.
+///
+/// A category with a limit is kept for the life of the process, so limits should be set for a bounded set of
+/// categories. Setting the limit back to `usize::MAX` removes it, and the category is forgotten once its actors stop.
+pub fn set_spawn_limit(category: &str, limit: usize) {
+    let category = spawn_category(category);
+    category.limit.store(limit, Ordering::Relaxed);
+    remove_if_unused(&category);
+}
+
+/// Spawns an actor if `category` has not reached its limit of alive actors, see [`set_spawn_limit`].
+///
+/// This is admission control for spawning, which prevents untrusted input from creating an unbounded number of actors.
+/// The actor counts towards the limit from when it's spawned, until it has stopped and run [`Actor::on_stop`].
+///
+/// If the limit has been reached, the actor is returned in [`SpawnError::LimitReached`] without being spawned.
+///
+/// # Example
+///
+/// ```
+/// use kameo::error::SpawnError;
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct Session;
+///
+/// # tokio_test::block_on(async {
+/// kameo::actor::set_spawn_limit("tenant-a", 1);
+///
+/// let session = kameo::actor::try_spawn_limited("tenant-a", Session)?;
+/// assert!(matches!(
+///     kameo::actor::try_spawn_limited("tenant-a", Session),
+///     Err(SpawnError::LimitReached(_))
+/// ));
+///
+/// // Stopping an actor frees up its place
+/// session.stop_gracefully().await?;
+/// session.wait_for_stop().await;
+/// kameo::actor::try_spawn_limited("tenant-a", Session)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub fn try_spawn_limited<A>(category: &str, actor: A) -> Result<ActorRef<A>, SpawnError<A>>
+where
+    A: Actor,
+{
+    let Some(permit) = SpawnPermit::try_acquire(spawn_category(category)) else {
+        return Err(SpawnError::LimitReached(actor));
+    };
+
+    let prepared_actor = prepare(actor);
+    let actor_ref = prepared_actor.actor_ref().clone();
+    *actor_ref.counters.spawn_permit.lock().unwrap() = Some(permit);
+    prepared_actor.spawn();
+    Ok(actor_ref)
+}
+
+fn spawn_category(category: &str) -> Arc<SpawnCategory> {
+    SPAWN_CATEGORIES
+        .lock()
+        .unwrap()
+        .entry(category.to_string())
+        .or_insert_with(|| {
+            Arc::new(SpawnCategory {
+                name: category.to_string(),
+                limit: AtomicUsize::new(usize::MAX),
+                alive: AtomicUsize::new(0),
+            })
+        })
+        .clone()
+}
+
+#[derive(Debug)]
+struct SpawnCategory {
+    name: String,
+    limit: AtomicUsize,
+    alive: AtomicUsize,
+}
+
+/// A place in a spawn category, held by an actor until it stops.
+#[derive(Debug)]
+pub(crate) struct SpawnPermit {
+    category: Arc<SpawnCategory>,
+}
+
+impl SpawnPermit {
+    fn try_acquire(category: Arc<SpawnCategory>) -> Option<Self> {
+        let limit = category.limit.load(Ordering::Relaxed);
+        category
+            .alive
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |alive| {
+                (alive < limit).then_some(alive + 1)
+            })
+            .ok()?;
+        Some(SpawnPermit { category })
+    }
+}
+
+impl Drop for SpawnPermit {
+    fn drop(&mut self) {
+        self.category.alive.fetch_sub(1, Ordering::AcqRel);
+        remove_if_unused(&self.category);
+    }
+}
+
+/// Removes a category without a limit from [`SPAWN_CATEGORIES`] if the caller holds its only other reference.
+///
+/// Every permit and every caller of [`spawn_category`] holds a reference, so no actors are alive in the category
+/// and none are being spawned in it.
+fn remove_if_unused(category: &Arc<SpawnCategory>) {
+    let mut categories = SPAWN_CATEGORIES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let unused = Arc::strong_count(category) == 2
+        && category.limit.load(Ordering::Relaxed) == usize::MAX
+        && categories
+            .get(&category.name)
+            .is_some_and(|registered| Arc::ptr_eq(registered, category));
+    if unused {
+        categories.remove(&category.name);
+    }
+}
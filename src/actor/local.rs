@@ -0,0 +1,139 @@
+use std::{fmt, future::Future, panic, thread};
+
+use tokio::{
+    runtime,
+    sync::{mpsc, oneshot},
+    task::{self, LocalSet},
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A dedicated thread running a [`LocalSet`], for handling messages which need to do `!Send` work.
+///
+/// Message handlers must be `Send`, since actors can be moved between threads. A handler can instead pass a closure
+/// to [`run`](LocalRunner::run), which creates a future on the runner's thread and waits for its output. The
+/// future never leaves that thread, so it can hold `!Send` values such as `Rc` or `RefCell`, or call
+/// [`spawn_local`](tokio::task::spawn_local).
+///
+/// # Constraints
+///
+/// - The message itself, the closure, and the output must all be `Send`. Only the values created by the closure
+///   may be `!Send`, so they must be constructed on the runner's thread.
+/// - The actor waits for the output, so messages are still processed one at a time. Work from other actors sharing
+///   the runner runs concurrently on the same thread, and a blocking call blocks all of it.
+/// - The thread runs its own current thread tokio runtime, which drives any timers or IO created on it.
+/// - A panic in the future is resumed in the handler awaiting it, so it's handled by the actor's
+///   [`unwind_policy`](crate::Actor::unwind_policy) as if it happened in the handler.
+///
+/// Clones share the same thread, which stops once every clone is dropped and its last futures have completed.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// use kameo::actor::LocalRunner;
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor)]
+/// struct Renderer {
+///     local: LocalRunner,
+/// }
+///
+/// struct Render(String);
+///
+/// impl Message<Render> for Renderer {
+///     type Reply = usize;
+///
+///     async fn handle(&mut self, Render(text): Render, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.local
+///             .run(move || async move {
+///                 // `Rc` is `!Send`, so it can only be used on the runner's thread
+///                 let shared = Rc::new(text);
+///                 let copy = Rc::clone(&shared);
+///                 tokio::task::yield_now().await;
+///                 copy.len()
+///             })
+///             .await
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let renderer = kameo::spawn(Renderer { local: LocalRunner::new() });
+/// assert_eq!(renderer.ask(Render("hello".to_string())).await?, 5);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct LocalRunner {
+    tx: mpsc::UnboundedSender<Job>,
+}
+
+impl LocalRunner {
+    /// Starts a new runner thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runner's runtime or thread cannot be created.
+    pub fn new() -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Job>();
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build local runner runtime");
+        thread::Builder::new()
+            .name("kameo-local-runner".to_string())
+            .spawn(move || {
+                let local = LocalSet::new();
+                local.spawn_local(async move {
+                    while let Some(job) = rx.recv().await {
+                        job();
+                    }
+                });
+                // Runs until every job has completed, after all runners are dropped
+                rt.block_on(local);
+            })
+            .expect("failed to spawn local runner thread");
+
+        LocalRunner { tx }
+    }
+
+    /// Runs the future returned by `f` on the runner's thread, returning its output.
+    ///
+    /// `f` is called on the runner's thread, so the future it returns doesn't need to be `Send`.
+    pub async fn run<F, Fut>(&self, f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let job: Job = Box::new(move || {
+            let handle = task::spawn_local(async move { f().await });
+            task::spawn_local(async move {
+                let _ = reply_tx.send(handle.await);
+            });
+        });
+        self.tx
+            .send(job)
+            .expect("local runner thread is held open by its runners");
+
+        match reply_rx.await.expect("local runner dropped a running job") {
+            Ok(output) => output,
+            Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+            Err(err) => panic!("local runner job was cancelled: {err}"),
+        }
+    }
+}
+
+impl Default for LocalRunner {
+    fn default() -> Self {
+        LocalRunner::new()
+    }
+}
+
+impl fmt::Debug for LocalRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalRunner").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,48 @@
+//! Metrics emitted with the [`metrics`](::metrics) crate, labelled with the actor's [`name`](Actor::name).
+//!
+//! - `kameo_actors_alive`: gauge of actors currently running.
+//! - `kameo_messages_processed_total`: counter of messages handled.
+//! - `kameo_handler_duration_seconds`: histogram of how long each message handler took.
+//! - `kameo_mailbox_depth`: gauge of the signals queued in the actor's mailbox, sampled as each message is received.
+
+use std::time::Duration;
+
+use ::metrics::{counter, gauge, histogram};
+
+use crate::mailbox::Signal;
+
+use super::Actor;
+
+/// Counts the actor as alive until dropped, including when its task is aborted.
+pub(crate) struct AliveGuard {
+    name: &'static str,
+}
+
+impl AliveGuard {
+    pub(crate) fn new<A: Actor>() -> Self {
+        let name = super::name::<A>();
+        gauge!("kameo_actors_alive", "actor" => name).increment(1.0);
+        AliveGuard { name }
+    }
+}
+
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        gauge!("kameo_actors_alive", "actor" => self.name).decrement(1.0);
+    }
+}
+
+/// Records a handled message and how long its handler took.
+pub(crate) fn message_handled<A: Actor>(elapsed: Duration) {
+    let name = super::name::<A>();
+    counter!("kameo_messages_processed_total", "actor" => name).increment(1);
+    histogram!("kameo_handler_duration_seconds", "actor" => name).record(elapsed);
+}
+
+/// Records the number of signals queued in the actor's mailbox, if the received signal is a message.
+pub(crate) fn mailbox_depth<A: Actor>(signal: &Signal<A>) {
+    if let Signal::Message { actor_ref, .. } | Signal::Read { actor_ref, .. } = signal {
+        gauge!("kameo_mailbox_depth", "actor" => super::name::<A>())
+            .set(actor_ref.mailbox_len() as f64);
+    }
+}
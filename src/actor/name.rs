@@ -0,0 +1,51 @@
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
+use once_cell::sync::Lazy;
+
+use super::Actor;
+
+/// Names set at runtime with [`set_name`], taking precedence over [`Actor::name`].
+static NAME_OVERRIDES: Lazy<RwLock<HashMap<TypeId, &'static str>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Overrides the name used for an actor type at runtime, in place of [`Actor::name`].
+///
+/// The name is used in tracing logs, and for the tokio task and thread names of actors spawned after it is set.
+/// This is useful for generic actors, where the type name includes each type parameter,
+/// and the name needs to depend on runtime configuration.
+///
+/// Names known at compile time can instead be set with `#[actor(name = "...")]` when deriving [`Actor`],
+/// or by implementing [`Actor::name`].
+///
+/// # Example
+///
+/// ```
+/// use std::marker::PhantomData;
+///
+/// #[derive(kameo::Actor)]
+/// struct Worker<T: Send + 'static>(PhantomData<T>);
+///
+/// kameo::actor::set_name::<Worker<String>>("StringWorker");
+/// assert_eq!(kameo::actor::name::<Worker<String>>(), "StringWorker");
+/// assert_eq!(kameo::actor::name::<Worker<u32>>(), "Worker");
+/// ```
+pub fn set_name<A: Actor>(name: &'static str) {
+    NAME_OVERRIDES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(TypeId::of::<A>(), name);
+}
+
+/// Returns the name of an actor type, which is the name set with [`set_name`], or otherwise [`Actor::name`].
+pub fn name<A: Actor>() -> &'static str {
+    NAME_OVERRIDES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&TypeId::of::<A>())
+        .copied()
+        .unwrap_or_else(A::name)
+}
@@ -0,0 +1,86 @@
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{
+    error::SendError,
+    message::Message,
+    request::{LocalTellRequest, MessageSend, TellRequest, WithoutRequestTimeout},
+    Actor, Reply,
+};
+
+use super::ActorRef;
+
+/// Forwards every message received on a channel to an actor as a tell, until the channel is closed.
+///
+/// This eases migrating code built on hand-rolled `mpsc` channels to kameo, since existing senders
+/// can keep sending to the channel while the receiving end is handled by an actor.
+///
+/// The forwarding task holds a strong ref to the actor, and stops when the channel is closed and empty,
+/// or when the actor stops. If the actor stops, any messages remaining in the channel are dropped.
+/// The returned `JoinHandle` can be used to wait for the task, or abort it.
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Context, Message};
+/// use tokio::sync::mpsc;
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Sum(u32);
+///
+/// impl Message<u32> for Sum {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, n: u32, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.0 += n;
+///     }
+/// }
+///
+/// struct Total;
+///
+/// impl Message<Total> for Sum {
+///     type Reply = u32;
+///
+///     async fn handle(&mut self, _: Total, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.0
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let (tx, rx) = mpsc::channel(8);
+/// let actor_ref = kameo::spawn(Sum::default());
+/// let forwarding = kameo::pipe_into(rx, &actor_ref);
+///
+/// for n in 1..=3 {
+///     tx.send(n).await?;
+/// }
+/// drop(tx);
+/// forwarding.await?;
+///
+/// assert_eq!(actor_ref.ask(Total).await?, 6);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub fn pipe_into<A, M>(mut rx: mpsc::Receiver<M>, actor_ref: &ActorRef<A>) -> JoinHandle<()>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+        MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+{
+    let actor_ref = actor_ref.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                msg = rx.recv() => {
+                    let Some(msg) = msg else {
+                        break;
+                    };
+                    if actor_ref.tell(msg).send().await.is_err() {
+                        break;
+                    }
+                }
+                _ = actor_ref.wait_for_stop() => break,
+            }
+        }
+    })
+}
@@ -303,7 +303,7 @@ where
             }
         }
 
-        return WorkerReply::Err(SendError::ActorNotRunning(msg));
+        WorkerReply::Err(SendError::ActorNotRunning(msg))
     }
 }
 
@@ -6,12 +6,13 @@
 //! to react to the same event or data.
 //!
 //! `PubSub` can be used either as a standalone object or as a spawned actor. When spawned as an actor,
-//! the `Publish(msg)` and `Subscribe(actor_ref)` messages are used to interact with it.
+//! the `Publish(msg)` and `Subscribe::new(actor_ref)` messages are used to interact with it.
 //!
 //! # Features
 //! - **Publish-Subscribe Pattern**: Actors can subscribe to the `PubSub` actor to receive broadcast messages.
 //! - **Message Broadcasting**: Messages published to the `PubSub` actor are sent to all subscribed actors.
 //! - **Subscriber Management**: Actors can subscribe and unsubscribe dynamically, allowing flexible message routing.
+//! - **Filtering**: Subscribers can provide a filter, so they only receive the messages they are interested in.
 //!
 //! # Example
 //!
@@ -38,15 +39,21 @@
 //!
 //! // Or spawn PubSub as an actor and use messages
 //! let pubsub_actor_ref = kameo::spawn(PubSub::new());
-//! pubsub_actor_ref.tell(Subscribe(actor_ref)).await?;
+//! pubsub_actor_ref.tell(Subscribe::new(actor_ref)).await?;
 //! pubsub_actor_ref.tell(Publish("Hello, spawned world!")).await?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! # });
 //! ```
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+};
 
 use futures::future::{join_all, BoxFuture};
+use tracing::warn;
 
 use crate::{
     error::SendError,
@@ -61,12 +68,20 @@ use super::{ActorID, ActorRef};
 /// A publish-subscribe (pubsub) actor that allows message broadcasting to multiple subscribers.
 ///
 /// `PubSub` can be used as a standalone object or spawned as an actor. When spawned, messages can
-/// be sent using the `Publish(msg)` and `Subscribe(actor_ref)` messages to publish data and manage subscribers.
+/// be sent using the `Publish(msg)` and `Subscribe::new(actor_ref)` messages to publish data and manage subscribers.
 /// This provides flexibility in how you interact with the pubsub system, depending on whether you want
 /// to manage it directly or interact with it via messages.
 #[allow(missing_debug_implementations)]
 pub struct PubSub<M> {
-    subscribers: HashMap<ActorID, Box<dyn MessageSubscriber<M> + Send + Sync>>,
+    subscribers: HashMap<ActorID, Subscriber<M>>,
+}
+
+/// A filter deciding which published messages a subscriber receives, see [`Subscribe::filtered`].
+pub type SubscribeFilter<M> = Arc<dyn Fn(&M) -> bool + Send + Sync>;
+
+struct Subscriber<M> {
+    actor_ref: Box<dyn MessageSubscriber<M> + Send + Sync>,
+    filter: Option<SubscribeFilter<M>>,
 }
 
 impl<M> PubSub<M> {
@@ -82,7 +97,9 @@ impl<M> PubSub<M> {
     /// Publishes a message to all subscribed actors.
     ///
     /// The message is cloned and sent to each subscriber. Any actor subscribed to the `PubSub` actor
-    /// will receive a copy of the message.
+    /// will receive a copy of the message, unless its filter rejects it.
+    ///
+    /// Filters are evaluated before sending, and a subscriber whose filter panics is unsubscribed.
     ///
     /// # Example
     ///
@@ -104,11 +121,32 @@ impl<M> PubSub<M> {
     where
         M: Clone + Send + 'static,
     {
-        let results = join_all(self.subscribers.iter().map(|(id, subscriber)| {
-            let msg = msg.clone();
-            async move { (*id, subscriber.tell(msg).await) }
-        }))
+        let mut panicked = Vec::new();
+        let results = join_all(
+            self.subscribers
+                .iter()
+                .filter(|(id, subscriber)| {
+                    let Some(filter) = &subscriber.filter else {
+                        return true;
+                    };
+                    match panic::catch_unwind(AssertUnwindSafe(|| filter(&msg))) {
+                        Ok(matches) => matches,
+                        Err(_) => {
+                            panicked.push(**id);
+                            false
+                        }
+                    }
+                })
+                .map(|(id, subscriber)| {
+                    let msg = msg.clone();
+                    async move { (*id, subscriber.actor_ref.tell(msg).await) }
+                }),
+        )
         .await;
+        for id in panicked {
+            warn!(%id, "subscriber filter panicked, unsubscribing");
+            self.subscribers.remove(&id);
+        }
         for (id, result) in results.into_iter() {
             match result {
                 Ok(_) => {}
@@ -117,7 +155,9 @@ impl<M> PubSub<M> {
                 }
                 Err(SendError::MailboxFull(_))
                 | Err(SendError::HandlerError(_))
-                | Err(SendError::Timeout(_)) => {}
+                | Err(SendError::Timeout(_))
+                | Err(SendError::TooLarge(_))
+                | Err(SendError::Overloaded(_)) => {}
             }
         }
     }
@@ -160,7 +200,66 @@ impl<M> PubSub<M> {
         for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
             MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
     {
-        self.subscribers.insert(actor_ref.id(), Box::new(actor_ref));
+        self.insert_subscriber(actor_ref, None);
+    }
+
+    /// Subscribes an actor to receive only the published messages matching `filter`.
+    ///
+    /// The filter is evaluated when a message is published, so the actor is not sent messages it would ignore.
+    /// If the filter panics, the actor is unsubscribed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kameo::Actor;
+    /// use kameo::actor::pubsub::PubSub;
+    /// # use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # impl Message<Temperature> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Temperature, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// #[derive(Clone)]
+    /// struct Temperature(f32);
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut pubsub = PubSub::new();
+    ///
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// pubsub.subscribe_filtered(actor_ref, |Temperature(celsius)| *celsius > 30.0);
+    /// pubsub.publish(Temperature(21.5)).await; // Not sent to the actor
+    /// # })
+    /// ```
+    #[inline]
+    pub fn subscribe_filtered<A, F>(&mut self, actor_ref: ActorRef<A>, filter: F)
+    where
+        A: Actor + Message<M>,
+        M: Send + 'static,
+        F: Fn(&M) -> bool + Send + Sync + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        self.insert_subscriber(actor_ref, Some(Arc::new(filter)));
+    }
+
+    fn insert_subscriber<A>(&mut self, actor_ref: ActorRef<A>, filter: Option<SubscribeFilter<M>>)
+    where
+        A: Actor + Message<M>,
+        M: Send + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        self.subscribers.insert(
+            actor_ref.id(),
+            Subscriber {
+                actor_ref: Box::new(actor_ref),
+                filter,
+            },
+        );
     }
 }
 
@@ -199,11 +298,99 @@ where
 /// A message used to subscribe an actor to a `PubSub` actor.
 ///
 /// This struct wraps an `ActorRef` and is used to subscribe an actor to a pubsub actor. Once subscribed,
-/// the actor will receive all published messages from the pubsub actor.
-#[derive(Clone, Debug)]
-pub struct Subscribe<A: Actor>(pub ActorRef<A>);
+/// the actor will receive all published messages from the pubsub actor which match its filter, if any.
+pub struct Subscribe<A: Actor, M> {
+    /// The actor to subscribe.
+    pub subscriber: ActorRef<A>,
+    /// Only messages for which the filter returns `true` are sent to the subscriber.
+    pub filter: Option<SubscribeFilter<M>>,
+}
+
+impl<A: Actor, M> Subscribe<A, M> {
+    /// Creates a subscription receiving all published messages.
+    pub fn new(subscriber: ActorRef<A>) -> Self {
+        Subscribe {
+            subscriber,
+            filter: None,
+        }
+    }
+
+    /// Creates a subscription receiving only the published messages matching `filter`.
+    ///
+    /// See [`PubSub::subscribe_filtered`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::pubsub::{PubSub, Publish, Subscribe};
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Counter(u32);
+    ///
+    /// impl Message<u32> for Counter {
+    ///     type Reply = ();
+    ///     async fn handle(&mut self, _: u32, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// struct Count;
+    ///
+    /// impl Message<Count> for Counter {
+    ///     type Reply = u32;
+    ///     async fn handle(&mut self, _: Count, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let pubsub = kameo::spawn(PubSub::<u32>::new());
+    /// let evens = kameo::spawn(Counter::default());
+    /// let broken = kameo::spawn(Counter::default());
+    /// pubsub.ask(Subscribe::filtered(evens.clone(), |n| n % 2 == 0)).await?;
+    /// // A panicking filter only unsubscribes its own subscriber
+    /// pubsub.ask(Subscribe::filtered(broken.clone(), |_| panic!("oops"))).await?;
+    ///
+    /// for n in 0..10 {
+    ///     pubsub.ask(Publish(n)).await?;
+    /// }
+    /// assert_eq!(evens.ask(Count).await?, 5);
+    /// assert_eq!(broken.ask(Count).await?, 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn filtered(
+        subscriber: ActorRef<A>,
+        filter: impl Fn(&M) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Subscribe {
+            subscriber,
+            filter: Some(Arc::new(filter)),
+        }
+    }
+}
+
+impl<A: Actor, M> Clone for Subscribe<A, M> {
+    fn clone(&self) -> Self {
+        Subscribe {
+            subscriber: self.subscriber.clone(),
+            filter: self.filter.clone(),
+        }
+    }
+}
+
+impl<A: Actor, M> fmt::Debug for Subscribe<A, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscribe")
+            .field("subscriber", &self.subscriber)
+            .field("filtered", &self.filter.is_some())
+            .finish()
+    }
+}
 
-impl<A, M> Message<Subscribe<A>> for PubSub<M>
+impl<A, M> Message<Subscribe<A, M>> for PubSub<M>
 where
     A: Actor + Message<M>,
     M: Send + 'static,
@@ -214,10 +401,10 @@ where
 
     async fn handle(
         &mut self,
-        Subscribe(actor_ref): Subscribe<A>,
+        Subscribe { subscriber, filter }: Subscribe<A, M>,
         _ctx: Context<'_, Self, Self::Reply>,
     ) -> Self::Reply {
-        self.subscribe(actor_ref)
+        self.insert_subscriber(subscriber, filter)
     }
 }
 
@@ -229,7 +416,7 @@ impl<A, M, Mb> MessageSubscriber<M> for ActorRef<A>
 where
     A: Actor<Mailbox = Mb> + Message<M>,
     M: Send + 'static,
-    Mb: Sync,
+    Mb: Send + Sync,
     for<'a> TellRequest<LocalTellRequest<'a, A, Mb>, Mb, M, WithoutRequestTimeout>:
         MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
 {
@@ -0,0 +1,222 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::{
+    future::{join_all, BoxFuture},
+    stream::AbortHandle,
+    FutureExt,
+};
+use once_cell::sync::Lazy;
+use tokio::{
+    sync::watch,
+    task::{JoinError, JoinHandle},
+};
+
+use crate::{error::ActorStopReason, mailbox::SignalMailbox, Actor};
+
+use super::ActorID;
+
+/// All actors currently running in the process, used to shut them down with [`shutdown_all`].
+///
+/// Only weak mailboxes are held, so the registry does not keep actors alive.
+static RUNNING_ACTORS: Lazy<Mutex<HashMap<ActorID, RunningActor>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone)]
+struct RunningActor {
+    mailbox: Box<dyn SignalMailbox>,
+    abort_handle: AbortHandle,
+    stop_reason: Arc<watch::Sender<Option<ActorStopReason>>>,
+}
+
+pub(crate) fn register_running(
+    id: ActorID,
+    mailbox: Box<dyn SignalMailbox>,
+    abort_handle: AbortHandle,
+    stop_reason: Arc<watch::Sender<Option<ActorStopReason>>>,
+) {
+    RUNNING_ACTORS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(
+            id,
+            RunningActor {
+                mailbox,
+                abort_handle,
+                stop_reason,
+            },
+        );
+}
+
+pub(crate) fn unregister_running(id: ActorID) {
+    RUNNING_ACTORS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(&id);
+}
+
+/// Join handles of actors spawned with [`spawn_detached`](crate::actor::spawn_detached), until they are joined.
+static DETACHED_ACTORS: Lazy<Mutex<HashMap<ActorID, Box<dyn DetachedHandle>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+trait DetachedHandle: Send {
+    fn as_any(&self) -> &dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any + Send>;
+
+    fn join_reason(self: Box<Self>) -> BoxFuture<'static, Result<ActorStopReason, JoinError>>;
+}
+
+impl<A: Actor> DetachedHandle for JoinHandle<(A, ActorStopReason)> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any + Send> {
+        self
+    }
+
+    fn join_reason(self: Box<Self>) -> BoxFuture<'static, Result<ActorStopReason, JoinError>> {
+        self.map(|res| res.map(|(_, reason)| reason)).boxed()
+    }
+}
+
+pub(crate) fn register_detached<A: Actor>(id: ActorID, handle: JoinHandle<(A, ActorStopReason)>) {
+    DETACHED_ACTORS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(id, Box::new(handle));
+}
+
+/// Waits for a detached actor to stop, returning the actor's final state and stop reason.
+///
+/// Only actors spawned with [`spawn_detached`](crate::actor::spawn_detached) can be joined, and each can only be joined once.
+/// Returns `None` if no detached actor of type `A` with the ID is being tracked, such as if it was already joined.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::error::ActorStopReason;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::actor::spawn_detached(MyActor);
+/// actor_ref.stop_gracefully().await?;
+///
+/// let (_actor, reason) = kameo::join::<MyActor>(actor_ref.id()).await.unwrap()?;
+/// assert!(matches!(reason, ActorStopReason::Normal));
+/// assert!(kameo::join::<MyActor>(actor_ref.id()).await.is_none());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub async fn join<A: Actor>(id: ActorID) -> Option<Result<(A, ActorStopReason), JoinError>> {
+    let handle = {
+        let mut detached = DETACHED_ACTORS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        if !detached
+            .get(&id)?
+            .as_any()
+            .is::<JoinHandle<(A, ActorStopReason)>>()
+        {
+            return None;
+        }
+        detached.remove(&id)?
+    };
+    let handle = handle
+        .into_any()
+        .downcast::<JoinHandle<(A, ActorStopReason)>>()
+        .ok()?;
+
+    Some(handle.await)
+}
+
+/// Gracefully stops all running actors, waiting up to `timeout` for them to stop.
+///
+/// Each actor is signalled to stop after processing the messages already in its mailbox, and its
+/// [`on_stop`](crate::actor::Actor::on_stop) hook is awaited. Actors which do not stop within the timeout are killed.
+///
+/// This should be called before the Tokio runtime shuts down, such as at the end of `main`,
+/// to avoid actors being dropped in the middle of handling a message.
+///
+/// Returns the IDs of actors which were killed since they did not stop within the timeout.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(MyActor);
+///
+/// let killed = kameo::shutdown_all(Duration::from_secs(5)).await;
+/// assert!(killed.is_empty());
+/// assert!(!actor_ref.is_alive());
+/// # });
+/// ```
+pub async fn shutdown_all(timeout: Duration) -> Vec<ActorID> {
+    shutdown_where(timeout, |_| true).await
+}
+
+/// Gracefully stops the running actors matching `filter`, see [`shutdown_all`].
+pub(crate) async fn shutdown_where(
+    timeout: Duration,
+    filter: impl Fn(&ActorID) -> bool,
+) -> Vec<ActorID> {
+    let actors: Vec<_> = RUNNING_ACTORS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .filter(|(id, _)| filter(id))
+        .map(|(id, actor)| (*id, actor.clone()))
+        .collect();
+
+    join_all(actors.iter().map(|(_, actor)| actor.mailbox.signal_stop())).await;
+
+    let results = join_all(actors.into_iter().map(|(id, actor)| async move {
+        let mut rx = actor.stop_reason.subscribe();
+        let stopped = tokio::time::timeout(timeout, rx.wait_for(Option::is_some))
+            .await
+            .is_ok();
+        if stopped {
+            None
+        } else {
+            actor.abort_handle.abort();
+            Some(id)
+        }
+    }))
+    .await;
+
+    results.into_iter().flatten().collect()
+}
+
+/// Waits for all detached actors to stop, returning their stop reasons.
+///
+/// This is useful at the end of `main` after stopping actors, such as with [`shutdown_all`], to observe how each
+/// detached actor spawned with [`spawn_detached`](crate::actor::spawn_detached) stopped.
+/// Actors which were already joined with [`join`] are not included.
+pub async fn join_all_detached() -> Vec<(ActorID, Result<ActorStopReason, JoinError>)> {
+    let detached = std::mem::take(
+        &mut *DETACHED_ACTORS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()),
+    );
+    join_all(
+        detached
+            .into_iter()
+            .map(|(id, handle)| handle.join_reason().map(move |res| (id, res))),
+    )
+    .await
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+    time::Duration,
+};
+
+use tokio::{task::AbortHandle, time::Instant};
+
+use crate::{
+    error::SendError,
+    message::Message,
+    request::{LocalTellRequest, MessageSend, TellRequest, WithoutRequestTimeout},
+    Actor, Reply,
+};
+
+use super::ActorRef;
+
+/// A handle to the messages scheduled for delayed delivery to an actor, returned by
+/// [`ActorRef::scheduled`].
+///
+/// Messages are scheduled with [`tell_after`](ScheduledMessages::tell_after), which returns an id that can be used to
+/// [`cancel`](ScheduledMessages::cancel) the message before it's delivered. Actors which reschedule their own timers
+/// can use [`pending`](ScheduledMessages::pending) to avoid scheduling duplicates.
+///
+/// A scheduled message is sent as a tell once its delay has elapsed. Its timer only holds a weak ref to the actor,
+/// so it doesn't keep the actor alive, and the message is dropped if the actor has stopped by then.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Reminders(Vec<&'static str>);
+///
+/// impl Message<&'static str> for Reminders {
+///     type Reply = Vec<&'static str>;
+///
+///     async fn handle(&mut self, msg: &'static str, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.0.push(msg);
+///         self.0.clone()
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Reminders::default());
+/// let scheduled = actor_ref.scheduled();
+///
+/// let standup = scheduled.tell_after("standup", Duration::from_millis(10));
+/// let lunch = scheduled.tell_after("lunch", Duration::from_secs(60));
+/// assert_eq!(scheduled.pending().len(), 2);
+///
+/// assert!(scheduled.cancel(lunch));
+/// tokio::time::sleep(Duration::from_millis(50)).await;
+///
+/// // Cancelling a message which was already delivered does nothing
+/// assert!(!scheduled.cancel(standup));
+/// assert!(scheduled.pending().is_empty());
+/// assert_eq!(actor_ref.ask("check").await?, ["standup", "check"]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct ScheduledMessages<A: Actor> {
+    actor_ref: ActorRef<A>,
+}
+
+impl<A: Actor> ScheduledMessages<A> {
+    pub(crate) fn new(actor_ref: ActorRef<A>) -> Self {
+        ScheduledMessages { actor_ref }
+    }
+
+    /// Schedules a message to be sent to the actor as a tell after `delay`.
+    ///
+    /// Returns the id of the scheduled message, which can be passed to [`cancel`](ScheduledMessages::cancel).
+    /// Errors sending the message, such as the actor having stopped or its mailbox being full, are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn tell_after<M>(&self, msg: M, delay: Duration) -> ScheduledMessageId
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        self.schedule(delay, move |actor_ref| async move {
+            let _ = actor_ref.tell(msg).send().await;
+        })
+    }
+
+    /// Schedules `send` to be called with a strong ref to the actor after `delay`, unless it's cancelled
+    /// or the actor has stopped first.
+    pub(crate) fn schedule<F, Fut>(&self, delay: Duration, send: F) -> ScheduledMessageId
+    where
+        F: FnOnce(ActorRef<A>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let state = &self.actor_ref.counters.scheduled;
+        let id = ScheduledMessageId(state.next_id.fetch_add(1, Ordering::Relaxed));
+        let fire_at = Instant::now() + delay;
+
+        // The entry is inserted before the timer can fire, since it's removed when the message is sent
+        let mut pending = state.lock();
+        let actor_ref = self.actor_ref.downgrade();
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep_until(fire_at).await;
+            let Some(actor_ref) = actor_ref.upgrade() else {
+                return;
+            };
+            // Removing the entry claims the message, so a cancel from here on does nothing
+            if actor_ref.counters.scheduled.lock().remove(&id).is_none() {
+                return;
+            }
+            send(actor_ref).await;
+        });
+        pending.insert(
+            id,
+            PendingTimer {
+                fire_at,
+                abort_handle: handle.abort_handle(),
+            },
+        );
+
+        id
+    }
+
+    /// Returns the messages which are scheduled but have not been sent yet, ordered by when they're due.
+    pub fn pending(&self) -> Vec<ScheduledMessage> {
+        let mut pending: Vec<_> = self
+            .actor_ref
+            .counters
+            .scheduled
+            .lock()
+            .iter()
+            .map(|(id, timer)| ScheduledMessage {
+                id: *id,
+                fire_at: timer.fire_at.into_std(),
+            })
+            .collect();
+        pending.sort_by_key(|scheduled| (scheduled.fire_at, scheduled.id));
+        pending
+    }
+
+    /// Cancels a scheduled message, returning `false` if it was already sent or cancelled.
+    pub fn cancel(&self, id: ScheduledMessageId) -> bool {
+        match self.actor_ref.counters.scheduled.lock().remove(&id) {
+            Some(timer) => {
+                timer.abort_handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancels every scheduled message which has not been sent yet, returning how many were cancelled.
+    pub fn cancel_all(&self) -> usize {
+        let timers: Vec<_> = self.actor_ref.counters.scheduled.lock().drain().collect();
+        for (_, timer) in &timers {
+            timer.abort_handle.abort();
+        }
+        timers.len()
+    }
+}
+
+impl<A: Actor> Clone for ScheduledMessages<A> {
+    fn clone(&self) -> Self {
+        ScheduledMessages {
+            actor_ref: self.actor_ref.clone(),
+        }
+    }
+}
+
+impl<A: Actor> std::fmt::Debug for ScheduledMessages<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScheduledMessages")
+            .field("actor_id", &self.actor_ref.id())
+            .field("pending", &self.actor_ref.counters.scheduled.lock().len())
+            .finish()
+    }
+}
+
+/// The id of a message scheduled with [`ScheduledMessages::tell_after`].
+///
+/// Ids are unique per actor, and are never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScheduledMessageId(u64);
+
+/// A message which is scheduled to be sent to an actor, returned by [`ScheduledMessages::pending`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduledMessage {
+    /// The id of the scheduled message.
+    pub id: ScheduledMessageId,
+    /// When the message is due to be sent.
+    pub fire_at: std::time::Instant,
+}
+
+/// The bookkeeping for an actor's scheduled messages, shared between its references.
+#[derive(Debug, Default)]
+pub(crate) struct ScheduledState {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<ScheduledMessageId, PendingTimer>>,
+}
+
+impl ScheduledState {
+    fn lock(&self) -> MutexGuard<'_, HashMap<ScheduledMessageId, PendingTimer>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[derive(Debug)]
+struct PendingTimer {
+    fire_at: Instant,
+    abort_handle: AbortHandle,
+}
@@ -0,0 +1,205 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use futures::{Future, FutureExt};
+use once_cell::sync::Lazy;
+
+use crate::{
+    error::{ActorStopReason, ScopeError},
+    Actor,
+};
+
+use super::{group::ActorGroup, ActorID, ActorRef};
+
+/// Named actor refs registered in a scope, keyed by name.
+type Siblings = Arc<Mutex<HashMap<String, Box<dyn Any + Send + Sync>>>>;
+
+/// The siblings of each actor spawned in a scope, used by [`Context::sibling`](crate::message::Context::sibling).
+static SCOPE_MEMBERS: Lazy<Mutex<HashMap<ActorID, Siblings>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// How panics in child actors affect the result of a [`scope`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ChildPanicPolicy {
+    /// The scope returns a [`ScopeError`] if any child actor panicked.
+    #[default]
+    Propagate,
+    /// Child actor panics are ignored, and the scope always returns the result of its body.
+    Ignore,
+}
+
+/// A handle for spawning actors owned by a [`scope`].
+///
+/// The handle can be cloned and moved into other tasks, but actors spawned through it are still stopped
+/// when the scope ends.
+///
+/// Actors can be registered in a scope by name, so actors spawned in the same scope can look up their siblings
+/// with [`Context::sibling`](crate::message::Context::sibling), without threading refs through each of them manually.
+/// Names are local to the scope, so they can't collide with other scopes or the global registry.
+#[derive(Clone)]
+pub struct Scope {
+    group: Arc<Mutex<ActorGroup>>,
+    siblings: Siblings,
+}
+
+impl Scope {
+    /// Spawns an actor owned by this scope.
+    ///
+    /// The actor is stopped gracefully and joined when the scope ends.
+    pub fn spawn<A: Actor>(&self, actor: A) -> ActorRef<A> {
+        let prepared = super::prepare(actor);
+        let actor_ref = prepared.actor_ref().clone();
+        // Registered before the actor starts, so its siblings can be looked up from `on_start`
+        lock(&SCOPE_MEMBERS).insert(actor_ref.id(), self.siblings.clone());
+        prepared.spawn();
+        lock(&self.group).insert(actor_ref.clone());
+        actor_ref
+    }
+
+    /// Spawns an actor owned by this scope, registering it under a name to be looked up by its siblings.
+    ///
+    /// See [`Scope::register`].
+    pub fn spawn_named<A: Actor>(&self, name: impl Into<String>, actor: A) -> ActorRef<A> {
+        let actor_ref = self.spawn(actor);
+        self.register(name, &actor_ref);
+        actor_ref
+    }
+
+    /// Registers an actor under a name, so actors spawned in this scope can look it up with
+    /// [`Context::sibling`](crate::message::Context::sibling).
+    ///
+    /// The actor doesn't need to be spawned in the scope. Registering a name again replaces the previous actor.
+    /// Registered refs are held until the scope ends.
+    pub fn register<A: Actor>(&self, name: impl Into<String>, actor_ref: &ActorRef<A>) {
+        lock(&self.siblings).insert(name.into(), Box::new(actor_ref.clone()));
+    }
+
+    /// Looks up an actor registered in this scope by name.
+    ///
+    /// Returns `None` if no actor is registered under the name, or it is not of type `A`.
+    pub fn sibling<A: Actor>(&self, name: &str) -> Option<ActorRef<A>> {
+        lock(&self.siblings)
+            .get(name)?
+            .downcast_ref::<ActorRef<A>>()
+            .cloned()
+    }
+}
+
+impl fmt::Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("group", &self.group)
+            .field("siblings", &lock(&self.siblings).keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Looks up a named sibling of an actor spawned in a scope.
+pub(crate) fn sibling<A: Actor>(id: ActorID, name: &str) -> Option<ActorRef<A>> {
+    let siblings = lock(&SCOPE_MEMBERS).get(&id)?.clone();
+    let siblings = lock(&siblings);
+    siblings.get(name)?.downcast_ref::<ActorRef<A>>().cloned()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Runs an async block with a [`Scope`], stopping and joining every actor spawned in it when the block ends.
+///
+/// Actors spawned with [`Scope::spawn`] are signalled to stop gracefully once the block completes, whether it returned
+/// or panicked, and the scope waits for all of them to stop. This guarantees no actors outlive the scope,
+/// which is useful for request-scoped work.
+///
+/// If the block panics, the panic is resumed after the child actors have stopped.
+/// If any child actor panicked, a [`ScopeError`] is returned, see [`scope_with_policy`] to ignore child panics.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(Actor, Default)]
+/// struct Counter {
+///     count: i64,
+/// }
+///
+/// struct Inc;
+///
+/// impl Message<Inc> for Counter {
+///     type Reply = i64;
+///
+///     async fn handle(&mut self, _: Inc, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.count += 1;
+///         self.count
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let (count, counter) = kameo::scope(|s| async move {
+///     let counter = s.spawn(Counter::default());
+///     counter.tell(Inc).await?;
+///     let count = counter.ask(Inc).await?;
+///     Ok::<_, Box<dyn std::error::Error>>((count, counter))
+/// })
+/// .await??;
+///
+/// assert_eq!(count, 2);
+/// assert!(!counter.is_alive());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub async fn scope<F, Fut, T>(f: F) -> Result<T, ScopeError>
+where
+    F: FnOnce(Scope) -> Fut,
+    Fut: Future<Output = T>,
+{
+    scope_with_policy(ChildPanicPolicy::default(), f).await
+}
+
+/// Runs an async block with a [`Scope`], handling child actor panics according to the given policy.
+///
+/// See [`scope`] for more information.
+pub async fn scope_with_policy<F, Fut, T>(policy: ChildPanicPolicy, f: F) -> Result<T, ScopeError>
+where
+    F: FnOnce(Scope) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let scope = Scope {
+        group: Arc::new(Mutex::new(ActorGroup::new())),
+        siblings: Siblings::default(),
+    };
+    let res = AssertUnwindSafe(f(scope.clone())).catch_unwind().await;
+
+    // Take the group so actors spawned after the scope ends are not joined
+    let group = std::mem::take(&mut *lock(&scope.group));
+    group.stop_all().await;
+    let panicked: Vec<_> = group
+        .join_all()
+        .await
+        .into_iter()
+        .filter_map(|(id, reason)| match reason {
+            ActorStopReason::Panicked(err) => Some((id, err)),
+            _ => None,
+        })
+        .collect();
+    {
+        let mut members = lock(&SCOPE_MEMBERS);
+        for actor_ref in group.iter() {
+            members.remove(&actor_ref.id());
+        }
+    }
+    lock(&scope.siblings).clear();
+
+    let value = res.unwrap_or_else(|err| panic::resume_unwind(err));
+    match policy {
+        ChildPanicPolicy::Propagate if !panicked.is_empty() => Err(ScopeError::new(panicked)),
+        _ => Ok(value),
+    }
+}
@@ -1,26 +1,32 @@
-use std::{convert, panic::AssertUnwindSafe, sync::Arc, thread};
+use std::{
+    collections::VecDeque, convert, iter, mem, ops::ControlFlow, panic, pin::Pin, sync::Arc,
+    thread, time::Instant,
+};
 
 use futures::{
-    stream::{AbortHandle, AbortRegistration, Abortable},
-    Future, FutureExt,
+    future,
+    stream::{AbortHandle, AbortRegistration, Abortable, Aborted, FuturesUnordered},
+    Future, FutureExt, StreamExt,
 };
 use tokio::{
     runtime::{Handle, RuntimeFlavor},
-    sync::Semaphore,
+    sync::{oneshot, watch, Notify, Semaphore},
     task::JoinHandle,
 };
-use tracing::{error, trace};
+use tracing::{debug, error, info, trace, warn, Level};
 
 use crate::{
     actor::{
-        kind::{ActorBehaviour, ActorState},
-        Actor, ActorRef, Links, CURRENT_ACTOR_ID,
+        kind::{catch_unwind_if, ActorBehaviour, ActorState},
+        Actor, ActorInit, ActorRef, Links, WeakActorRef, CURRENT_ACTOR_ID, CURRENT_EXECUTION_MODE,
     },
-    error::{ActorStopReason, PanicError},
-    mailbox::{Mailbox, MailboxReceiver, Signal},
+    error::{ActorStopReason, BoxError, PanicError, SendError},
+    mailbox::{Mailbox, MailboxReceiver, MessageOptions, Signal, UndeliveredMessage},
+    message::DynReadMessage,
+    reply::BoxReplySender,
 };
 
-use super::ActorID;
+use super::{limit::SpawnPermit, registry, ActorID};
 
 /// Spawns an actor in a Tokio task, running asynchronously.
 ///
@@ -42,6 +48,10 @@ use super::ActorID;
 /// ```
 ///
 /// The actor will continue running in the background, and messages can be sent to it via `actor_ref`.
+///
+/// Discarding the returned `ActorRef` leaves no way to send the actor messages, unless it sends messages to itself.
+/// With the `warn-unreferenced` feature enabled, a warning is logged when a running actor has no refs
+/// and has not received any messages shortly after starting, which helps catch this mistake during development.
 pub fn spawn<A>(actor: A) -> ActorRef<A>
 where
     A: Actor,
@@ -52,6 +62,105 @@ where
     actor_ref
 }
 
+/// Spawns an actor in a Tokio task, returning its [`ActorRef`] along with a future resolving to the startup result.
+///
+/// The actor ref is usable immediately, just as with [`spawn`], and messages sent before startup completes
+/// are buffered until `on_start` has finished. The returned future resolves once `on_start` has completed,
+/// with an error if it failed or panicked, allowing callers to verify an actor booted before sending it real traffic
+/// without linking to it or joining it.
+///
+/// The future resolves with `Ok(())` if the actor is stopped during startup for any other reason, such as being killed.
+/// Dropping the future has no effect on the actor.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::ActorRef;
+/// use kameo::error::BoxError;
+/// use kameo::mailbox::unbounded::UnboundedMailbox;
+/// use kameo::Actor;
+///
+/// struct Database;
+///
+/// impl Actor for Database {
+///     type Mailbox = UnboundedMailbox<Self>;
+///
+///     async fn on_start(&mut self, _: ActorRef<Self>) -> Result<(), BoxError> {
+///         Err("connection refused".into())
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let (_actor_ref, startup) = kameo::actor::try_spawn(Database);
+/// let err = startup.await.unwrap_err();
+/// assert_eq!(err.to_string(), "panicked: connection refused");
+/// # })
+/// ```
+pub fn try_spawn<A>(actor: A) -> (ActorRef<A>, impl Future<Output = Result<(), PanicError>>)
+where
+    A: Actor,
+{
+    let actor_ref = spawn(actor);
+    let startup_semaphore = actor_ref.startup_semaphore.clone();
+    let mut stop_reason = actor_ref.stop_reason.subscribe();
+    let startup = async move {
+        tokio::select! {
+            biased;
+            res = startup_semaphore.acquire() => {
+                if res.is_ok() {
+                    return Ok(());
+                }
+            }
+            _ = stop_reason.wait_for(Option::is_some) => {}
+        }
+
+        // The startup semaphore is closed when `on_start` fails
+        let reason = stop_reason
+            .wait_for(Option::is_some)
+            .await
+            .expect("stop reason sender is held by the actor ref")
+            .clone();
+        match reason {
+            Some(ActorStopReason::Panicked(err)) if startup_semaphore.is_closed() => Err(err),
+            _ => Ok(()),
+        }
+    };
+
+    (actor_ref, startup)
+}
+
+/// Spawns an actor in a Tokio task, keeping its `JoinHandle` so it can be joined later.
+///
+/// Unlike [`spawn`], which drops the `JoinHandle`, the handle of a detached actor is tracked internally,
+/// and the actor's final state and stop reason can be awaited with [`join`](crate::join),
+/// or for all detached actors with [`join_all_detached`](crate::actor::join_all_detached).
+///
+/// The final state of the actor is kept until it is joined, so detached actors should always be joined eventually.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::actor::spawn_detached(MyActor);
+/// actor_ref.kill();
+///
+/// let stopped = kameo::actor::join_all_detached().await;
+/// assert_eq!(stopped.len(), 1);
+/// assert_eq!(stopped[0].0, actor_ref.id());
+/// # })
+/// ```
+pub fn spawn_detached<A>(actor: A) -> ActorRef<A>
+where
+    A: Actor,
+{
+    prepare(actor).spawn_detached()
+}
+
 /// Spawns and links an actor in a Tokio task, running asynchronously.
 ///
 /// This function is used to ensure an actor is linked with another actor before its truly spawned,
@@ -126,6 +235,113 @@ where
     actor_ref
 }
 
+/// Spawns an actor in a Tokio task, creating it from its arguments with [`ActorInit::init`].
+///
+/// The actor is created before this returns, so an error from [`ActorInit::init`] is returned directly,
+/// without the actor being spawned.
+///
+/// See [`ActorInit`] for an example.
+pub async fn spawn_with_args<A>(args: A::Args) -> Result<ActorRef<A>, BoxError>
+where
+    A: ActorInit,
+{
+    let actor = A::init(args).await?;
+    Ok(spawn(actor))
+}
+
+/// Spawns an actor in a Tokio task, sharing read-only data with other actor instances.
+///
+/// The factory function is provided with a clone of the `Arc`, allowing many actors to share a large dataset
+/// which can be accessed in message handlers without being cloned per message. Each spawned actor holds its own
+/// reference to the data, ensuring it outlives all actor instances.
+///
+/// This is particularly useful for read heavy fan-out workloads, such as a pool of workers querying the same data.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::sync::Arc;
+///
+/// use kameo::Actor;
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(Actor)]
+/// struct Lookup {
+///     data: Arc<HashMap<u32, String>>,
+/// }
+///
+/// struct Get(u32);
+///
+/// impl Message<Get> for Lookup {
+///     type Reply = Option<String>;
+///
+///     async fn handle(&mut self, Get(key): Get, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.data.get(&key).cloned()
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let data = Arc::new(HashMap::from([(1, "one".to_string())]));
+/// let workers: Vec<_> = (0..4)
+///     .map(|_| kameo::actor::spawn_shared(&data, |data| Lookup { data }))
+///     .collect();
+///
+/// assert_eq!(workers[0].ask(Get(1)).await?, Some("one".to_string()));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub fn spawn_shared<A, D, F>(data: &Arc<D>, f: F) -> ActorRef<A>
+where
+    A: Actor,
+    D: ?Sized,
+    F: FnOnce(Arc<D>) -> A,
+{
+    spawn(f(Arc::clone(data)))
+}
+
+/// Spawns `n` actors created by a factory, which is passed each actor's index.
+///
+/// The actors are spawned one after another with [`spawn`], so their `on_start` hooks run concurrently,
+/// and the refs are returned in index order. This is useful for sharding, where the actor at index `i`
+/// handles the keys whose hash modulo `n` is `i`.
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor)]
+/// struct Shard {
+///     index: usize,
+/// }
+///
+/// struct Index;
+///
+/// impl Message<Index> for Shard {
+///     type Reply = usize;
+///
+///     async fn handle(&mut self, _: Index, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.index
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let shards = kameo::spawn_many(4, |index| Shard { index });
+///
+/// let key = 42;
+/// assert_eq!(shards[key % shards.len()].ask(Index).await?, 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub fn spawn_many<A, F>(n: usize, factory: F) -> Vec<ActorRef<A>>
+where
+    A: Actor,
+    F: FnMut(usize) -> A,
+{
+    (0..n).map(factory).map(spawn).collect()
+}
+
 /// Spawns an actor in its own dedicated thread, allowing for blocking operations.
 ///
 /// This function spawns the actor in a separate thread, making it suitable for actors that perform blocking
@@ -163,16 +379,70 @@ where
 ///
 /// This function is useful for actors that require or benefit from running blocking operations while still
 /// enabling asynchronous functionality.
+///
+/// The thread is named after the actor and uses the default stack size, see [`spawn_in_thread_with`] to configure it.
 pub fn spawn_in_thread<A>(actor: A) -> ActorRef<A>
+where
+    A: Actor,
+{
+    spawn_in_thread_with(actor, ThreadConfig::default())
+}
+
+/// Spawns an actor in its own dedicated thread, configured with a custom name or stack size.
+///
+/// This is useful for actors whose handlers need more than the default stack size, such as deeply recursive parsers.
+///
+/// See [`spawn_in_thread`] for more information.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::ThreadConfig;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # #[tokio::main(flavor = "multi_thread")]
+/// # async fn main() {
+/// let actor_ref = kameo::actor::spawn_in_thread_with(
+///     MyActor,
+///     ThreadConfig {
+///         name: Some("parser".to_string()),
+///         stack_size: Some(16 * 1024 * 1024),
+///     },
+/// );
+/// # actor_ref.kill();
+/// # }
+/// ```
+pub fn spawn_in_thread_with<A>(actor: A, config: ThreadConfig) -> ActorRef<A>
 where
     A: Actor,
 {
     let prepared_actor = prepare(actor);
     let actor_ref = prepared_actor.actor_ref().clone();
-    prepared_actor.spawn_in_thread();
+    prepared_actor.spawn_in_thread_with(config);
     actor_ref
 }
 
+/// Configures the OS thread an actor is spawned in, see [`spawn_in_thread_with`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ThreadConfig {
+    /// The name of the thread, defaulting to the actor's [`name`](Actor::name).
+    pub name: Option<String>,
+    /// The stack size of the thread in bytes, defaulting to the standard library's default, which is 2 MiB.
+    pub stack_size: Option<usize>,
+}
+
+/// How an actor is being executed, see [`Context::execution_mode`](crate::message::Context::execution_mode).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExecutionMode {
+    /// The actor is running in a tokio task, such as with [`spawn`], and should avoid blocking.
+    TokioTask,
+    /// The actor is running in its own thread with [`spawn_in_thread`], so blocking is fine.
+    DedicatedThread,
+}
+
 /// Prepares an actor without spawning it, returning a [`PreparedActor`].
 ///
 /// The actor is fully initialized, but will not start processing messages until explicitly
@@ -236,6 +506,7 @@ pub struct PreparedActor<A: Actor> {
     actor_ref: ActorRef<A>,
     mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
     abort_registration: AbortRegistration,
+    stop_guard: StopGuard,
 }
 
 impl<A: Actor> PreparedActor<A> {
@@ -251,11 +522,13 @@ impl<A: Actor> PreparedActor<A> {
             startup_semaphore.clone(),
         );
 
+        let stop_guard = StopGuard::new(&actor_ref);
         PreparedActor {
             actor,
             actor_ref,
             mailbox_rx,
             abort_registration,
+            stop_guard,
         }
     }
 
@@ -276,11 +549,13 @@ impl<A: Actor> PreparedActor<A> {
         );
         let actor = f(&actor_ref).await;
 
+        let stop_guard = StopGuard::new(&actor_ref);
         PreparedActor {
             actor,
             actor_ref,
             mailbox_rx,
             abort_registration,
+            stop_guard,
         }
     }
 
@@ -291,6 +566,114 @@ impl<A: Actor> PreparedActor<A> {
         &self.actor_ref
     }
 
+    /// Returns an owned clone of the [`ActorRef`].
+    ///
+    /// Clones can be handed out to other tasks before the actor is spawned. Messages sent through any
+    /// of them are buffered in the actor's mailbox, and are processed in the order they were sent once
+    /// the actor starts running.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Recorder {
+    ///     received: Vec<u32>,
+    /// }
+    ///
+    /// struct Record(u32);
+    ///
+    /// impl Message<Record> for Recorder {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, Record(n): Record, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.received.push(n);
+    ///     }
+    /// }
+    ///
+    /// struct Received;
+    ///
+    /// impl Message<Received> for Recorder {
+    ///     type Reply = Vec<u32>;
+    ///
+    ///     async fn handle(&mut self, _: Received, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.received.clone()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared_actor = kameo::actor::prepare(Recorder::default());
+    /// let first = prepared_actor.actor_ref_owned();
+    /// let second = prepared_actor.actor_ref_owned();
+    ///
+    /// first.tell(Record(1)).send().await?;
+    /// second.tell(Record(2)).send().await?;
+    /// first.tell(Record(3)).send().await?;
+    ///
+    /// prepared_actor.spawn();
+    /// assert_eq!(second.ask(Received).await?, vec![1, 2, 3]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn actor_ref_owned(&self) -> ActorRef<A> {
+        self.actor_ref.clone()
+    }
+
+    /// Returns a mutable reference to the actor before it runs.
+    ///
+    /// This is mostly useful in tests for setting up precise preconditions, since no messages are processed until
+    /// the actor is run or spawned, even if messages are already queued in its mailbox.
+    pub fn actor_mut(&mut self) -> &mut A {
+        &mut self.actor
+    }
+
+    /// Replaces the actor with `actor` before it runs, returning the previous actor.
+    ///
+    /// The actor's [`ActorRef`] and mailbox are kept, so messages already sent to the actor are handled by the new actor.
+    /// No messages are processed during the swap, since the actor has not started yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    /// use kameo::request::MessageSend;
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Counter {
+    ///     count: i64,
+    /// }
+    ///
+    /// struct Inc;
+    ///
+    /// impl Message<Inc> for Counter {
+    ///     type Reply = i64;
+    ///
+    ///     async fn handle(&mut self, _: Inc, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.count += 1;
+    ///         self.count
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut prepared = kameo::actor::prepare(Counter { count: 0 });
+    /// prepared.actor_ref().tell(Inc).await?;
+    ///
+    /// let previous = prepared.replace_actor(Counter { count: 41 });
+    /// assert_eq!(previous.count, 0);
+    /// prepared.actor_mut().count += 100;
+    ///
+    /// let actor_ref = prepared.actor_ref().clone();
+    /// prepared.spawn();
+    /// assert_eq!(actor_ref.ask(Inc).await?, 143);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn replace_actor(&mut self, actor: A) -> A {
+        mem::replace(&mut self.actor, actor)
+    }
+
     /// Runs the actor in the current context **without** spawning a separate task, until the actor is stopped.
     ///
     /// This is useful when you need to run an actor synchronously in the current context,
@@ -323,193 +706,1042 @@ impl<A: Actor> PreparedActor<A> {
     /// # });
     /// ```
     pub async fn run(self) -> (A, ActorStopReason) {
-        run_actor_lifecycle::<A, ActorBehaviour<A>>(
+        register_running(&self.actor_ref);
+        self.into_future_guarded().await
+    }
+
+    /// Returns the future running the actor, which marks the actor as killed if it is dropped before the actor stops,
+    /// even if it was never polled.
+    fn into_future_guarded(mut self) -> impl Future<Output = (A, ActorStopReason)> + Send {
+        self.stop_guard.take_spawn_permit(&self.actor_ref);
+        A::task_wrapper(run_actor_lifecycle::<A, ActorBehaviour<A>>(
             self.actor,
             self.actor_ref,
             self.mailbox_rx,
             self.abort_registration,
-        )
-        .await
+            self.stop_guard,
+        ))
     }
 
     /// Spawns the actor in a new background tokio task, returning the `JoinHandle`.
     ///
     /// See [`spawn`] for more information.
     pub fn spawn(self) -> JoinHandle<(A, ActorStopReason)> {
+        // Registered before the task is polled, so the actor can be found by `shutdown_all` immediately
+        register_running(&self.actor_ref);
+
         #[cfg(not(tokio_unstable))]
         {
-            tokio::spawn(CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.run()))
+            spawn_task(CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.into_future_guarded()))
         }
 
         #[cfg(tokio_unstable)]
         {
             tokio::task::Builder::new()
-                .name(A::name())
-                .spawn(CURRENT_ACTOR_ID.scope(actor_ref.id(), self.run()))
+                .name(super::name::<A>())
+                .spawn(task_scope(
+                    CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.into_future_guarded()),
+                ))
                 .unwrap()
         }
     }
 
+    /// Spawns the actor in a new background tokio task, tracking its `JoinHandle` internally.
+    ///
+    /// See [`spawn_detached`] for more information.
+    pub fn spawn_detached(self) -> ActorRef<A> {
+        let actor_ref = self.actor_ref.clone();
+        registry::register_detached(actor_ref.id(), self.spawn());
+        actor_ref
+    }
+
     /// Spawns the actor in a new background thread, returning the `JoinHandle`.
     ///
     /// See [`spawn_in_thread`] for more information.
     pub fn spawn_in_thread(self) -> thread::JoinHandle<(A, ActorStopReason)> {
+        self.spawn_in_thread_with(ThreadConfig::default())
+    }
+
+    /// Spawns the actor in a new background thread configured with `config`, returning the `JoinHandle`.
+    ///
+    /// See [`spawn_in_thread_with`] for more information.
+    pub fn spawn_in_thread_with(
+        self,
+        config: ThreadConfig,
+    ) -> thread::JoinHandle<(A, ActorStopReason)> {
         let handle = Handle::current();
         if matches!(handle.runtime_flavor(), RuntimeFlavor::CurrentThread) {
             panic!("threaded actors are not supported in a single threaded tokio runtime");
         }
+        register_running(&self.actor_ref);
 
-        std::thread::Builder::new()
-            .name(A::name().to_string())
+        let mut builder = std::thread::Builder::new().name(
+            config
+                .name
+                .unwrap_or_else(|| super::name::<A>().to_string()),
+        );
+        if let Some(stack_size) = config.stack_size {
+            builder = builder.stack_size(stack_size);
+        }
+        builder
             .spawn({
                 let actor_ref = self.actor_ref.clone();
-                move || handle.block_on(CURRENT_ACTOR_ID.scope(actor_ref.id(), self.run()))
+                let teardown = crate::test::Teardown::current();
+                move || {
+                    handle.block_on(teardown.scope(CURRENT_EXECUTION_MODE.scope(
+                        ExecutionMode::DedicatedThread,
+                        CURRENT_ACTOR_ID.scope(actor_ref.id(), self.into_future_guarded()),
+                    )))
+                }
             })
             .unwrap()
     }
+
+    /// Starts the actor in the current task, returning an [`ActorDriver`] for running its message loop manually.
+    ///
+    /// This runs [`Actor::on_start`], returning the stopped actor instead if it fails.
+    ///
+    /// See [`ActorDriver`] for more information.
+    pub async fn into_driver(mut self) -> Result<ActorDriver<A>, (A, ActorStopReason)> {
+        register_running(&self.actor_ref);
+        self.stop_guard.take_spawn_permit(&self.actor_ref);
+        let (running, actor_loop) =
+            start_actor(self.actor, self.actor_ref, self.mailbox_rx, self.stop_guard).await?;
+
+        Ok(ActorDriver {
+            running,
+            actor_loop,
+            aborted: Box::pin(Abortable::new(future::pending(), self.abort_registration)),
+            stopped: None,
+        })
+    }
+}
+
+/// A started actor whose message loop is run manually, for embedding an actor in a custom loop.
+///
+/// This is for interleaving an actor's messages with async sources the actor doesn't model, such as timers or
+/// sockets, while having mutable access to the actor between messages. Signals are received with
+/// [`recv`](ActorDriver::recv), which can be used in [`tokio::select!`], and are then passed to
+/// [`handle`](ActorDriver::handle). Once `handle` returns [`ControlFlow::Break`], the actor has stopped, and
+/// [`finish`](ActorDriver::finish) runs [`Actor::on_stop`] and returns it.
+///
+/// Running an actor with [`PreparedActor::run`] or spawning it should be preferred where possible.
+/// Actors run by a driver cannot be migrated with [`ActorRef::migrate_to`], and are stopped by
+/// [`ActorRef::kill`] only once `recv` is next polled.
+///
+/// Dropping the driver before calling `finish` marks the actor as killed, without running `on_stop`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Counter {
+///     count: u32,
+///     ticks: u32,
+/// }
+///
+/// struct Inc;
+///
+/// impl Message<Inc> for Counter {
+///     type Reply = u32;
+///
+///     async fn handle(&mut self, _: Inc, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.count += 1;
+///         self.count
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let prepared_actor = kameo::actor::prepare(Counter::default());
+/// let actor_ref = prepared_actor.actor_ref_owned();
+/// tokio::spawn(async move {
+///     actor_ref.ask(Inc).await.unwrap();
+///     actor_ref.stop_gracefully().await.unwrap();
+/// });
+///
+/// let Ok(mut driver) = prepared_actor.into_driver().await else {
+///     panic!("actor failed to start");
+/// };
+/// let mut interval = tokio::time::interval(Duration::from_millis(10));
+/// loop {
+///     tokio::select! {
+///         signal = driver.recv() => {
+///             if driver.handle(signal).await.is_break() {
+///                 break;
+///             }
+///         }
+///         _ = interval.tick() => driver.actor_mut().ticks += 1,
+///     }
+/// }
+///
+/// let (counter, _) = driver.finish().await;
+/// assert_eq!(counter.count, 1);
+/// # });
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct ActorDriver<A: Actor> {
+    running: RunningActor<A>,
+    actor_loop: ActorLoop<A, ActorBehaviour<A>>,
+    aborted: Pin<Box<Abortable<future::Pending<()>>>>,
+    stopped: Option<ActorStopReason>,
+}
+
+impl<A: Actor> ActorDriver<A> {
+    /// Returns a reference to the actor.
+    pub fn actor(&self) -> &A {
+        self.actor_loop.state.actor()
+    }
+
+    /// Returns a mutable reference to the actor.
+    pub fn actor_mut(&mut self) -> &mut A {
+        self.actor_loop.state.actor_mut()
+    }
+
+    /// Returns whether the actor has stopped, and [`finish`](ActorDriver::finish) should be called.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.is_some()
+    }
+
+    /// Receives the next signal from the actor's mailbox, to be passed to [`handle`](ActorDriver::handle).
+    ///
+    /// This is cancel safe, so it can be used as a branch of [`tokio::select!`] without losing signals.
+    pub async fn recv(&mut self) -> MailboxSignal<A> {
+        let ActorLoop {
+            mailbox_rx,
+            kill_after_current,
+            paused,
+            ..
+        } = &mut self.actor_loop;
+        let received = tokio::select! {
+            biased;
+            _ = &mut self.aborted => Received::Aborted,
+            _ = kill_after_current.notified() => Received::Killed,
+            signal = recv_unless_paused(mailbox_rx, paused) => Received::Signal(signal),
+        };
+        MailboxSignal(received)
+    }
+
+    /// Handles a signal received with [`recv`](ActorDriver::recv), such as by running a message handler.
+    ///
+    /// Returns [`ControlFlow::Break`] once the actor has stopped, after which [`finish`](ActorDriver::finish)
+    /// should be called.
+    pub async fn handle(&mut self, signal: MailboxSignal<A>) -> ControlFlow<()> {
+        if self.stopped.is_some() {
+            return ControlFlow::Break(());
+        }
+
+        let reason = match signal.0 {
+            Received::Aborted => {
+                self.stopped = Some(ActorStopReason::Killed);
+                return ControlFlow::Break(());
+            }
+            Received::Killed => ActorStopReason::Killed,
+            Received::Signal(signal) => {
+                let ActorLoop {
+                    state,
+                    startup_semaphore,
+                    ..
+                } = &mut self.actor_loop;
+                match handle_signal(state, startup_semaphore, signal).await {
+                    Some(LoopExit::Stopped(reason)) => reason,
+                    // Dropping the acknowledgement fails the migration, since the loop isn't run by the actor
+                    Some(LoopExit::Migrate { .. }) | None => return ControlFlow::Continue(()),
+                }
+            }
+        };
+        match self.actor_loop.state.on_shutdown(reason).await {
+            Some(reason) => {
+                self.stopped = Some(reason);
+                ControlFlow::Break(())
+            }
+            None => ControlFlow::Continue(()),
+        }
+    }
+
+    /// Stops the actor, running [`Actor::on_stop`] and returning the actor along with its stop reason.
+    ///
+    /// If the actor has not already stopped, it's stopped with [`ActorStopReason::Normal`], and any messages still
+    /// queued are passed to [`Actor::on_undelivered`].
+    pub async fn finish(self) -> (A, ActorStopReason) {
+        let reason = self.stopped.unwrap_or(ActorStopReason::Normal);
+        stop_actor(self.running, self.actor_loop, reason).await
+    }
+}
+
+/// A signal received from an actor's mailbox by [`ActorDriver::recv`].
+#[allow(missing_debug_implementations)]
+pub struct MailboxSignal<A: Actor>(Received<A>);
+
+enum Received<A: Actor> {
+    Signal(Option<Signal<A>>),
+    Killed,
+    Aborted,
 }
 
 #[inline]
 async fn run_actor_lifecycle<A, S>(
-    mut actor: A,
+    actor: A,
     actor_ref: ActorRef<A>,
     mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
     abort_registration: AbortRegistration,
+    stop_guard: StopGuard,
 ) -> (A, ActorStopReason)
+where
+    A: Actor,
+    S: ActorState<A> + Send + 'static,
+{
+    #[cfg(feature = "metrics")]
+    let _alive = super::metrics::AliveGuard::new::<A>();
+    let (running, actor_loop) =
+        match start_actor::<A, S>(actor, actor_ref, mailbox_rx, stop_guard).await {
+            Ok(started) => started,
+            Err(stopped) => return stopped,
+        };
+    let (actor_loop, reason) = run_actor_loop(running.id, actor_loop, abort_registration).await;
+    stop_actor(running, actor_loop, reason).await
+}
+
+/// Everything needed to stop a started actor, once its message loop has returned.
+struct RunningActor<A: Actor> {
+    id: ActorID,
+    name: &'static str,
+    actor_ref: WeakActorRef<A>,
+    links: Links,
+    stop_reason: Arc<watch::Sender<Option<ActorStopReason>>>,
+    keep_alive: Option<ActorRef<A>>,
+    stop_guard: StopGuard,
+}
+
+/// Runs [`Actor::on_start`], returning the actor's message loop.
+///
+/// If `on_start` fails, the actor is stopped and returned instead.
+async fn start_actor<A, S>(
+    mut actor: A,
+    actor_ref: ActorRef<A>,
+    mut mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
+    mut stop_guard: StopGuard,
+) -> Result<(RunningActor<A>, ActorLoop<A, S>), (A, ActorStopReason)>
 where
     A: Actor,
     S: ActorState<A>,
 {
     let id = actor_ref.id();
-    let name = A::name();
-    trace!(%id, %name, "actor started");
+    let name = super::name::<A>();
+    if A::log_lifecycle() {
+        trace!(%id, %name, "actor started");
+    }
 
-    let start_res = AssertUnwindSafe(actor.on_start(actor_ref.clone()))
-        .catch_unwind()
-        .await
-        .map(|res| res.map_err(PanicError::new))
-        .map_err(PanicError::new_boxed)
-        .and_then(convert::identity);
+    let start = Instant::now();
+    let start_res = catch_unwind_if(
+        A::unwind_policy().on_start,
+        actor.on_start(actor_ref.clone()),
+    )
+    .await
+    .map(|res| res.map_err(PanicError::new))
+    .map_err(PanicError::from)
+    .and_then(convert::identity);
+    let elapsed = start.elapsed();
+    let _ = actor_ref.counters.startup_duration.set(elapsed);
+    if let Some(threshold) = A::slow_startup_threshold() {
+        if elapsed > threshold {
+            warn!(%id, %name, ?elapsed, ?threshold, "slow actor startup");
+        }
+    }
 
     let _ = actor_ref
         .weak_signal_mailbox()
         .signal_startup_finished()
         .await;
-    let (actor_ref, links, startup_semaphore) = {
-        // Downgrade actor ref
+    // Holding a strong ref keeps the mailbox open when all other refs are dropped
+    let keep_alive = (!A::stop_when_unreferenced()).then(|| actor_ref.clone());
+    let (actor_ref, links, startup_semaphore, kill_after_current, stop_reason, paused) = {
+        // Downgrade actor ref, dropping the strong ref so the actor can stop when it's no longer referenced
         let weak_actor_ref = actor_ref.downgrade();
-        (weak_actor_ref, actor_ref.links, actor_ref.startup_semaphore)
+        let parts = (
+            weak_actor_ref,
+            actor_ref.links.clone(),
+            actor_ref.startup_semaphore.clone(),
+            actor_ref.kill_after_current.clone(),
+            actor_ref.stop_reason.clone(),
+            actor_ref.counters.paused.subscribe(),
+        );
+        drop(actor_ref);
+        parts
     };
 
     if let Err(err) = start_res {
+        // Wakes anyone waiting for startup, since it will never finish
+        startup_semaphore.close();
         let reason = ActorStopReason::Panicked(err);
         let mut state = S::new_from_actor(actor, actor_ref.clone());
         let reason = state.on_shutdown(reason.clone()).await.unwrap_or(reason);
-        let mut actor = state.shutdown().await;
-        actor
-            .on_stop(actor_ref.clone(), reason.clone())
-            .await
+        let (mut actor, buffered) = state.shutdown().await;
+        let transfer_to = actor_ref.loop_state.stop.lock().unwrap().transfer_to.take();
+        let stashed = actor_ref
+            .loop_state
+            .stash
+            .lock()
+            .unwrap()
+            .drain_undelivered();
+        drain_undelivered(&mut actor, stashed, buffered, &mut mailbox_rx, transfer_to).await;
+        let on_stop_res = catch_unwind_if(
+            A::unwind_policy().on_stop,
+            actor.on_stop(actor_ref.clone(), reason.clone()),
+        )
+        .await;
+        stop_guard.disarm();
+        stop_reason.send_replace(Some(reason.clone()));
+        registry::unregister_running(id);
+        log_actor_stop_reason(id, name, &reason, A::log_lifecycle(), A::panic_log_level());
+        on_stop_res
+            .unwrap_or_else(|err| panic::resume_unwind(err.payload))
             .unwrap();
-        log_actor_stop_reason(id, name, &reason);
-        return (actor, reason);
+        return Err((actor, reason));
     }
 
-    let mut state = S::new_from_actor(actor, actor_ref.clone());
+    #[cfg(feature = "warn-unreferenced")]
+    warn_if_unreferenced(actor_ref.clone(), keep_alive.is_some());
 
-    let reason = Abortable::new(
-        abortable_actor_loop(&mut state, mailbox_rx, startup_semaphore),
-        abort_registration,
-    )
-    .await
-    .unwrap_or(ActorStopReason::Killed);
+    let state = S::new_from_actor(actor, actor_ref.clone());
+    Ok((
+        RunningActor {
+            id,
+            name,
+            actor_ref,
+            links,
+            stop_reason,
+            keep_alive,
+            stop_guard,
+        },
+        ActorLoop {
+            state,
+            mailbox_rx,
+            startup_semaphore,
+            kill_after_current,
+            paused,
+            pending: None,
+        },
+    ))
+}
+
+/// Stops an actor whose message loop has returned, running [`Actor::on_stop`].
+async fn stop_actor<A, S>(
+    running: RunningActor<A>,
+    actor_loop: ActorLoop<A, S>,
+    reason: ActorStopReason,
+) -> (A, ActorStopReason)
+where
+    A: Actor,
+    S: ActorState<A>,
+{
+    let RunningActor {
+        id,
+        name,
+        actor_ref,
+        links,
+        stop_reason,
+        keep_alive,
+        mut stop_guard,
+    } = running;
+    let ActorLoop {
+        state,
+        mut mailbox_rx,
+        pending,
+        ..
+    } = actor_loop;
+    drop(keep_alive);
+    let (killed_immediately, transfer_to) = {
+        let mut stop = actor_ref.loop_state.stop.lock().unwrap();
+        (
+            matches!(reason, ActorStopReason::Killed) && stop.killed_immediately,
+            stop.transfer_to.take(),
+        )
+    };
+    let reason = match reason {
+        ActorStopReason::Killed if killed_immediately => ActorStopReason::KilledImmediately,
+        // Stopping to hand off the mailbox is not a failure
+        ActorStopReason::Killed if transfer_to.is_some() => ActorStopReason::Normal,
+        reason => reason,
+    };
 
-    let mut actor = state.shutdown().await;
+    let (mut actor, mut buffered) = state.shutdown().await;
+    buffered.extend(pending);
+    if !killed_immediately {
+        let stashed = actor_ref
+            .loop_state
+            .stash
+            .lock()
+            .unwrap()
+            .drain_undelivered();
+        drain_undelivered(&mut actor, stashed, buffered, &mut mailbox_rx, transfer_to).await;
+    }
 
     {
         let mut links = links.lock().await;
-        for (_, actor_ref) in links.drain() {
-            let _ = actor_ref.signal_link_died(id, reason.clone()).await;
+        for (_, link) in links.drain() {
+            let _ = link.mailbox.signal_link_died(id, reason.clone()).await;
         }
     }
 
-    let on_stop_res = actor.on_stop(actor_ref, reason.clone()).await;
-    log_actor_stop_reason(id, name, &reason);
-    on_stop_res.unwrap();
+    let on_stop_res = if killed_immediately {
+        Ok(Ok(()))
+    } else {
+        catch_unwind_if(
+            A::unwind_policy().on_stop,
+            actor.on_stop(actor_ref, reason.clone()),
+        )
+        .await
+    };
+    stop_guard.disarm();
+    stop_reason.send_replace(Some(reason.clone()));
+    registry::unregister_running(id);
+    log_actor_stop_reason(id, name, &reason, A::log_lifecycle(), A::panic_log_level());
+    on_stop_res
+        .unwrap_or_else(|err| panic::resume_unwind(err.payload))
+        .unwrap();
 
     (actor, reason)
 }
 
-async fn abortable_actor_loop<A, S>(
-    state: &mut S,
-    mut mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
+/// Passes each message left in the actor's stash, buffered during startup, or remaining in the mailbox
+/// to [`Actor::on_undelivered`].
+///
+/// If the mailbox is being transferred with [`ActorRef::transfer_mailbox_to`], messages are redelivered
+/// to the other actor in order instead, and only those it no longer accepts are passed to `on_undelivered`.
+async fn drain_undelivered<A: Actor>(
+    actor: &mut A,
+    stashed: VecDeque<UndeliveredMessage<A>>,
+    buffered: VecDeque<Signal<A>>,
+    mailbox_rx: &mut <A::Mailbox as Mailbox<A>>::Receiver,
+    transfer_to: Option<ActorRef<A>>,
+) {
+    let signals = buffered
+        .into_iter()
+        .chain(iter::from_fn(|| mailbox_rx.try_recv()))
+        .filter_map(UndeliveredMessage::from_signal);
+    for msg in stashed.into_iter().chain(signals) {
+        let msg = match &transfer_to {
+            Some(transfer_to) => match msg.redeliver(transfer_to).await {
+                Ok(()) => continue,
+                Err(SendError::ActorNotRunning(msg) | SendError::MailboxFull(msg)) => msg,
+                Err(_) => continue,
+            },
+            None => msg,
+        };
+        actor.on_undelivered(msg).await;
+    }
+}
+
+/// Marks the actor as killed if its task is dropped before it finished stopping, such as when the task is
+/// aborted or its runtime shuts down, or if it's dropped as a [`PreparedActor`] without ever running.
+///
+/// `on_stop` cannot be awaited from here, but anyone waiting for the actor to stop is still notified,
+/// and links are sent a link died signal if their mailbox has capacity.
+struct StopGuard {
+    id: ActorID,
+    name: &'static str,
+    log_lifecycle: bool,
+    panic_log_level: Level,
+    links: Links,
+    stop_reason: Arc<watch::Sender<Option<ActorStopReason>>>,
+    spawn_permit: Option<SpawnPermit>,
+    armed: bool,
+}
+
+impl StopGuard {
+    fn new<A: Actor>(actor_ref: &ActorRef<A>) -> Self {
+        StopGuard {
+            id: actor_ref.id(),
+            name: super::name::<A>(),
+            log_lifecycle: A::log_lifecycle(),
+            panic_log_level: A::panic_log_level(),
+            links: actor_ref.links.clone(),
+            stop_reason: actor_ref.stop_reason.clone(),
+            spawn_permit: None,
+            armed: true,
+        }
+    }
+
+    /// Takes over the actor's place in its spawn category, which is assigned after the actor is prepared.
+    fn take_spawn_permit<A: Actor>(&mut self, actor_ref: &ActorRef<A>) {
+        self.spawn_permit = actor_ref.counters.spawn_permit.lock().unwrap().take();
+    }
+
+    /// Marks the actor as stopped normally, freeing its place in its spawn category before the stop reason is sent.
+    fn disarm(&mut self) {
+        self.armed = false;
+        self.spawn_permit = None;
+    }
+}
+
+impl Drop for StopGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+
+        let reason = ActorStopReason::Killed;
+        if let Ok(mut links) = self.links.try_lock() {
+            for (_, link) in links.drain() {
+                let _ = link
+                    .mailbox
+                    .signal_link_died(self.id, reason.clone())
+                    .now_or_never();
+            }
+        }
+        self.spawn_permit = None;
+        self.stop_reason.send_replace(Some(reason.clone()));
+        registry::unregister_running(self.id);
+        log_actor_stop_reason(
+            self.id,
+            self.name,
+            &reason,
+            self.log_lifecycle,
+            self.panic_log_level,
+        );
+    }
+}
+
+/// Everything an actor's message loop owns, which moves with the actor when it is migrated.
+struct ActorLoop<A: Actor, S> {
+    state: S,
+    mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
     startup_semaphore: Arc<Semaphore>,
-) -> ActorStopReason
+    kill_after_current: Arc<Notify>,
+    /// Whether the actor has been paused, in which case no signals are received from the mailbox.
+    paused: watch::Receiver<bool>,
+    /// A signal which was received but not handled before the loop returned, to be handled next.
+    pending: Option<Signal<A>>,
+}
+
+/// Why an actor's message loop returned.
+enum LoopExit {
+    Stopped(ActorStopReason),
+    Migrate {
+        handle: Handle,
+        migrated: oneshot::Sender<()>,
+    },
+}
+
+/// A migrated actor loop running on another runtime, which is aborted when dropped.
+struct MigratedLoop<A: Actor, S> {
+    abort_handle: AbortHandle,
+    join_handle: JoinHandle<(ActorLoop<A, S>, Result<LoopExit, Aborted>)>,
+}
+
+impl<A: Actor, S> Drop for MigratedLoop<A, S> {
+    fn drop(&mut self) {
+        self.abort_handle.abort();
+    }
+}
+
+/// Runs the actor's message loop until it stops, moving it to another runtime each time it is migrated.
+///
+/// The loop runs in the current task until it is first migrated. From then on it runs in a task on the
+/// target runtime, while the current task waits for it, and aborts it if the actor is killed.
+async fn run_actor_loop<A, S>(
+    id: ActorID,
+    actor_loop: ActorLoop<A, S>,
+    abort_registration: AbortRegistration,
+) -> (ActorLoop<A, S>, ActorStopReason)
+where
+    A: Actor,
+    S: ActorState<A> + Send + 'static,
+{
+    let mut current = Some(actor_loop);
+    let mut migrated: Option<MigratedLoop<A, S>> = None;
+
+    let res = Abortable::new(
+        async {
+            let mut exit = { abortable_actor_loop(current.as_mut().unwrap()).await };
+            loop {
+                let (handle, on_migrated) = match exit {
+                    LoopExit::Stopped(reason) => return reason,
+                    LoopExit::Migrate { handle, migrated } => (handle, migrated),
+                };
+
+                let mut actor_loop = current.take().unwrap();
+                let (abort_handle, abort_registration) = AbortHandle::new_pair();
+                let join_handle = handle.spawn(CURRENT_EXECUTION_MODE.scope(
+                    ExecutionMode::TokioTask,
+                    CURRENT_ACTOR_ID.scope(
+                        id,
+                        A::task_wrapper(async move {
+                            let _ = on_migrated.send(());
+                            let exit = Abortable::new(
+                                abortable_actor_loop(&mut actor_loop),
+                                abort_registration,
+                            )
+                            .await;
+                            (actor_loop, exit)
+                        }),
+                    ),
+                ));
+                let migrated_loop = migrated.insert(MigratedLoop {
+                    abort_handle,
+                    join_handle,
+                });
+
+                let (actor_loop, res) = join_migrated(&mut migrated_loop.join_handle).await;
+                migrated = None;
+                current = Some(actor_loop);
+                exit = res.unwrap_or(LoopExit::Stopped(ActorStopReason::Killed));
+            }
+        },
+        abort_registration,
+    )
+    .await;
+
+    let reason = match res {
+        Ok(reason) => reason,
+        Err(Aborted) => {
+            // Abort the migrated loop too, and wait for it to hand the actor back
+            if let Some(mut migrated_loop) = migrated.take() {
+                migrated_loop.abort_handle.abort();
+                let (actor_loop, _) = join_migrated(&mut migrated_loop.join_handle).await;
+                current = Some(actor_loop);
+            }
+            ActorStopReason::Killed
+        }
+    };
+
+    (current.unwrap(), reason)
+}
+
+async fn join_migrated<T>(join_handle: &mut JoinHandle<T>) -> T {
+    match join_handle.await {
+        Ok(res) => res,
+        Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+        Err(_) => panic!("actor was dropped by the runtime it migrated to"),
+    }
+}
+
+async fn abortable_actor_loop<A, S>(actor_loop: &mut ActorLoop<A, S>) -> LoopExit
 where
     A: Actor,
     S: ActorState<A>,
 {
     loop {
-        let reason = recv_mailbox_loop(state, &mut mailbox_rx, &startup_semaphore).await;
-        if let Some(reason) = state.on_shutdown(reason).await {
-            return reason;
+        let reason = match recv_mailbox_loop(actor_loop).await {
+            LoopExit::Stopped(reason) => reason,
+            exit @ LoopExit::Migrate { .. } => return exit,
+        };
+        if let Some(reason) = actor_loop.state.on_shutdown(reason).await {
+            return LoopExit::Stopped(reason);
         }
     }
 }
 
-async fn recv_mailbox_loop<A, S>(
-    state: &mut S,
-    mailbox_rx: &mut <A::Mailbox as Mailbox<A>>::Receiver,
-    startup_semaphore: &Semaphore,
-) -> ActorStopReason
+async fn recv_mailbox_loop<A, S>(actor_loop: &mut ActorLoop<A, S>) -> LoopExit
 where
     A: Actor,
     S: ActorState<A>,
 {
+    let ActorLoop {
+        state,
+        mailbox_rx,
+        startup_semaphore,
+        kill_after_current,
+        paused,
+        pending,
+    } = actor_loop;
     loop {
-        match mailbox_rx.recv().await {
-            Some(Signal::StartupFinished) => {
-                startup_semaphore.add_permits(Semaphore::MAX_PERMITS);
-                if let Some(reason) = state.handle_startup_finished().await {
-                    return reason;
-                }
-            }
-            Some(Signal::Message {
+        let signal = match pending.take() {
+            Some(signal) => Some(signal),
+            None => tokio::select! {
+                biased;
+                // Checked between messages, so a handler is never interrupted
+                _ = kill_after_current.notified() => return LoopExit::Stopped(ActorStopReason::Killed),
+                signal = recv_unless_paused(mailbox_rx, paused) => signal,
+            },
+        };
+        #[cfg(feature = "metrics")]
+        if let Some(signal) = &signal {
+            super::metrics::mailbox_depth(signal);
+        }
+        let exit = match signal {
+            Some(Signal::Read {
                 message,
                 actor_ref,
                 reply,
-                sent_within_actor,
-            }) => {
-                if let Some(reason) = state
-                    .handle_message(message, actor_ref, reply, sent_within_actor)
-                    .await
-                {
-                    return reason;
+                options,
+                ..
+            }) if state.reads_concurrently() => {
+                let first = (message, actor_ref, reply, options);
+                let actor_loop = ReadLoop {
+                    mailbox_rx,
+                    startup_semaphore,
+                    kill_after_current,
+                    paused,
+                    pending,
+                };
+                handle_reads(state, first, actor_loop).await
+            }
+            signal => handle_signal(state, startup_semaphore, signal).await,
+        };
+        if let Some(exit) = exit {
+            return exit;
+        }
+    }
+}
+
+/// Receives the next signal from the mailbox, waiting first for the actor to be resumed if it's paused.
+///
+/// This is cancel safe, since the mailbox's `recv` is. Pausing an actor which is waiting for a signal stops the wait.
+async fn recv_unless_paused<A: Actor>(
+    mailbox_rx: &mut <A::Mailbox as Mailbox<A>>::Receiver,
+    paused: &mut watch::Receiver<bool>,
+) -> Option<Signal<A>> {
+    loop {
+        // The sender lives as long as the actor's counters, so it's never closed while the actor is running
+        if paused.wait_for(|paused| !paused).await.is_err() {
+            return mailbox_rx.recv().await;
+        }
+        tokio::select! {
+            biased;
+            changed = paused.changed() => {
+                if changed.is_err() {
+                    return mailbox_rx.recv().await;
                 }
             }
-            Some(Signal::LinkDied { id, reason }) => {
-                if let Some(reason) = state.handle_link_died(id, reason).await {
-                    return reason;
+            signal = mailbox_rx.recv() => return signal,
+        }
+    }
+}
+
+/// The parts of a [`Signal::Read`] needed to handle it.
+type ReadSignal<A> = (
+    Box<dyn DynReadMessage<A>>,
+    ActorRef<A>,
+    Option<BoxReplySender>,
+    Option<Box<MessageOptions>>,
+);
+
+/// The parts of an [`ActorLoop`] used while handling reads, which don't borrow the actor.
+struct ReadLoop<'a, A: Actor> {
+    mailbox_rx: &'a mut <A::Mailbox as Mailbox<A>>::Receiver,
+    startup_semaphore: &'a Semaphore,
+    kill_after_current: &'a Notify,
+    paused: &'a watch::Receiver<bool>,
+    pending: &'a mut Option<Signal<A>>,
+}
+
+/// Handles reads concurrently, starting with `first`, until a signal which isn't a read is received.
+///
+/// The signal ending the batch is only handled once every read has finished, so a message never runs while a read
+/// is borrowing the actor. No more signals are received while the actor's
+/// [`max_concurrent_reads`](Actor::max_concurrent_reads) are running, once a read has failed,
+/// or while the actor is paused.
+/// If the loop exits before the signal is handled, it's left pending to be handled next.
+async fn handle_reads<A, S>(
+    state: &mut S,
+    (message, actor_ref, reply, options): ReadSignal<A>,
+    actor_loop: ReadLoop<'_, A>,
+) -> Option<LoopExit>
+where
+    A: Actor,
+    S: ActorState<A>,
+{
+    let ReadLoop {
+        mailbox_rx,
+        startup_semaphore,
+        kill_after_current,
+        paused,
+        pending,
+    } = actor_loop;
+    let limit = A::max_concurrent_reads().max(1);
+    let mut reads = FuturesUnordered::new();
+    reads.push(state.read(message, actor_ref, reply, options));
+    let mut stop_reason = None;
+    let mut killed = false;
+    let mut next = None;
+    while !reads.is_empty() {
+        tokio::select! {
+            biased;
+            Some(reason) = reads.next() => {
+                if let Some(reason) = reason {
+                    stop_reason.get_or_insert(reason);
                 }
             }
-            Some(Signal::Stop) | None => {
-                if let Some(reason) = state.handle_stop().await {
-                    return reason;
+            _ = kill_after_current.notified(), if !killed => killed = true,
+            signal = mailbox_rx.recv(),
+                if next.is_none() && stop_reason.is_none() && !killed && reads.len() < limit && !*paused.borrow() =>
+            {
+                #[cfg(feature = "metrics")]
+                if let Some(signal) = &signal {
+                    super::metrics::mailbox_depth(signal);
+                }
+                match signal {
+                    Some(Signal::Read {
+                        message,
+                        actor_ref,
+                        reply,
+                        options,
+                        ..
+                    }) => reads.push(state.read(message, actor_ref, reply, options)),
+                    signal => next = Some(signal),
                 }
             }
         }
     }
+    drop(reads);
+
+    let exit = match (stop_reason, killed) {
+        (Some(reason), _) => LoopExit::Stopped(reason),
+        (None, true) => LoopExit::Stopped(ActorStopReason::Killed),
+        (None, false) => {
+            return match next {
+                Some(signal) => handle_signal(state, startup_semaphore, signal).await,
+                None => None,
+            };
+        }
+    };
+    // A closed mailbox is received again, so only an actual signal needs to be kept
+    *pending = next.flatten();
+    Some(exit)
+}
+
+/// Handles a signal received from the mailbox, returning whether the message loop should exit.
+///
+/// A closed mailbox is handled as a stop signal.
+async fn handle_signal<A, S>(
+    state: &mut S,
+    startup_semaphore: &Semaphore,
+    signal: Option<Signal<A>>,
+) -> Option<LoopExit>
+where
+    A: Actor,
+    S: ActorState<A>,
+{
+    match signal {
+        Some(Signal::StartupFinished) => {
+            startup_semaphore.add_permits(Semaphore::MAX_PERMITS);
+            state.handle_startup_finished().await.map(LoopExit::Stopped)
+        }
+        Some(Signal::Message {
+            message,
+            actor_ref,
+            reply,
+            sent_within_actor,
+            options,
+        }) => state
+            .handle_message(message, actor_ref, reply, sent_within_actor, options)
+            .await
+            .map(LoopExit::Stopped),
+        Some(Signal::Read {
+            message,
+            actor_ref,
+            reply,
+            sent_within_actor,
+            options,
+        }) => state
+            .handle_read(message, actor_ref, reply, sent_within_actor, options)
+            .await
+            .map(LoopExit::Stopped),
+        Some(Signal::LinkDied { id, reason }) => state
+            .handle_link_died(id, reason)
+            .await
+            .map(LoopExit::Stopped),
+        Some(Signal::Migrate { handle, migrated }) => Some(LoopExit::Migrate { handle, migrated }),
+        Some(Signal::AwaitState { waiter }) => {
+            state.handle_await_state(waiter);
+            None
+        }
+        Some(Signal::Describe { reply }) => {
+            state.handle_describe(reply);
+            None
+        }
+        Some(Signal::Stop { reason }) => state.handle_stop(reason).await.map(LoopExit::Stopped),
+        None => state.handle_stop(None).await.map(LoopExit::Stopped),
+    }
+}
+
+#[inline]
+fn register_running<A: Actor>(actor_ref: &ActorRef<A>) {
+    crate::test::record_spawned(actor_ref.id());
+    registry::register_running(
+        actor_ref.id(),
+        actor_ref.weak_signal_mailbox(),
+        actor_ref.abort_handle.clone(),
+        actor_ref.stop_reason.clone(),
+    );
+}
+
+/// Spawns a tokio task in the scope actors are spawned in, so it's tracked by the current
+/// [`with_teardown`](crate::test::with_teardown) and reports [`ExecutionMode::TokioTask`].
+pub(crate) fn spawn_task<F>(fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(task_scope(fut))
+}
+
+/// Wraps a future in the scope of a tokio task spawned by [`spawn_task`].
+fn task_scope<F: Future>(fut: F) -> impl Future<Output = F::Output> {
+    crate::test::Teardown::current()
+        .scope(CURRENT_EXECUTION_MODE.scope(ExecutionMode::TokioTask, fut))
+}
+
+/// How long after startup an actor must have been sent a message or still be referenced,
+/// before it's considered a mistake that its refs were dropped.
+#[cfg(feature = "warn-unreferenced")]
+const UNREFERENCED_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Warns if all of an actor's refs are dropped shortly after it starts, before it has received any messages.
+///
+/// This usually means the actor was spawned with its `ActorRef` discarded, leaving no way to send it messages.
+/// Actors which have already stopped are ignored, since dropping all refs is how
+/// [`stop_when_unreferenced`](Actor::stop_when_unreferenced) actors are stopped.
+#[cfg(feature = "warn-unreferenced")]
+fn warn_if_unreferenced<A: Actor>(actor_ref: super::WeakActorRef<A>, kept_alive: bool) {
+    use std::sync::atomic::Ordering;
+
+    // The actor's own keep alive ref is not counted as a reference
+    let internal_refs = usize::from(kept_alive);
+    tokio::spawn(async move {
+        tokio::time::sleep(UNREFERENCED_GRACE_PERIOD).await;
+        let processed = actor_ref.counters.processed.load(Ordering::Relaxed);
+        let running = actor_ref.stop_reason.borrow().is_none();
+        if running && processed == 0 && actor_ref.strong_count() <= internal_refs {
+            warn!(
+                id = %actor_ref.id(),
+                name = %super::name::<A>(),
+                "actor has not received any messages, and all of its refs were dropped"
+            );
+        }
+    });
 }
 
 #[inline]
-fn log_actor_stop_reason(id: ActorID, name: &str, reason: &ActorStopReason) {
+fn log_actor_stop_reason(
+    id: ActorID,
+    name: &str,
+    reason: &ActorStopReason,
+    log_lifecycle: bool,
+    panic_log_level: Level,
+) {
     match reason {
         reason @ ActorStopReason::Normal
         | reason @ ActorStopReason::Killed
-        | reason @ ActorStopReason::LinkDied { .. } => {
-            trace!(%id, %name, %reason, "actor stopped");
-        }
-        reason @ ActorStopReason::Panicked(_) => {
-            error!(%id, %name, %reason, "actor stopped")
+        | reason @ ActorStopReason::KilledImmediately
+        | reason @ ActorStopReason::LinkDied { .. }
+        | reason @ ActorStopReason::Custom(_) => {
+            if log_lifecycle {
+                trace!(%id, %name, %reason, "actor stopped");
+            }
         }
+        // Event levels must be constant, so each level is logged separately
+        reason @ ActorStopReason::Panicked(_) => match panic_log_level {
+            Level::ERROR => error!(%id, %name, %reason, "actor stopped"),
+            Level::WARN => warn!(%id, %name, %reason, "actor stopped"),
+            Level::INFO => info!(%id, %name, %reason, "actor stopped"),
+            Level::DEBUG => debug!(%id, %name, %reason, "actor stopped"),
+            Level::TRACE => trace!(%id, %name, %reason, "actor stopped"),
+        },
     }
 }
@@ -0,0 +1,138 @@
+use std::{fmt, future::Future};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::task::AbortHandle;
+
+use crate::{
+    error::{ActorStopReason, BoxError, PanicError},
+    mailbox::unbounded::UnboundedMailbox,
+    Actor,
+};
+
+use super::{spawn, ActorRef, WeakActorRef};
+
+/// Spawns an actor which runs `future` as its work, bringing existing task based code under supervision.
+///
+/// The future is spawned in its own task once the actor starts. When it completes the actor stops normally,
+/// and if it panics the actor stops with [`ActorStopReason::Panicked`], so linked actors are notified just as
+/// they would be for any other actor. Linking works as usual, for example with
+/// [`ActorRef::link`](crate::actor::ActorRef::link).
+///
+/// The actor doesn't handle any messages, and is only controlled through its [`ActorRef`]:
+/// stopping or killing it aborts the future. Until the future completes the actor keeps itself alive,
+/// so the returned ref can be dropped without cancelling the work.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::{ActorID, WeakActorRef};
+/// use kameo::error::{ActorStopReason, BoxError};
+/// use kameo::mailbox::unbounded::UnboundedMailbox;
+/// use kameo::Actor;
+/// use tokio::sync::oneshot;
+///
+/// struct Supervisor;
+///
+/// impl Actor for Supervisor {
+///     type Mailbox = UnboundedMailbox<Self>;
+///
+///     async fn on_link_died(
+///         &mut self,
+///         _: WeakActorRef<Self>,
+///         _: ActorID,
+///         reason: ActorStopReason,
+///     ) -> Result<Option<ActorStopReason>, BoxError> {
+///         Ok(Some(reason))
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let prepared = kameo::actor::prepare(Supervisor);
+/// let supervisor = prepared.actor_ref_owned();
+/// let supervisor_handle = prepared.spawn();
+///
+/// let (tx, rx) = oneshot::channel::<()>();
+/// let task = kameo::supervise_task(async move {
+///     let _ = rx.await;
+///     panic!("legacy task failed");
+/// });
+/// supervisor.link(&task).await;
+///
+/// tx.send(()).unwrap();
+/// let (_, reason) = supervisor_handle.await?;
+/// assert!(matches!(reason.root_cause(), ActorStopReason::Panicked(_)));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub fn supervise_task<F>(future: F) -> ActorRef<TaskActor>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    spawn(TaskActor {
+        future: Some(future.boxed()),
+        abort_handle: None,
+    })
+}
+
+/// The actor spawned by [`supervise_task`], which runs a future as its work.
+pub struct TaskActor {
+    future: Option<BoxFuture<'static, ()>>,
+    abort_handle: Option<AbortHandle>,
+}
+
+impl Actor for TaskActor {
+    type Mailbox = UnboundedMailbox<Self>;
+
+    fn name() -> &'static str {
+        "TaskActor"
+    }
+
+    async fn on_start(&mut self, actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+        let Some(future) = self.future.take() else {
+            return Ok(());
+        };
+        let task = tokio::spawn(future);
+        self.abort_handle = Some(task.abort_handle());
+
+        // Holds a strong ref, keeping the actor alive until the work is done
+        tokio::spawn(async move {
+            match task.await {
+                Ok(()) => {
+                    let _ = actor_ref.stop_gracefully().await;
+                }
+                Err(err) if err.is_panic() => {
+                    let reason = ActorStopReason::Panicked(PanicError::new_boxed(err.into_panic()));
+                    let _ = actor_ref.stop_with_reason(reason).await;
+                }
+                // Aborted by the actor stopping
+                Err(_) => {}
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn on_stop(&mut self, _: WeakActorRef<Self>, _: ActorStopReason) -> Result<(), BoxError> {
+        if let Some(abort_handle) = &self.abort_handle {
+            abort_handle.abort();
+        }
+        Ok(())
+    }
+}
+
+// Also aborts the work if the actor is killed without running `on_stop`
+impl Drop for TaskActor {
+    fn drop(&mut self) {
+        if let Some(abort_handle) = &self.abort_handle {
+            abort_handle.abort();
+        }
+    }
+}
+
+impl fmt::Debug for TaskActor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskActor")
+            .field("started", &self.abort_handle.is_some())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,57 @@
+use futures::{future, FutureExt};
+use tracing::trace;
+
+use super::AnyActorRef;
+
+/// Links a set of actors as a team, which all stop as soon as any one of them stops.
+///
+/// Unlike [`ActorRef::link`](crate::actor::ActorRef::link), where an actor stopping normally does not stop the actors
+/// it is linked to by default, a team member stopping for any reason, even normally, kills every other member.
+/// This is useful for tightly coupled actors which only make sense together.
+///
+/// The team is monitored by a background task, which does not keep any member's mailbox open.
+/// If a member has already stopped, the rest of the team is killed straight away.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::AnyActorRef;
+/// use kameo::error::ActorStopReason;
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct Reader;
+///
+/// #[derive(Actor)]
+/// struct Writer;
+///
+/// # tokio_test::block_on(async {
+/// let reader = AnyActorRef::from(kameo::spawn(Reader));
+/// let writer = AnyActorRef::from(kameo::spawn(Writer));
+/// kameo::link_team(&[reader.clone(), writer.clone()]);
+///
+/// reader.stop_gracefully().await?;
+/// assert!(matches!(writer.wait_for_stop().await, ActorStopReason::Killed));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub fn link_team(members: &[AnyActorRef]) {
+    if members.len() < 2 {
+        return;
+    }
+
+    let mut monitors: Vec<_> = members.iter().map(AnyActorRef::monitor).collect();
+    tokio::spawn(async move {
+        let (reason, index, _) = future::select_all(
+            monitors
+                .iter_mut()
+                .map(|monitor| monitor.wait_for_stop().boxed()),
+        )
+        .await;
+        let id = monitors[index].id;
+        trace!(%id, ?reason, "team member stopped, killing the rest of the team");
+        for monitor in &monitors {
+            monitor.abort_handle.abort();
+        }
+    });
+}
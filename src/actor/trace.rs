@@ -0,0 +1,89 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// A message recorded in an actor's recent message trace.
+///
+/// Messages are recorded by their type name, and can optionally carry a serialized payload
+/// attached by the handler with [`Context::trace_payload`](crate::message::Context::trace_payload).
+///
+/// Recent messages can be retrieved with [`ActorRef::recent_messages`](crate::actor::ActorRef::recent_messages),
+/// and are attached to the [`PanicError`](crate::error::PanicError) if the actor panics.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RecordedMessage {
+    type_name: &'static str,
+    payload: Option<Arc<[u8]>>,
+}
+
+impl RecordedMessage {
+    /// Returns the type name of the message.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the serialized payload of the message, if one was attached by the handler.
+    pub fn payload(&self) -> Option<&[u8]> {
+        self.payload.as_deref()
+    }
+}
+
+impl fmt::Debug for RecordedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordedMessage")
+            .field("type_name", &self.type_name)
+            .field(
+                "payload_len",
+                &self.payload.as_ref().map(|payload| payload.len()),
+            )
+            .finish()
+    }
+}
+
+/// A ring buffer of the most recent messages received by an actor.
+///
+/// The capacity is stored behind the same `Arc` as the buffer to keep actor refs small.
+#[derive(Clone)]
+pub(crate) struct MessageTrace(Arc<MessageTraceInner>);
+
+struct MessageTraceInner {
+    capacity: usize,
+    buffer: Mutex<VecDeque<RecordedMessage>>,
+}
+
+impl MessageTrace {
+    pub(crate) fn new(capacity: usize) -> Self {
+        MessageTrace(Arc::new(MessageTraceInner {
+            capacity,
+            buffer: Mutex::new(VecDeque::with_capacity(capacity)),
+        }))
+    }
+
+    pub(crate) fn record(&self, type_name: &'static str) {
+        if self.0.capacity == 0 {
+            return;
+        }
+
+        let mut buffer = self.0.buffer.lock().unwrap_or_else(|err| err.into_inner());
+        if buffer.len() == self.0.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(RecordedMessage {
+            type_name,
+            payload: None,
+        });
+    }
+
+    pub(crate) fn set_last_payload(&self, payload: Vec<u8>) {
+        let mut buffer = self.0.buffer.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(last) = buffer.back_mut() {
+            last.payload = Some(payload.into());
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<RecordedMessage> {
+        let buffer = self.0.buffer.lock().unwrap_or_else(|err| err.into_inner());
+        buffer.iter().cloned().collect()
+    }
+}
@@ -8,6 +8,7 @@ use std::{
     any::{self, Any},
     cmp, error, fmt,
     hash::{Hash, Hasher},
+    num, panic,
     sync::{Arc, Mutex, MutexGuard, PoisonError},
 };
 
@@ -25,6 +26,58 @@ pub type BoxError = Box<dyn error::Error + Send + Sync + 'static>;
 pub type BoxSendError = SendError<Box<dyn any::Any + Send>, Box<dyn any::Any + Send>>;
 
 /// Error that can occur when sending a message to an actor.
+///
+/// When a handler's reply is a `Result<T, E>`, asking the actor returns `Result<T, SendError<M, E>>`,
+/// where errors returned by the handler are kept as their concrete type in [`HandlerError`](SendError::HandlerError),
+/// rather than being boxed or stringified.
+///
+/// # Example
+///
+/// ```
+/// use kameo::error::SendError;
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor)]
+/// struct Bank {
+///     balance: u64,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum WithdrawError {
+///     InsufficientFunds { balance: u64 },
+///     ZeroAmount,
+/// }
+///
+/// struct Withdraw(u64);
+///
+/// impl Message<Withdraw> for Bank {
+///     type Reply = Result<u64, WithdrawError>;
+///
+///     async fn handle(&mut self, Withdraw(amount): Withdraw, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         if amount == 0 {
+///             return Err(WithdrawError::ZeroAmount);
+///         }
+///         self.balance = self.balance.checked_sub(amount).ok_or(WithdrawError::InsufficientFunds {
+///             balance: self.balance,
+///         })?;
+///         Ok(self.balance)
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let bank = kameo::spawn(Bank { balance: 10 });
+///
+/// assert!(matches!(bank.ask(Withdraw(4)).await, Ok(6)));
+/// match bank.ask(Withdraw(100)).await {
+///     Err(SendError::HandlerError(WithdrawError::InsufficientFunds { balance })) => assert_eq!(balance, 6),
+///     other => panic!("unexpected result: {other:?}"),
+/// }
+/// assert!(matches!(
+///     bank.ask(Withdraw(0)).await,
+///     Err(SendError::HandlerError(WithdrawError::ZeroAmount))
+/// ));
+/// # });
+/// ```
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum SendError<M = (), E = Infallible> {
     /// The actor isn't running.
@@ -37,6 +90,13 @@ pub enum SendError<M = (), E = Infallible> {
     HandlerError(E),
     /// Timed out waiting for a reply.
     Timeout(Option<M>),
+    /// The message's [`size_hint`](crate::message::Message::size_hint) exceeds the actor's
+    /// [`max_message_size`](crate::Actor::max_message_size), so it was not sent.
+    TooLarge(M),
+    /// The actor is overloaded and shedding load, so the message was not sent.
+    ///
+    /// See [`Actor::overload_config`].
+    Overloaded(M),
 }
 
 impl<M, E> SendError<M, E> {
@@ -48,6 +108,8 @@ impl<M, E> SendError<M, E> {
             SendError::MailboxFull(_) => SendError::MailboxFull(()),
             SendError::HandlerError(_) => SendError::HandlerError(()),
             SendError::Timeout(_) => SendError::Timeout(None),
+            SendError::TooLarge(_) => SendError::TooLarge(()),
+            SendError::Overloaded(_) => SendError::Overloaded(()),
         }
     }
 
@@ -62,6 +124,8 @@ impl<M, E> SendError<M, E> {
             SendError::MailboxFull(msg) => SendError::MailboxFull(f(msg)),
             SendError::HandlerError(err) => SendError::HandlerError(err),
             SendError::Timeout(msg) => SendError::Timeout(msg.map(f)),
+            SendError::TooLarge(msg) => SendError::TooLarge(f(msg)),
+            SendError::Overloaded(msg) => SendError::Overloaded(f(msg)),
         }
     }
 
@@ -76,6 +140,78 @@ impl<M, E> SendError<M, E> {
             SendError::MailboxFull(msg) => SendError::MailboxFull(msg),
             SendError::HandlerError(err) => SendError::HandlerError(op(err)),
             SendError::Timeout(msg) => SendError::Timeout(msg),
+            SendError::TooLarge(msg) => SendError::TooLarge(msg),
+            SendError::Overloaded(msg) => SendError::Overloaded(msg),
+        }
+    }
+
+    /// Returns whether the message reached the actor's handler, which determines whether it's safe to retry.
+    ///
+    /// Requests which failed with [`Delivery::NotDelivered`] can be retried without the message being handled twice,
+    /// while [`Delivery::Handled`] means the handler itself returned an error, so retrying would usually fail again.
+    ///
+    /// # Migrating from matching on variants
+    ///
+    /// Retry logic which matched on individual variants, such as retrying every error except
+    /// [`HandlerError`](SendError::HandlerError), should match on the delivery instead. In particular,
+    /// [`ActorStopped`](SendError::ActorStopped) and a [`Timeout`](SendError::Timeout) waiting for a reply are both
+    /// [`Delivery::Unknown`], since the message may already have been handled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::error::{Delivery, SendError};
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Inventory;
+    ///
+    /// struct Reserve(u32);
+    ///
+    /// impl Message<Reserve> for Inventory {
+    ///     type Reply = Result<(), &'static str>;
+    ///
+    ///     async fn handle(&mut self, _: Reserve, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         Err("out of stock")
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let inventory = kameo::spawn(Inventory);
+    /// let err = inventory.ask(Reserve(1)).await.unwrap_err();
+    /// assert_eq!(err.delivery(), Delivery::Handled);
+    ///
+    /// inventory.stop_gracefully().await?;
+    /// inventory.wait_for_stop().await;
+    /// let err = inventory.ask(Reserve(2)).await.unwrap_err();
+    /// assert_eq!(err.delivery(), Delivery::NotDelivered);
+    ///
+    /// // The message is returned, so it can be sent elsewhere
+    /// assert!(matches!(err.into_msg(), Some(Reserve(2))));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn delivery(&self) -> Delivery {
+        match self {
+            SendError::ActorNotRunning(_)
+            | SendError::MailboxFull(_)
+            | SendError::TooLarge(_)
+            | SendError::Overloaded(_)
+            | SendError::Timeout(Some(_)) => Delivery::NotDelivered,
+            SendError::HandlerError(_) => Delivery::Handled,
+            SendError::ActorStopped | SendError::Timeout(None) => Delivery::Unknown,
+        }
+    }
+
+    /// Returns the message which failed to be delivered, if it was returned with the error.
+    pub fn into_msg(self) -> Option<M> {
+        match self {
+            SendError::ActorNotRunning(msg)
+            | SendError::MailboxFull(msg)
+            | SendError::TooLarge(msg)
+            | SendError::Overloaded(msg)
+            | SendError::Timeout(Some(msg)) => Some(msg),
+            SendError::ActorStopped | SendError::HandlerError(_) | SendError::Timeout(None) => None,
         }
     }
 
@@ -93,10 +229,25 @@ impl<M, E> SendError<M, E> {
             SendError::Timeout(msg) => {
                 SendError::Timeout(msg.map(|msg| Box::new(msg) as Box<dyn any::Any + Send>))
             }
+            SendError::TooLarge(msg) => SendError::TooLarge(Box::new(msg)),
+            SendError::Overloaded(msg) => SendError::Overloaded(Box::new(msg)),
         }
     }
 }
 
+/// Whether a failed request's message reached the actor's handler, returned by [`SendError::delivery`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Delivery {
+    /// The message was never handed to the actor, such as when it isn't running or its mailbox is full,
+    /// so it's safe to send again.
+    NotDelivered,
+    /// The message was handled, and either the handler returned an error or its reply couldn't be sent back.
+    Handled,
+    /// The message was sent, but it's unknown whether it was handled, such as when the actor stopped
+    /// or the reply timed out.
+    Unknown,
+}
+
 impl<M, E> SendError<M, SendError<M, E>> {
     /// Flattens a nested SendError.
     pub fn flatten(self) -> SendError<M, E> {
@@ -115,6 +266,12 @@ impl<M, E> SendError<M, SendError<M, E>> {
             SendError::Timeout(msg) | SendError::HandlerError(SendError::Timeout(msg)) => {
                 SendError::Timeout(msg)
             }
+            SendError::TooLarge(msg) | SendError::HandlerError(SendError::TooLarge(msg)) => {
+                SendError::TooLarge(msg)
+            }
+            SendError::Overloaded(msg) | SendError::HandlerError(SendError::Overloaded(msg)) => {
+                SendError::Overloaded(msg)
+            }
         }
     }
 }
@@ -132,6 +289,8 @@ impl BoxSendError {
             SendError::MailboxFull(err) => SendError::MailboxFull(*err.downcast().unwrap()),
             SendError::HandlerError(err) => SendError::HandlerError(*err.downcast().unwrap()),
             SendError::Timeout(err) => SendError::Timeout(err.map(|err| *err.downcast().unwrap())),
+            SendError::TooLarge(err) => SendError::TooLarge(*err.downcast().unwrap()),
+            SendError::Overloaded(err) => SendError::Overloaded(*err.downcast().unwrap()),
         }
     }
 }
@@ -147,6 +306,8 @@ where
             SendError::MailboxFull(_) => write!(f, "MailboxFull"),
             SendError::HandlerError(err) => err.fmt(f),
             SendError::Timeout(_) => write!(f, "Timeout"),
+            SendError::TooLarge(_) => write!(f, "TooLarge"),
+            SendError::Overloaded(_) => write!(f, "Overloaded"),
         }
     }
 }
@@ -162,6 +323,8 @@ where
             SendError::MailboxFull(_) => write!(f, "mailbox full"),
             SendError::HandlerError(err) => err.fmt(f),
             SendError::Timeout(_) => write!(f, "timeout"),
+            SendError::TooLarge(_) => write!(f, "message too large"),
+            SendError::Overloaded(_) => write!(f, "actor overloaded"),
         }
     }
 }
@@ -343,6 +506,17 @@ pub enum RemoteSendError<E> {
     /// An IO failure happened on an outbound stream.
     #[serde(skip)]
     Io(Option<std::io::Error>),
+    /// The peer could not be reached after the retries allowed by the actor ref's
+    /// [`ReconnectPolicy`](crate::remote::ReconnectPolicy), or too many requests were already waiting to reconnect.
+    ///
+    /// The request was never received by the remote peer.
+    Disconnected,
+    /// The message exceeds the actor's [`max_message_size`](crate::Actor::max_message_size).
+    TooLarge,
+    /// The actor is overloaded and shedding load, so the message was not handled.
+    ///
+    /// See [`Actor::overload_config`].
+    Overloaded,
 }
 
 #[cfg(feature = "remote")]
@@ -382,6 +556,9 @@ impl<E> RemoteSendError<E> {
             RemoteSendError::NetworkTimeout => RemoteSendError::NetworkTimeout,
             RemoteSendError::ConnectionClosed => RemoteSendError::ConnectionClosed,
             RemoteSendError::Io(err) => RemoteSendError::Io(err),
+            RemoteSendError::Disconnected => RemoteSendError::Disconnected,
+            RemoteSendError::TooLarge => RemoteSendError::TooLarge,
+            RemoteSendError::Overloaded => RemoteSendError::Overloaded,
         }
     }
 }
@@ -427,6 +604,9 @@ impl<E> RemoteSendError<RemoteSendError<E>> {
             NetworkTimeout | HandlerError(NetworkTimeout) => NetworkTimeout,
             ConnectionClosed | HandlerError(ConnectionClosed) => ConnectionClosed,
             Io(err) | HandlerError(Io(err)) => Io(err),
+            Disconnected | HandlerError(Disconnected) => Disconnected,
+            TooLarge | HandlerError(TooLarge) => TooLarge,
+            Overloaded | HandlerError(Overloaded) => Overloaded,
         }
     }
 }
@@ -440,6 +620,8 @@ impl<M, E> From<SendError<M, E>> for RemoteSendError<E> {
             SendError::MailboxFull(_) => RemoteSendError::MailboxFull,
             SendError::HandlerError(err) => RemoteSendError::HandlerError(err),
             SendError::Timeout(_) => RemoteSendError::ReplyTimeout,
+            SendError::TooLarge(_) => RemoteSendError::TooLarge,
+            SendError::Overloaded(_) => RemoteSendError::Overloaded,
         }
     }
 }
@@ -487,6 +669,9 @@ where
             RemoteSendError::ConnectionClosed => write!(f, "connection closed"),
             RemoteSendError::Io(Some(err)) => err.fmt(f),
             RemoteSendError::Io(None) => write!(f, "io error"),
+            RemoteSendError::Disconnected => write!(f, "disconnected"),
+            RemoteSendError::TooLarge => write!(f, "message too large"),
+            RemoteSendError::Overloaded => write!(f, "actor overloaded"),
         }
     }
 }
@@ -499,8 +684,11 @@ impl<E> error::Error for RemoteSendError<E> where E: fmt::Debug + fmt::Display {
 pub enum ActorStopReason {
     /// Actor stopped normally.
     Normal,
-    /// Actor was killed.
+    /// Actor was killed with [`ActorRef::kill`](crate::actor::ActorRef::kill), after running its cleanup.
     Killed,
+    /// Actor was killed with [`ActorRef::kill_immediate`](crate::actor::ActorRef::kill_immediate),
+    /// without running [`Actor::on_stop`].
+    KilledImmediately,
     /// Actor panicked.
     Panicked(PanicError),
     /// Link died.
@@ -510,6 +698,56 @@ pub enum ActorStopReason {
         /// Actor died reason.
         reason: Box<ActorStopReason>,
     },
+    /// Actor stopped with an application specific reason.
+    ///
+    /// Created with [`ActorStopReason::custom`], and usually given to [`ActorRef::stop_with_reason`](crate::actor::ActorRef::stop_with_reason).
+    /// The inner value can be retrieved with [`ActorStopReason::downcast_custom_ref`].
+    Custom(Arc<dyn Any + Send + Sync>),
+}
+
+impl ActorStopReason {
+    /// Creates a custom stop reason from an application specific value.
+    pub fn custom<T>(reason: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        ActorStopReason::Custom(Arc::new(reason))
+    }
+
+    /// Creates a reason for stopping because the linked actor `id` stopped with `reason`.
+    pub fn link_died(id: ActorID, reason: ActorStopReason) -> Self {
+        ActorStopReason::LinkDied {
+            id,
+            reason: Box::new(reason),
+        }
+    }
+
+    /// Returns the reason at the end of a chain of [`LinkDied`](ActorStopReason::LinkDied) reasons,
+    /// which is the reason the first actor in the chain stopped with.
+    ///
+    /// Returns `self` if this is not a `LinkDied` reason.
+    pub fn root_cause(&self) -> &ActorStopReason {
+        match self {
+            ActorStopReason::LinkDied { reason, .. } => reason.root_cause(),
+            reason => reason,
+        }
+    }
+
+    /// Returns a reference to the custom reason downcasted into `T`,
+    /// or `None` if this is not a custom reason of type `T`.
+    ///
+    /// For [`ActorStopReason::LinkDied`], the reason of the linked actor is checked instead,
+    /// allowing supervisors to inspect why a linked actor stopped.
+    pub fn downcast_custom_ref<T>(&self) -> Option<&T>
+    where
+        T: 'static,
+    {
+        match self {
+            ActorStopReason::Custom(reason) => reason.downcast_ref(),
+            ActorStopReason::LinkDied { reason, .. } => reason.downcast_custom_ref(),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Debug for ActorStopReason {
@@ -517,12 +755,14 @@ impl fmt::Debug for ActorStopReason {
         match self {
             ActorStopReason::Normal => write!(f, "Normal"),
             ActorStopReason::Killed => write!(f, "Killed"),
+            ActorStopReason::KilledImmediately => write!(f, "KilledImmediately"),
             ActorStopReason::Panicked(_) => write!(f, "Panicked"),
             ActorStopReason::LinkDied { id, reason } => f
                 .debug_struct("LinkDied")
                 .field("id", id)
                 .field("reason", &reason)
                 .finish(),
+            ActorStopReason::Custom(_) => write!(f, "Custom"),
         }
     }
 }
@@ -532,10 +772,12 @@ impl fmt::Display for ActorStopReason {
         match self {
             ActorStopReason::Normal => write!(f, "actor stopped normally"),
             ActorStopReason::Killed => write!(f, "actor was killed"),
+            ActorStopReason::KilledImmediately => write!(f, "actor was killed immediately"),
             ActorStopReason::Panicked(err) => err.fmt(f),
             ActorStopReason::LinkDied { id, reason: _ } => {
                 write!(f, "link {id} died")
             }
+            ActorStopReason::Custom(_) => write!(f, "actor stopped with a custom reason"),
         }
     }
 }
@@ -543,7 +785,11 @@ impl fmt::Display for ActorStopReason {
 /// A shared error that occurs when an actor panics or returns an error from a hook in the [Actor] trait.
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
-pub struct PanicError(Arc<Mutex<Box<dyn Any + Send>>>);
+pub struct PanicError(
+    Arc<Mutex<Box<dyn Any + Send>>>,
+    Option<Arc<PanicLocation>>,
+    #[cfg(feature = "message-trace")] Option<Arc<[crate::actor::RecordedMessage]>>,
+);
 
 impl PanicError {
     /// Creates a new PanicError from a generic error.
@@ -551,12 +797,22 @@ impl PanicError {
     where
         E: Send + 'static,
     {
-        PanicError(Arc::new(Mutex::new(Box::new(err))))
+        PanicError(
+            Arc::new(Mutex::new(Box::new(err))),
+            None,
+            #[cfg(feature = "message-trace")]
+            None,
+        )
     }
 
     /// Creates a new PanicError from a generic boxed error.
     pub fn new_boxed(err: Box<dyn Any + Send>) -> Self {
-        PanicError(Arc::new(Mutex::new(err)))
+        PanicError(
+            Arc::new(Mutex::new(err)),
+            None,
+            #[cfg(feature = "message-trace")]
+            None,
+        )
     }
 
     /// Calls the passed closure `f` with an option containing the boxed any type downcasted into a `Cow<'static, str>`,
@@ -597,6 +853,124 @@ impl PanicError {
         let lock = self.0.lock()?;
         Ok(f(&lock))
     }
+
+    /// Returns the panic message, if the actor panicked with a string or a hook returned a [`BoxError`].
+    ///
+    /// This is useful for supervisors which decide how to handle a linked actor's death based on why it panicked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::error::PanicError;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct ConnectionLost;
+    ///
+    /// let err = PanicError::new("out of memory");
+    /// assert_eq!(err.message().as_deref(), Some("out of memory"));
+    ///
+    /// let err = PanicError::new(ConnectionLost);
+    /// assert!(err.message().is_none());
+    /// assert!(err.is::<ConnectionLost>());
+    /// assert_eq!(err.downcast::<ConnectionLost>(), Some(ConnectionLost));
+    /// ```
+    pub fn message(&self) -> Option<String> {
+        let any = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        any.downcast_ref::<&'static str>()
+            .map(|s| s.to_string())
+            .or_else(|| any.downcast_ref::<String>().cloned())
+            .or_else(|| any.downcast_ref::<BoxError>().map(|err| err.to_string()))
+    }
+
+    /// Returns `true` if the panic payload is of type `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is::<T>()
+    }
+
+    /// Returns a clone of the panic payload if it is of type `T`, otherwise `None`.
+    ///
+    /// The payload is shared between clones of the error, so it is cloned rather than moved out.
+    pub fn downcast<T: Clone + 'static>(&self) -> Option<T> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    /// Returns where the actor panicked, if it was caught while running one of its hooks or message handlers.
+    ///
+    /// The location is recorded by a panic hook which kameo installs when the first actor starts, wrapping any hook
+    /// which was already set, and which only records panics on threads currently running an actor. It's `None` for
+    /// errors returned by hooks or handlers, for panics raised with [`std::panic::resume_unwind`], and if the panic hook
+    /// has since been replaced.
+    ///
+    /// The hook is process wide and stays installed for the life of the process, since a panic hook can't be
+    /// swapped safely while other threads may be panicking. A hook set with [`std::panic::set_hook`] before the first
+    /// actor starts keeps being called. One set afterwards replaces kameo's hook, so locations are no longer
+    /// recorded, unless it calls the hook returned by [`std::panic::take_hook`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct MyActor;
+    ///
+    /// struct Crash;
+    ///
+    /// impl Message<Crash> for MyActor {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Crash, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         panic!("oh no");
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared = kameo::actor::prepare(MyActor);
+    /// let actor_ref = prepared.actor_ref_owned();
+    /// let handle = prepared.spawn();
+    /// let _ = actor_ref.tell(Crash).await;
+    ///
+    /// let (_, reason) = handle.await?;
+    /// let kameo::error::ActorStopReason::Panicked(err) = reason else {
+    ///     panic!("actor didn't panic");
+    /// };
+    /// let location = err.location().expect("panicked in a message handler");
+    /// assert!(location.file().ends_with(".rs"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn location(&self) -> Option<&PanicLocation> {
+        self.1.as_deref()
+    }
+
+    pub(crate) fn with_location(mut self, location: Option<PanicLocation>) -> Self {
+        self.1 = location.map(Arc::new);
+        self
+    }
+
+    /// Returns the most recent messages received by the actor before it panicked, oldest first.
+    ///
+    /// This is `None` if the panic did not occur while handling a message, such as in a lifecycle hook.
+    #[cfg(feature = "message-trace")]
+    pub fn recent_messages(&self) -> Option<&[crate::actor::RecordedMessage]> {
+        self.2.as_deref()
+    }
+
+    #[cfg(feature = "message-trace")]
+    pub(crate) fn with_recent_messages(
+        mut self,
+        recent_messages: Vec<crate::actor::RecordedMessage>,
+    ) -> Self {
+        self.2 = Some(recent_messages.into());
+        self
+    }
 }
 
 impl fmt::Display for PanicError {
@@ -630,6 +1004,155 @@ impl fmt::Display for PanicError {
     }
 }
 
+/// The source location where an actor panicked, see [`PanicError::location`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PanicLocation {
+    file: String,
+    line: u32,
+    column: u32,
+}
+
+impl PanicLocation {
+    /// Returns the name of the source file the panic originated from.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Returns the line number the panic originated from.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column the panic originated from.
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl From<&panic::Location<'_>> for PanicLocation {
+    fn from(location: &panic::Location<'_>) -> Self {
+        PanicLocation {
+            file: location.file().to_string(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+impl fmt::Display for PanicLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// An error returned by an [`Fsm`](crate::actor::fsm::Fsm) when a message is not allowed in its current state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidStateError<S> {
+    state: S,
+    message: &'static str,
+}
+
+impl<S> InvalidStateError<S> {
+    pub(crate) fn new(state: S, message: &'static str) -> Self {
+        InvalidStateError { state, message }
+    }
+
+    /// Returns the state the message was rejected in.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns the type name of the rejected message.
+    pub fn message(&self) -> &'static str {
+        self.message
+    }
+}
+
+impl<S: fmt::Debug> fmt::Display for InvalidStateError<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "message {} not allowed in state {:?}",
+            self.message, self.state
+        )
+    }
+}
+
+impl<S: fmt::Debug> error::Error for InvalidStateError<S> {}
+
+/// An error returned by a [`scope`](crate::actor::scope) when one or more of its child actors panicked.
+#[derive(Clone)]
+pub struct ScopeError {
+    panicked: Vec<(ActorID, PanicError)>,
+}
+
+impl ScopeError {
+    pub(crate) fn new(panicked: Vec<(ActorID, PanicError)>) -> Self {
+        ScopeError { panicked }
+    }
+
+    /// Returns the IDs of the child actors which panicked, along with their panic errors.
+    pub fn panicked(&self) -> &[(ActorID, PanicError)] {
+        &self.panicked
+    }
+}
+
+impl fmt::Debug for ScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopeError")
+            .field(
+                "panicked",
+                &self.panicked.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl fmt::Display for ScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.panicked.as_slice() {
+            [(id, err)] => write!(f, "scoped actor {id} {err}"),
+            panicked => write!(f, "{} scoped actors panicked", panicked.len()),
+        }
+    }
+}
+
+impl error::Error for ScopeError {}
+
+/// Error that can occur when spawning an actor with [`try_spawn_limited`](crate::actor::try_spawn_limited).
+pub enum SpawnError<A = ()> {
+    /// The actor's category has reached its limit of alive actors, see
+    /// [`set_spawn_limit`](crate::actor::set_spawn_limit). The actor is returned without being spawned.
+    LimitReached(A),
+}
+
+impl<A> SpawnError<A> {
+    /// Clears the inner actor back to `()`.
+    pub fn reset(self) -> SpawnError {
+        match self {
+            SpawnError::LimitReached(_) => SpawnError::LimitReached(()),
+        }
+    }
+}
+
+impl<A> fmt::Debug for SpawnError<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::LimitReached(_) => write!(f, "LimitReached"),
+        }
+    }
+}
+
+impl<A> fmt::Display for SpawnError<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::LimitReached(_) => write!(f, "spawn limit reached"),
+        }
+    }
+}
+
+impl<A> error::Error for SpawnError<A> {}
+
 /// Errors that can occur when deserializing an `ActorID` from bytes.
 #[derive(Debug)]
 pub enum ActorIDFromBytesError {
@@ -659,6 +1182,38 @@ impl fmt::Display for ActorIDFromBytesError {
 
 impl error::Error for ActorIDFromBytesError {}
 
+/// Errors that can occur when parsing an `ActorID` from a string.
+#[derive(Debug)]
+pub enum ParseActorIDError {
+    /// The string is not in the format `ActorID(<sequence_id>)` or `ActorID(<sequence_id>, <peer_id>)`.
+    InvalidFormat,
+    /// The `sequence_id` is not a valid integer.
+    InvalidSequenceID(num::ParseIntError),
+    /// An error occurred while parsing the `PeerId`.
+    #[cfg(feature = "remote")]
+    ParsePeerID(libp2p_identity::ParseError),
+}
+
+#[cfg(feature = "remote")]
+impl From<libp2p_identity::ParseError> for ParseActorIDError {
+    fn from(err: libp2p_identity::ParseError) -> Self {
+        ParseActorIDError::ParsePeerID(err)
+    }
+}
+
+impl fmt::Display for ParseActorIDError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseActorIDError::InvalidFormat => write!(f, "invalid actor id format"),
+            ParseActorIDError::InvalidSequenceID(err) => write!(f, "invalid sequence id: {err}"),
+            #[cfg(feature = "remote")]
+            ParseActorIDError::ParsePeerID(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for ParseActorIDError {}
+
 /// An infallible error type, similar to [std::convert::Infallible].
 ///
 /// Kameo provides its own Infallible type in order to implement Serialize/Deserialize for it.
@@ -667,7 +1222,7 @@ pub enum Infallible {}
 
 impl Clone for Infallible {
     fn clone(&self) -> Infallible {
-        match *self {}
+        *self
     }
 }
 
@@ -698,8 +1253,8 @@ impl PartialEq for Infallible {
 impl Eq for Infallible {}
 
 impl PartialOrd for Infallible {
-    fn partial_cmp(&self, _other: &Self) -> Option<cmp::Ordering> {
-        match *self {}
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
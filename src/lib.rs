@@ -14,7 +14,12 @@ pub mod message;
 pub mod remote;
 pub mod reply;
 pub mod request;
+pub mod test;
 
-pub use actor::{spawn, Actor};
+pub use actor::{
+    join, link_team, pipe_into, scope, shutdown_all, spawn, spawn_fn, spawn_many, supervise_task,
+    Actor,
+};
 pub use kameo_macros::{messages, remote_message, Actor, RemoteActor, Reply};
 pub use reply::Reply;
+pub use request::{gather, race};
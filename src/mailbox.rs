@@ -1,18 +1,60 @@
 //! Behaviour for actor mailboxes.
 //!
 //! An actor mailbox is a channel which stores pending messages and signals for an actor to process sequentially.
+//!
+//! # Custom mailboxes
+//!
+//! Besides the mailboxes in this module, an actor can use any type implementing [`Mailbox`] by setting it as its
+//! [`Actor::Mailbox`], and creating it in [`Actor::new_mailbox`]. The mailbox is the sending half, cloned into every
+//! [`ActorRef`], and its [`Receiver`](Mailbox::Receiver) is owned by the actor's task.
+//!
+//! Mailboxes carry [`Signal`]s, which should be treated as opaque and passed through unchanged. A mailbox may
+//! inspect a signal to decide where to queue it, such as classifying messages by their
+//! [`lane`](crate::message::DynMessage::lane), but must uphold the following contract:
+//!
+//! - **Sending**: [`send`](Mailbox::send) queues the signal, waiting for capacity if the mailbox is bounded.
+//!   [`try_send`](Mailbox::try_send) fails with [`SendError::MailboxFull`] instead of waiting, and
+//!   [`blocking_send`](Mailbox::blocking_send) blocks the thread. Once the receiver has been dropped, every send
+//!   fails with [`SendError::ActorNotRunning`], returning the signal.
+//! - **Receiving**: [`recv`](MailboxReceiver::recv) waits for the next signal, and only returns `None` once every
+//!   strong mailbox has been dropped and nothing is left queued, which stops the actor.
+//!   [`try_recv`](MailboxReceiver::try_recv) must never wait, since it's used to drain the mailbox when the actor
+//!   stops.
+//! - **Stopping**: [`Signal::Stop`] must be received after every message sent before it, so that stopping gracefully
+//!   processes the messages already queued. Messages sent after it may be dropped.
+//! - **Linking**: the [`SignalMailbox`] methods are used by linked actors, and should succeed even while the mailbox
+//!   is full, for example by queueing them on a separate unbounded channel.
+//! - **Closing**: there is no explicit close. The mailbox is closed when its receiver is dropped, after which
+//!   [`is_closed`](Mailbox::is_closed) returns `true` and [`closed`](Mailbox::closed) resolves.
+//! - **Counting**: [`len`](Mailbox::len) is a best effort count of queued signals, used for stats and load shedding.
+//!   It defaults to `0`, so a mailbox which doesn't count its signals never sheds load based on its length.
+//!   [`strong_count`](Mailbox::strong_count) counts the clones of the mailbox, excluding weak mailboxes.
+//!
+//! The mailbox doesn't need to delay messages while the actor is starting up, or order
+//! [`Signal::StartupFinished`] relative to messages, since messages received before it are buffered by the actor.
+//!
+//! See the `custom_mailbox` example for a complete implementation.
 
 pub mod bounded;
+pub mod stack;
 pub mod unbounded;
+pub mod weighted;
+
+use std::{
+    fmt,
+    sync::{atomic::Ordering, Arc, OnceLock},
+    time::{Duration, Instant},
+};
 
 use dyn_clone::DynClone;
-use futures::{future::BoxFuture, Future};
+use futures::{future::BoxFuture, Future, FutureExt};
+use tokio::sync::oneshot;
 
 use crate::{
-    actor::{ActorID, ActorRef},
+    actor::{ActorID, ActorRef, StateWaiter},
     error::{ActorStopReason, SendError},
-    message::DynMessage,
-    reply::BoxReplySender,
+    message::{DynMessage, DynReadMessage},
+    reply::{BoxReplySender, ReplyOptions},
     Actor,
 };
 
@@ -44,12 +86,30 @@ pub trait Mailbox<A: Actor>: SignalMailbox + Clone + Send + Sync {
     fn strong_count(&self) -> usize;
     /// Returns the number of weak mailboxes.
     fn weak_count(&self) -> usize;
+    /// Returns the number of signals currently queued in the mailbox.
+    ///
+    /// Defaults to `0` for mailboxes which can't report their length, so
+    /// [`ActorRef::mailbox_len`](crate::actor::ActorRef::mailbox_len) always reports them as empty.
+    fn len(&self) -> usize {
+        0
+    }
+    /// Returns `true` if there are no signals queued in the mailbox.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 /// A mailbox receiver.
 pub trait MailboxReceiver<A: Actor>: Send + 'static {
     /// Receives a value from the mailbox.
     fn recv(&mut self) -> impl Future<Output = Option<Signal<A>>> + Send + '_;
+
+    /// Receives a value from the mailbox if one is immediately available, without waiting.
+    ///
+    /// This is used to drain the mailbox when the actor stops.
+    fn try_recv(&mut self) -> Option<Signal<A>> {
+        self.recv().now_or_never().flatten()
+    }
 }
 
 /// A weak mailbox which can be upraded.
@@ -65,44 +125,547 @@ pub trait WeakMailbox: SignalMailbox + Clone + Send + Sync {
     fn weak_count(&self) -> usize;
 }
 
+/// A message or control signal queued in an actor's mailbox.
+///
+/// Signals are created by kameo and consumed by the actor's message loop. Custom mailboxes should pass them through
+/// unchanged, see the [module docs](self#custom-mailboxes) for the contract they must uphold.
 #[allow(missing_debug_implementations)]
-#[doc(hidden)]
 pub enum Signal<A: Actor> {
+    /// The actor has finished running [`on_start`](Actor::on_start).
     StartupFinished,
+    /// A message sent with `tell` or `ask`.
     Message {
+        /// The message to handle.
         message: Box<dyn DynMessage<A>>,
+        /// A reference to the actor, passed to the handler's context.
+        actor_ref: ActorRef<A>,
+        /// The sender of the reply, if the message was sent with `ask`.
+        reply: Option<BoxReplySender>,
+        /// Whether the message was sent by the actor to itself.
+        sent_within_actor: bool,
+        /// Options set by the sender, such as its deadline.
+        options: Option<Box<MessageOptions>>,
+    },
+    /// A read-only message sent with [`ActorRef::ask_read`], which can be handled concurrently with other reads.
+    Read {
+        /// The message to handle.
+        message: Box<dyn DynReadMessage<A>>,
+        /// A reference to the actor, passed to the handler's context.
         actor_ref: ActorRef<A>,
+        /// The sender of the reply.
         reply: Option<BoxReplySender>,
+        /// Whether the message was sent by the actor to itself.
         sent_within_actor: bool,
+        /// Options set by the sender, such as its deadline.
+        options: Option<Box<MessageOptions>>,
     },
+    /// A linked actor has stopped.
     LinkDied {
+        /// The id of the linked actor.
         id: ActorID,
+        /// The reason the linked actor stopped.
         reason: ActorStopReason,
     },
-    Stop,
+    /// The actor should move to another runtime, see [`ActorRef::migrate_to`].
+    Migrate {
+        /// The runtime to move to.
+        handle: tokio::runtime::Handle,
+        /// Notified once the actor has been moved.
+        migrated: oneshot::Sender<()>,
+    },
+    /// A caller is waiting for the actor's state to match a predicate, see [`ActorRef::await_state`].
+    AwaitState {
+        /// The waiting caller.
+        waiter: StateWaiter<A>,
+    },
+    /// A caller is asking for a summary of the actor's state, see [`ActorRef::describe`].
+    Describe {
+        /// Receives the actor's [`describe`](Actor::describe) summary.
+        reply: oneshot::Sender<String>,
+    },
+    /// The actor should stop gracefully once the messages queued before this signal have been processed.
+    Stop {
+        /// The reason to stop with, or `None` for [`ActorStopReason::Normal`].
+        reason: Option<ActorStopReason>,
+    },
 }
 
 impl<A: Actor> Signal<A> {
+    /// Returns `true` if the signal is a [`Message`](Signal::Message) or [`Read`](Signal::Read),
+    /// rather than a control signal.
+    pub fn is_message(&self) -> bool {
+        matches!(self, Signal::Message { .. } | Signal::Read { .. })
+    }
+
+    /// Drops a message which a mailbox discarded rather than queueing, such as when it's full.
+    ///
+    /// The drop is reported to the actor's [`on_message_dropped`](Actor::on_message_dropped) hook, and the caller
+    /// of an `ask` request is sent [`SendError::ActorStopped`] as the reply is dropped. Control signals should never
+    /// be discarded, and are dropped without being reported.
+    pub fn discard(self) {
+        if let Signal::Message { actor_ref, .. } | Signal::Read { actor_ref, .. } = self {
+            actor_ref.counters.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns whether the signal is a message sent by the actor to itself.
+    pub(crate) fn is_sent_within_actor(&self) -> bool {
+        matches!(
+            self,
+            Signal::Message {
+                sent_within_actor: true,
+                ..
+            } | Signal::Read {
+                sent_within_actor: true,
+                ..
+            }
+        )
+    }
+
+    /// Returns whether the signal is a message exceeding the actor's [`max_message_size`](Actor::max_message_size).
+    pub(crate) fn is_too_large(&self) -> bool {
+        match (self, A::max_message_size()) {
+            (Signal::Message { message, .. }, Some(max)) => (**message).size_hint() > max,
+            (Signal::Read { message, .. }, Some(max)) => (**message).size_hint() > max,
+            _ => false,
+        }
+    }
+
+    /// Returns whether the signal is a message which should be rejected, since the actor is shedding load.
+    ///
+    /// See [`Actor::overload_config`].
+    pub(crate) fn is_overloaded(&self) -> bool {
+        match (self, A::overload_config()) {
+            (
+                Signal::Message {
+                    actor_ref,
+                    sent_within_actor: false,
+                    ..
+                }
+                | Signal::Read {
+                    actor_ref,
+                    sent_within_actor: false,
+                    ..
+                },
+                Some(config),
+            ) => actor_ref
+                .counters
+                .overload
+                .should_shed(actor_ref.mailbox().len(), &config),
+            _ => false,
+        }
+    }
+
+    /// Shortens the message's deadline to the reply timeout, if it is sooner than an inherited deadline.
+    pub(crate) fn with_reply_timeout(self, reply_timeout: Duration) -> Self {
+        self.with_deadline(tokio::time::Instant::now().into_std() + reply_timeout)
+    }
+
+    /// Sets the deadline of the message, unless it already has a sooner one.
+    pub(crate) fn with_deadline(mut self, deadline: Instant) -> Self {
+        if let Signal::Message { options, .. } = &mut self {
+            let options = options.get_or_insert_with(Box::default);
+            options.deadline = Some(options.deadline.map_or(deadline, |d| d.min(deadline)));
+        }
+        self
+    }
+
+    /// Sets the reply options of the message.
+    pub(crate) fn with_reply_options(mut self, reply_options: ReplyOptions) -> Self {
+        if let Signal::Message { options, .. } = &mut self {
+            options.get_or_insert_with(Box::default).reply = reply_options;
+        }
+        self
+    }
+
+    /// Marks the message to be handled without waiting for the actor to finish starting up.
+    pub(crate) fn with_skip_startup_gate(mut self) -> Self {
+        if let Signal::Message { options, .. } = &mut self {
+            options.get_or_insert_with(Box::default).skip_startup_gate = true;
+        }
+        self
+    }
+
+    /// Records when the actor starts handling the message in `handling_started`.
+    pub(crate) fn with_timing(
+        mut self,
+        handling_started: Arc<OnceLock<tokio::time::Instant>>,
+    ) -> Self {
+        if let Signal::Message { options, .. } = &mut self {
+            options.get_or_insert_with(Box::default).handling_started = Some(handling_started);
+        }
+        self
+    }
+
     pub(crate) fn downcast_message<M>(self) -> Option<M>
     where
         M: 'static,
     {
         match self {
             Signal::Message { message, .. } => message.as_any().downcast().ok().map(|v| *v),
+            Signal::Read { message, .. } => message.as_any().downcast().ok().map(|v| *v),
             _ => None,
         }
     }
 }
 
+/// A message which was still queued in an actor's mailbox when it stopped, passed to [`Actor::on_undelivered`].
+///
+/// If the message was sent with `ask`, the caller is sent [`SendError::ActorStopped`] once this is dropped,
+/// unless the message is handed off to another instance of the actor with [`redeliver`](UndeliveredMessage::redeliver).
+///
+/// This includes read messages sent with [`ActorRef::ask_read`](crate::actor::ActorRef::ask_read), which are
+/// redelivered as read messages.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::ActorRef;
+/// use kameo::mailbox::{unbounded::UnboundedMailbox, UndeliveredMessage};
+/// use kameo::message::{Context, Message, ReadMessage};
+/// use kameo::Actor;
+/// use tokio::sync::oneshot;
+///
+/// struct Cache {
+///     value: u32,
+///     busy: Option<oneshot::Sender<()>>,
+///     successor: Option<ActorRef<Cache>>,
+/// }
+///
+/// impl Actor for Cache {
+///     type Mailbox = UnboundedMailbox<Self>;
+///
+///     async fn on_undelivered(&mut self, msg: UndeliveredMessage<Self>) {
+///         if let Some(successor) = &self.successor {
+///             let _ = msg.redeliver(successor).await;
+///         }
+///     }
+/// }
+///
+/// struct Block;
+///
+/// impl Message<Block> for Cache {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, _: Block, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         let _ = self.busy.take().unwrap().send(());
+///         std::future::pending().await // A job which never finishes
+///     }
+/// }
+///
+/// struct Get;
+///
+/// impl ReadMessage<Get> for Cache {
+///     type Reply = u32;
+///
+///     async fn handle(&self, _: Get, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.value
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let successor = kameo::spawn(Cache { value: 2, busy: None, successor: None });
+/// let (busy_tx, busy_rx) = oneshot::channel();
+/// let cache = kameo::spawn(Cache {
+///     value: 1,
+///     busy: Some(busy_tx),
+///     successor: Some(successor),
+/// });
+///
+/// cache.tell(Block).await?;
+/// let value = tokio::spawn({
+///     let cache = cache.clone();
+///     async move { cache.ask_read(Get).await }
+/// });
+/// busy_rx.await?;
+/// # while cache.mailbox_len() == 0 { tokio::task::yield_now().await; }
+/// cache.kill();
+/// assert_eq!(value.await??, 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct UndeliveredMessage<A: Actor> {
+    // Only `None` once taken by a method consuming the message
+    message: Option<UndeliveredKind<A>>,
+    reply: Option<BoxReplySender>,
+    sent_within_actor: bool,
+    options: Option<Box<MessageOptions>>,
+}
+
+/// Whether an [`UndeliveredMessage`] is handled with exclusive or shared access to the actor.
+enum UndeliveredKind<A: Actor> {
+    Message(Box<dyn DynMessage<A>>),
+    Read(Box<dyn DynReadMessage<A>>),
+}
+
+impl<A: Actor> UndeliveredMessage<A> {
+    /// Returns the type name of the message.
+    pub fn type_name(&self) -> &'static str {
+        match self.message.as_ref().unwrap() {
+            UndeliveredKind::Message(message) => (**message).type_name(),
+            UndeliveredKind::Read(message) => (**message).type_name(),
+        }
+    }
+
+    /// Returns whether the message is a [`ReadMessage`](crate::message::ReadMessage), handled with shared access
+    /// to the actor.
+    pub fn is_read(&self) -> bool {
+        matches!(self.message, Some(UndeliveredKind::Read(_)))
+    }
+
+    /// Returns whether the message was sent with `ask`, rather than `tell`.
+    pub fn is_ask(&self) -> bool {
+        self.reply.is_some()
+    }
+
+    /// Returns whether the message was sent by the actor to itself.
+    pub fn is_sent_within_actor(&self) -> bool {
+        self.sent_within_actor
+    }
+
+    /// Downcasts the message to its concrete type, such as to persist it or send it to another actor.
+    pub fn downcast<M: 'static>(mut self) -> Option<M> {
+        let message = match self.message.take().unwrap() {
+            UndeliveredKind::Message(message) => message.as_any(),
+            UndeliveredKind::Read(message) => message.as_any(),
+        };
+        message.downcast().ok().map(|msg| *msg)
+    }
+
+    /// Returns the message, or `None` if it's a read message, see [`is_read`](UndeliveredMessage::is_read).
+    pub fn into_message(mut self) -> Option<Box<dyn DynMessage<A>>> {
+        match self.message.take().unwrap() {
+            UndeliveredKind::Message(message) => Some(message),
+            UndeliveredKind::Read(_) => None,
+        }
+    }
+
+    /// Hands the message off to another instance of the actor, such as one restarted in place of the stopped actor.
+    ///
+    /// The message is queued in the other actor's mailbox along with its reply channel, deadline and sender,
+    /// so callers of an `ask` request receive the reply from the new instance rather than an error.
+    /// If the other actor is not running or its mailbox is full, the message is returned in the error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ActorRef;
+    /// use kameo::mailbox::{unbounded::UnboundedMailbox, UndeliveredMessage};
+    /// use kameo::message::{Context, Message};
+    /// use kameo::Actor;
+    /// use tokio::sync::oneshot;
+    ///
+    /// struct Worker {
+    ///     name: &'static str,
+    ///     busy: Option<oneshot::Sender<()>>,
+    ///     successor: Option<ActorRef<Worker>>,
+    /// }
+    ///
+    /// impl Actor for Worker {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_undelivered(&mut self, msg: UndeliveredMessage<Self>) {
+    ///         if let Some(successor) = &self.successor {
+    ///             let _ = msg.redeliver(successor).await;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct Block;
+    ///
+    /// impl Message<Block> for Worker {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Block, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let _ = self.busy.take().unwrap().send(());
+    ///         std::future::pending().await // A job which never finishes
+    ///     }
+    /// }
+    ///
+    /// struct Name;
+    ///
+    /// impl Message<Name> for Worker {
+    ///     type Reply = &'static str;
+    ///
+    ///     async fn handle(&mut self, _: Name, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.name
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let successor = kameo::spawn(Worker { name: "successor", busy: None, successor: None });
+    /// let (busy_tx, busy_rx) = oneshot::channel();
+    /// let worker = kameo::spawn(Worker {
+    ///     name: "worker",
+    ///     busy: Some(busy_tx),
+    ///     successor: Some(successor),
+    /// });
+    ///
+    /// worker.tell(Block).await?;
+    /// let name = tokio::spawn({
+    ///     let worker = worker.clone();
+    ///     async move { worker.ask(Name).await }
+    /// });
+    /// busy_rx.await?;
+    /// # while worker.mailbox_len() == 0 { tokio::task::yield_now().await; }
+    /// worker.kill();
+    /// assert_eq!(name.await??, "successor");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn redeliver(mut self, actor_ref: &ActorRef<A>) -> Result<(), SendError<Self>> {
+        let signal = match self.message.take().unwrap() {
+            UndeliveredKind::Message(message) => Signal::Message {
+                message,
+                actor_ref: actor_ref.clone(),
+                reply: self.reply.take(),
+                sent_within_actor: false,
+                options: self.options.take(),
+            },
+            UndeliveredKind::Read(message) => Signal::Read {
+                message,
+                actor_ref: actor_ref.clone(),
+                reply: self.reply.take(),
+                sent_within_actor: false,
+                options: self.options.take(),
+            },
+        };
+        actor_ref.mailbox().send(signal).await.map_err(|err| {
+            err.map_msg(|signal| {
+                let mut msg = UndeliveredMessage::from_signal(signal).unwrap();
+                msg.sent_within_actor = self.sent_within_actor;
+                msg
+            })
+        })
+    }
+
+    /// Converts a signal into an undelivered message.
+    ///
+    /// Returns `None` if the signal is not a message.
+    pub(crate) fn from_signal(signal: Signal<A>) -> Option<Self> {
+        let (message, reply, sent_within_actor, options) = match signal {
+            Signal::Message {
+                message,
+                reply,
+                sent_within_actor,
+                options,
+                ..
+            } => (
+                UndeliveredKind::Message(message),
+                reply,
+                sent_within_actor,
+                options,
+            ),
+            Signal::Read {
+                message,
+                reply,
+                sent_within_actor,
+                options,
+                ..
+            } => (
+                UndeliveredKind::Read(message),
+                reply,
+                sent_within_actor,
+                options,
+            ),
+            _ => return None,
+        };
+        Some(UndeliveredMessage {
+            message: Some(message),
+            reply,
+            sent_within_actor,
+            options,
+        })
+    }
+
+    /// Converts a message stashed with [`Context::stash`](crate::message::Context::stash) into an undelivered message.
+    pub(crate) fn from_stashed(
+        message: Box<dyn DynMessage<A>>,
+        reply: Option<BoxReplySender>,
+        options: Option<Box<MessageOptions>>,
+    ) -> Self {
+        UndeliveredMessage {
+            message: Some(UndeliveredKind::Message(message)),
+            reply,
+            sent_within_actor: false,
+            options,
+        }
+    }
+}
+
+impl<A: Actor> Drop for UndeliveredMessage<A> {
+    fn drop(&mut self) {
+        if let Some(tx) = self.reply.take() {
+            let _ = tx.send(Err(SendError::ActorStopped));
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for UndeliveredMessage<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UndeliveredMessage")
+            .field("type_name", &self.type_name())
+            .field("is_ask", &self.is_ask())
+            .field("sent_within_actor", &self.sent_within_actor)
+            .finish()
+    }
+}
+
+/// Options set by the sender of a message.
+///
+/// These are boxed in signals to keep them small, as most messages have none.
+#[derive(Clone, Debug, Default)]
 #[doc(hidden)]
+pub struct MessageOptions {
+    pub(crate) deadline: Option<Instant>,
+    pub(crate) reply: ReplyOptions,
+    pub(crate) sender_id: Option<ActorID>,
+    pub(crate) skip_startup_gate: bool,
+    /// Set to when the actor started handling the message, for asks sent with
+    /// [`with_timing`](crate::request::AskRequest::with_timing).
+    pub(crate) handling_started: Option<Arc<OnceLock<tokio::time::Instant>>>,
+}
+
+impl MessageOptions {
+    /// Boxes the options, or returns `None` if they are all the defaults.
+    pub(crate) fn boxed(
+        deadline: Option<Instant>,
+        reply: ReplyOptions,
+        sender_id: Option<ActorID>,
+    ) -> Option<Box<Self>> {
+        (deadline.is_some() || reply != ReplyOptions::default() || sender_id.is_some()).then(|| {
+            Box::new(MessageOptions {
+                deadline,
+                reply,
+                sender_id,
+                skip_startup_gate: false,
+                handling_started: None,
+            })
+        })
+    }
+}
+
+/// Sends control signals to a mailbox without knowing its actor type.
+///
+/// This is used by links, which can hold mailboxes of many actor types. Weak mailboxes implement it too,
+/// failing with [`SendError::ActorNotRunning`] once they can no longer be upgraded.
 pub trait SignalMailbox: DynClone + Send {
+    /// Sends [`Signal::StartupFinished`].
     fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>>;
+    /// Sends [`Signal::LinkDied`], which should succeed even while the mailbox is full.
     fn signal_link_died(
         &self,
         id: ActorID,
         reason: ActorStopReason,
     ) -> BoxFuture<'_, Result<(), SendError>>;
+    /// Sends [`Signal::Stop`].
     fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>>;
+    /// Returns the number of signals currently queued in the mailbox.
+    ///
+    /// Defaults to `0`, like [`Mailbox::len`].
+    fn mailbox_len(&self) -> usize {
+        0
+    }
 }
 
 dyn_clone::clone_trait_object!(SignalMailbox);
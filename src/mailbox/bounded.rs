@@ -13,15 +13,164 @@ use crate::{
 
 use super::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox};
 
-/// An unbounded mailbox, where the sending messages to a full mailbox causes backpressure.
-pub struct BoundedMailbox<A: Actor>(pub(crate) mpsc::Sender<Signal<A>>);
+/// A bounded mailbox, where the sending messages to a full mailbox causes backpressure.
+///
+/// Only messages count towards the capacity. Signals such as stopping the actor or a linked actor dying
+/// are sent on a separate unbounded lane and processed before any queued messages, so a full mailbox never prevents
+/// the actor from being stopped. A graceful stop still waits for the messages queued when it is received to be processed.
+///
+/// An actor sending a message to itself cannot wait for capacity, since it would be waiting on its own
+/// mailbox to be drained. Such sends never wait, and fail with [`SendError::MailboxFull`] if the mailbox is full.
+///
+/// # Example
+///
+/// ```
+/// use kameo::{mailbox::bounded::BoundedMailbox, message::{Context, Message}, request::MessageSend, Actor};
+/// use kameo::error::SendError;
+///
+/// struct Looper;
+///
+/// impl Actor for Looper {
+///     type Mailbox = BoundedMailbox<Self>;
+///
+///     fn new_mailbox() -> (Self::Mailbox, <Self::Mailbox as kameo::mailbox::Mailbox<Self>>::Receiver) {
+///         BoundedMailbox::new(1)
+///     }
+/// }
+///
+/// struct Fill;
+///
+/// impl Message<Fill> for Looper {
+///     type Reply = bool;
+///
+///     async fn handle(&mut self, _: Fill, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         let actor_ref = ctx.actor_ref();
+///         actor_ref.tell(Noop).send().await.unwrap();
+///         // The mailbox is now full, so sending to ourselves again would deadlock.
+///         matches!(actor_ref.tell(Noop).send().await, Err(SendError::MailboxFull(_)))
+///     }
+/// }
+///
+/// struct Noop;
+///
+/// impl Message<Noop> for Looper {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, _: Noop, _: Context<'_, Self, Self::Reply>) -> Self::Reply {}
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Looper);
+/// actor_ref.wait_startup().await;
+/// assert!(actor_ref.ask(Fill).send().await?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+///
+/// Stopping an actor whose mailbox is full:
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::{mailbox::bounded::BoundedMailbox, message::{Context, Message}, Actor};
+/// use kameo::error::SendError;
+/// use kameo::request::TryMessageSendSync;
+///
+/// #[derive(Default)]
+/// struct Slow {
+///     handled: u32,
+/// }
+///
+/// impl Actor for Slow {
+///     type Mailbox = BoundedMailbox<Self>;
+///
+///     fn new_mailbox() -> (Self::Mailbox, <Self::Mailbox as kameo::mailbox::Mailbox<Self>>::Receiver) {
+///         BoundedMailbox::new(1)
+///     }
+/// }
+///
+/// struct Work;
+///
+/// impl Message<Work> for Slow {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, _: Work, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         tokio::time::sleep(Duration::from_millis(50)).await;
+///         self.handled += 1;
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let prepared = kameo::actor::prepare(Slow::default());
+/// let actor_ref = prepared.actor_ref().clone();
+/// let join_handle = prepared.spawn();
+/// actor_ref.wait_startup().await;
+///
+/// actor_ref.tell(Work).await?; // Handled straight away
+/// actor_ref.tell(Work).await?; // Fills the mailbox
+/// assert!(matches!(actor_ref.tell(Work).try_send_sync(), Err(SendError::MailboxFull(_))));
+///
+/// // The stop signal doesn't wait for capacity, and the queued message is still handled first
+/// tokio::time::timeout(Duration::from_millis(10), actor_ref.stop_gracefully()).await??;
+/// let (actor, _) = join_handle.await?;
+/// assert_eq!(actor.handled, 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct BoundedMailbox<A: Actor>(
+    pub(crate) mpsc::Sender<Signal<A>>,
+    mpsc::UnboundedSender<Signal<A>>,
+);
 
 impl<A: Actor> BoundedMailbox<A> {
     /// Creates a new bounded mailbox with a given capacity.
     #[inline]
     pub fn new(capacity: usize) -> (Self, BoundedMailboxReceiver<A>) {
         let (tx, rx) = mpsc::channel(capacity);
-        (BoundedMailbox(tx), BoundedMailboxReceiver(rx))
+        let (control_tx, control_rx) = mpsc::unbounded_channel();
+        (
+            BoundedMailbox(tx, control_tx),
+            BoundedMailboxReceiver {
+                rx,
+                control: control_rx,
+                stop_after: None,
+                stop_reason: None,
+            },
+        )
+    }
+
+    /// Sends a signal which isn't a message on the control lane, which is never full.
+    fn send_control(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        debug_assert!(!signal.is_message());
+        self.1.send(signal)
+    }
+
+    /// Sends a signal, waiting for capacity unless the actor is sending to itself.
+    #[inline]
+    pub(crate) async fn send_signal(
+        &self,
+        signal: Signal<A>,
+    ) -> Result<(), mpsc::error::TrySendError<Signal<A>>> {
+        if !signal.is_message() {
+            self.send_control(signal)
+                .map_err(|err| mpsc::error::TrySendError::Closed(err.0))
+        } else if signal.is_sent_within_actor() {
+            self.0.try_send(signal)
+        } else {
+            self.0
+                .send(signal)
+                .await
+                .map_err(|err| mpsc::error::TrySendError::Closed(err.0))
+        }
+    }
+}
+
+fn into_send_error<A: Actor, E>(
+    err: mpsc::error::TrySendError<Signal<A>>,
+) -> SendError<Signal<A>, E> {
+    match err {
+        mpsc::error::TrySendError::Full(signal) => SendError::MailboxFull(signal),
+        mpsc::error::TrySendError::Closed(signal) => SendError::ActorNotRunning(signal),
     }
 }
 
@@ -36,17 +185,32 @@ impl<A: Actor> Mailbox<A> for BoundedMailbox<A> {
 
     #[inline]
     async fn send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
-        Ok(self.0.send(signal).await?)
+        self.send_signal(signal).await.map_err(into_send_error)
     }
 
     #[inline]
     fn try_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
-        Ok(self.0.try_send(signal)?)
+        if !signal.is_message() {
+            return self
+                .send_control(signal)
+                .map_err(|err| SendError::ActorNotRunning(err.0));
+        }
+        self.0.try_send(signal).map_err(into_send_error)
     }
 
     #[inline]
     fn blocking_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
-        Ok(self.0.blocking_send(signal)?)
+        if !signal.is_message() {
+            return self
+                .send_control(signal)
+                .map_err(|err| SendError::ActorNotRunning(err.0));
+        }
+        if signal.is_sent_within_actor() {
+            return self.0.try_send(signal).map_err(into_send_error);
+        }
+        self.0
+            .blocking_send(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
     }
 
     #[inline]
@@ -61,7 +225,7 @@ impl<A: Actor> Mailbox<A> for BoundedMailbox<A> {
 
     #[inline]
     fn downgrade(&self) -> Self::WeakMailbox {
-        WeakBoundedMailbox(self.0.downgrade())
+        WeakBoundedMailbox(self.0.downgrade(), self.1.downgrade())
     }
 
     #[inline]
@@ -73,11 +237,16 @@ impl<A: Actor> Mailbox<A> for BoundedMailbox<A> {
     fn weak_count(&self) -> usize {
         self.0.weak_count()
     }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.max_capacity() - self.0.capacity()
+    }
 }
 
 impl<A: Actor> Clone for BoundedMailbox<A> {
     fn clone(&self) -> Self {
-        BoundedMailbox(self.0.clone())
+        BoundedMailbox(self.0.clone(), self.1.clone())
     }
 }
 
@@ -90,31 +259,80 @@ impl<A: Actor> fmt::Debug for BoundedMailbox<A> {
 }
 
 /// A bounded mailbox receiver.
-pub struct BoundedMailboxReceiver<A: Actor>(mpsc::Receiver<Signal<A>>);
+pub struct BoundedMailboxReceiver<A: Actor> {
+    rx: mpsc::Receiver<Signal<A>>,
+    control: mpsc::UnboundedReceiver<Signal<A>>,
+    /// The number of queued messages to process before stopping, once a stop signal has been received.
+    stop_after: Option<usize>,
+    /// The reason carried by the received stop signal.
+    stop_reason: Option<ActorStopReason>,
+}
 
 impl<A: Actor> MailboxReceiver<A> for BoundedMailboxReceiver<A> {
     async fn recv(&mut self) -> Option<Signal<A>> {
-        self.0.recv().await
+        if self.stop_after.is_none() {
+            tokio::select! {
+                biased;
+                Some(signal) = self.control.recv() => match signal {
+                    // Stop once the messages queued before the stop signal have been processed
+                    Signal::Stop { reason } => {
+                        self.stop_after = Some(self.rx.len());
+                        self.stop_reason = reason;
+                    }
+                    signal => return Some(signal),
+                },
+                signal = self.rx.recv() => return signal,
+            }
+        }
+
+        match &mut self.stop_after {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                match self.rx.try_recv() {
+                    Ok(signal) => Some(signal),
+                    Err(_) => {
+                        self.stop_after = None;
+                        Some(Signal::Stop {
+                            reason: self.stop_reason.take(),
+                        })
+                    }
+                }
+            }
+            _ => {
+                self.stop_after = None;
+                Some(Signal::Stop {
+                    reason: self.stop_reason.take(),
+                })
+            }
+        }
+    }
+
+    fn try_recv(&mut self) -> Option<Signal<A>> {
+        self.rx.try_recv().or_else(|_| self.control.try_recv()).ok()
     }
 }
 
 impl<A: Actor> fmt::Debug for BoundedMailboxReceiver<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("BoundedMailboxReceiver")
-            .field("rx", &self.0)
+            .field("rx", &self.rx)
+            .field("control", &self.control)
             .finish()
     }
 }
 
 /// A weak bounded mailbox that does not prevent the actor from being stopped.
-pub struct WeakBoundedMailbox<A: Actor>(mpsc::WeakSender<Signal<A>>);
+pub struct WeakBoundedMailbox<A: Actor>(
+    mpsc::WeakSender<Signal<A>>,
+    mpsc::WeakUnboundedSender<Signal<A>>,
+);
 
 impl<A: Actor> WeakMailbox for WeakBoundedMailbox<A> {
     type StrongMailbox = BoundedMailbox<A>;
 
     #[inline]
     fn upgrade(&self) -> Option<Self::StrongMailbox> {
-        self.0.upgrade().map(BoundedMailbox)
+        Some(BoundedMailbox(self.0.upgrade()?, self.1.upgrade()?))
     }
 
     #[inline]
@@ -130,7 +348,7 @@ impl<A: Actor> WeakMailbox for WeakBoundedMailbox<A> {
 
 impl<A: Actor> Clone for WeakBoundedMailbox<A> {
     fn clone(&self) -> Self {
-        WeakBoundedMailbox(self.0.clone())
+        WeakBoundedMailbox(self.0.clone(), self.1.clone())
     }
 }
 
@@ -148,9 +366,7 @@ where
 {
     fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
         async move {
-            self.0
-                .send(Signal::StartupFinished)
-                .await
+            self.send_control(Signal::StartupFinished)
                 .map_err(|_| SendError::ActorNotRunning(()))
         }
         .boxed()
@@ -162,9 +378,7 @@ where
         reason: ActorStopReason,
     ) -> BoxFuture<'_, Result<(), SendError>> {
         async move {
-            self.0
-                .send(Signal::LinkDied { id, reason })
-                .await
+            self.send_control(Signal::LinkDied { id, reason })
                 .map_err(|_| SendError::ActorNotRunning(()))
         }
         .boxed()
@@ -172,13 +386,15 @@ where
 
     fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
         async move {
-            self.0
-                .send(Signal::Stop)
-                .await
+            self.send_control(Signal::Stop { reason: None })
                 .map_err(|_| SendError::ActorNotRunning(()))
         }
         .boxed()
     }
+
+    fn mailbox_len(&self) -> usize {
+        self.len()
+    }
 }
 
 impl<A> SignalMailbox for WeakBoundedMailbox<A>
@@ -218,4 +434,8 @@ where
         }
         .boxed()
     }
+
+    fn mailbox_len(&self) -> usize {
+        self.upgrade().map(|mb| mb.len()).unwrap_or(0)
+    }
 }
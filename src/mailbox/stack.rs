@@ -0,0 +1,449 @@
+//! Stack mailbox types, which process the most recently sent message first.
+//!
+//! A stack mailbox is unbounded, and processes messages in last in, first out (LIFO) order. This suits workloads
+//! where the newest request is the most valuable, such as speculative or caching work where older requests have
+//! likely been superseded.
+//!
+//! Startup, link and stop signals skip the stack, and are processed before any queued messages. A graceful stop
+//! still waits for the messages queued when it is received to be processed.
+//!
+//! # Starvation
+//!
+//! Older messages are only processed once every newer message has been. If messages are sent faster than the
+//! actor can process them, the oldest messages may wait indefinitely, including `ask` requests whose callers
+//! are waiting on a reply. Consider pairing a stack mailbox with a reply timeout or message deadline.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::mailbox::{stack::StackMailbox, Mailbox};
+//! use kameo::message::{Context, Message};
+//! use kameo::request::MessageSend;
+//! use kameo::Actor;
+//!
+//! #[derive(Default)]
+//! struct Recorder {
+//!     received: Vec<u32>,
+//! }
+//!
+//! impl Actor for Recorder {
+//!     type Mailbox = StackMailbox<Self>;
+//!
+//!     fn new_mailbox() -> (Self::Mailbox, <Self::Mailbox as Mailbox<Self>>::Receiver) {
+//!         StackMailbox::new()
+//!     }
+//! }
+//!
+//! struct Record(u32);
+//!
+//! impl Message<Record> for Recorder {
+//!     type Reply = ();
+//!
+//!     async fn handle(&mut self, Record(n): Record, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+//!         self.received.push(n);
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let prepared_actor = kameo::actor::prepare(Recorder::default());
+//! let actor_ref = prepared_actor.actor_ref_owned();
+//! actor_ref.tell(Record(1)).send().await?;
+//! actor_ref.tell(Record(2)).send().await?;
+//! actor_ref.tell(Record(3)).send().await?;
+//!
+//! // Messages queued while the actor was not yet running are processed newest first
+//! let handle = prepared_actor.spawn();
+//! actor_ref.wait_startup().await;
+//! actor_ref.stop_gracefully().await?;
+//! let (recorder, _) = handle.await?;
+//! assert_eq!(recorder.received, vec![3, 2, 1]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::{
+    fmt,
+    future::poll_fn,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    task::{Context, Poll, Waker},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::sync::mpsc;
+
+use crate::{
+    actor::ActorID,
+    error::{ActorStopReason, SendError},
+    Actor,
+};
+
+use super::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox};
+
+/// A signal tagged with the order it was sent in, used to stop gracefully after earlier messages.
+type Sequenced<A> = (u64, Signal<A>);
+
+struct Stack<A: Actor> {
+    signals: Vec<Sequenced<A>>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+struct Shared<A: Actor> {
+    stack: Mutex<Stack<A>>,
+    seq: AtomicU64,
+    len: AtomicUsize,
+}
+
+impl<A: Actor> Shared<A> {
+    fn lock(&self) -> MutexGuard<'_, Stack<A>> {
+        self.stack.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// An unbounded mailbox, where the most recently sent message is processed first.
+///
+/// See the [module level documentation](self) for more information.
+pub struct StackMailbox<A: Actor> {
+    control: mpsc::UnboundedSender<Sequenced<A>>,
+    shared: Arc<Shared<A>>,
+}
+
+impl<A: Actor> StackMailbox<A> {
+    /// Creates a new stack mailbox.
+    pub fn new() -> (Self, StackMailboxReceiver<A>) {
+        let (control_tx, control_rx) = mpsc::unbounded_channel();
+        let shared = Arc::new(Shared {
+            stack: Mutex::new(Stack {
+                signals: Vec::new(),
+                waker: None,
+                closed: false,
+            }),
+            seq: AtomicU64::new(0),
+            len: AtomicUsize::new(0),
+        });
+        (
+            StackMailbox {
+                control: control_tx,
+                shared: shared.clone(),
+            },
+            StackMailboxReceiver {
+                control: control_rx,
+                shared,
+                stop_at: None,
+                stop_reason: None,
+            },
+        )
+    }
+
+    /// Pushes messages onto the stack, and sends other signals on the control lane.
+    fn send_signal(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        let seq = self.shared.seq.fetch_add(1, Ordering::Relaxed);
+        if !signal.is_message() {
+            self.shared.len.fetch_add(1, Ordering::Relaxed);
+            return self.control.send((seq, signal)).map_err(|err| {
+                self.shared.len.fetch_sub(1, Ordering::Relaxed);
+                mpsc::error::SendError(err.0 .1)
+            });
+        }
+
+        let mut stack = self.shared.lock();
+        if stack.closed {
+            return Err(mpsc::error::SendError(signal));
+        }
+        stack.signals.push((seq, signal));
+        self.shared.len.fetch_add(1, Ordering::Relaxed);
+        let waker = stack.waker.take();
+        drop(stack);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+}
+
+impl<A: Actor> Mailbox<A> for StackMailbox<A> {
+    type Receiver = StackMailboxReceiver<A>;
+    type WeakMailbox = WeakStackMailbox<A>;
+
+    #[inline]
+    fn default_mailbox() -> (Self, Self::Receiver) {
+        StackMailbox::new()
+    }
+
+    #[inline]
+    async fn send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
+    }
+
+    #[inline]
+    fn try_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
+    }
+
+    #[inline]
+    fn blocking_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
+    }
+
+    #[inline]
+    async fn closed(&self) {
+        self.control.closed().await
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.control.is_closed()
+    }
+
+    #[inline]
+    fn downgrade(&self) -> Self::WeakMailbox {
+        WeakStackMailbox {
+            control: self.control.downgrade(),
+            shared: self.shared.clone(),
+        }
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.control.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.control.weak_count()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.shared.len.load(Ordering::Relaxed)
+    }
+}
+
+impl<A: Actor> Clone for StackMailbox<A> {
+    fn clone(&self) -> Self {
+        StackMailbox {
+            control: self.control.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for StackMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackMailbox")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A stack mailbox receiver, which receives the most recently sent message first.
+pub struct StackMailboxReceiver<A: Actor> {
+    control: mpsc::UnboundedReceiver<Sequenced<A>>,
+    shared: Arc<Shared<A>>,
+    stop_at: Option<u64>,
+    stop_reason: Option<ActorStopReason>,
+}
+
+impl<A: Actor> StackMailboxReceiver<A> {
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<Signal<A>>> {
+        let mut closed = false;
+        if self.stop_at.is_none() {
+            match self.control.poll_recv(cx) {
+                Poll::Ready(Some((seq, Signal::Stop { reason }))) => {
+                    // Stop once the messages sent before the stop signal have been processed
+                    self.shared.len.fetch_sub(1, Ordering::Relaxed);
+                    self.stop_at = Some(seq);
+                    self.stop_reason = reason;
+                }
+                Poll::Ready(Some((_, signal))) => {
+                    self.shared.len.fetch_sub(1, Ordering::Relaxed);
+                    return Poll::Ready(Some(signal));
+                }
+                Poll::Ready(None) => closed = true,
+                Poll::Pending => {}
+            }
+        }
+
+        loop {
+            let mut stack = self.shared.lock();
+            let Some((seq, signal)) = stack.signals.pop() else {
+                stack.waker = Some(cx.waker().clone());
+                break;
+            };
+            drop(stack);
+            self.shared.len.fetch_sub(1, Ordering::Relaxed);
+            // Messages sent after the stop signal are ignored
+            if self.stop_at.map_or(true, |stop_at| seq < stop_at) {
+                return Poll::Ready(Some(signal));
+            }
+        }
+
+        if self.stop_at.take().is_some() {
+            return Poll::Ready(Some(Signal::Stop {
+                reason: self.stop_reason.take(),
+            }));
+        }
+        if closed {
+            return Poll::Ready(None);
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<A: Actor> MailboxReceiver<A> for StackMailboxReceiver<A> {
+    async fn recv(&mut self) -> Option<Signal<A>> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+}
+
+impl<A: Actor> Drop for StackMailboxReceiver<A> {
+    fn drop(&mut self) {
+        let mut stack = self.shared.lock();
+        stack.closed = true;
+        stack.waker = None;
+        let signals = std::mem::take(&mut stack.signals);
+        drop(stack);
+        self.shared.len.fetch_sub(signals.len(), Ordering::Relaxed);
+    }
+}
+
+impl<A: Actor> fmt::Debug for StackMailboxReceiver<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackMailboxReceiver")
+            .field("stop_at", &self.stop_at)
+            .finish()
+    }
+}
+
+/// A weak stack mailbox that does not prevent the actor from being stopped.
+pub struct WeakStackMailbox<A: Actor> {
+    control: mpsc::WeakUnboundedSender<Sequenced<A>>,
+    shared: Arc<Shared<A>>,
+}
+
+impl<A: Actor> WeakMailbox for WeakStackMailbox<A> {
+    type StrongMailbox = StackMailbox<A>;
+
+    #[inline]
+    fn upgrade(&self) -> Option<Self::StrongMailbox> {
+        Some(StackMailbox {
+            control: self.control.upgrade()?,
+            shared: self.shared.clone(),
+        })
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.control.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.control.weak_count()
+    }
+}
+
+impl<A: Actor> Clone for WeakStackMailbox<A> {
+    fn clone(&self) -> Self {
+        WeakStackMailbox {
+            control: self.control.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for WeakStackMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakStackMailbox")
+            .field("control", &self.control)
+            .finish()
+    }
+}
+
+impl<A> SignalMailbox for StackMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.send_signal(Signal::StartupFinished)
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.send_signal(Signal::LinkDied { id, reason })
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.send_signal(Signal::Stop { reason: None })
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<A> SignalMailbox for WeakStackMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_startup_finished().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_link_died(id, reason).await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_stop().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.shared.len.load(Ordering::Relaxed)
+    }
+}
@@ -1,6 +1,12 @@
 //! Unbounded mailbox types based on tokio mpsc unbounded channels.
 
-use std::fmt;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use futures::{future::BoxFuture, FutureExt};
 use tokio::sync::mpsc;
@@ -14,14 +20,35 @@ use crate::{
 use super::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox};
 
 /// An unbounded mailbox, where the number of messages queued can grow infinitely.
-pub struct UnboundedMailbox<A: Actor>(pub(crate) mpsc::UnboundedSender<Signal<A>>);
+pub struct UnboundedMailbox<A: Actor>(
+    pub(crate) mpsc::UnboundedSender<Signal<A>>,
+    Arc<AtomicUsize>,
+);
 
 impl<A: Actor> UnboundedMailbox<A> {
     /// Creates a new unbounded mailbox.
     #[inline]
     pub fn new() -> (Self, UnboundedMailboxReceiver<A>) {
         let (tx, rx) = mpsc::unbounded_channel();
-        (UnboundedMailbox(tx), UnboundedMailboxReceiver(rx))
+        let len = Arc::new(AtomicUsize::new(0));
+        (
+            UnboundedMailbox(tx, len.clone()),
+            UnboundedMailboxReceiver(rx, len),
+        )
+    }
+
+    /// Sends a signal, keeping track of the number of queued signals.
+    ///
+    /// Unbounded senders do not expose the channel length, so it is tracked separately.
+    #[inline]
+    pub(crate) fn send_signal(
+        &self,
+        signal: Signal<A>,
+    ) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        self.1.fetch_add(1, Ordering::Relaxed);
+        self.0.send(signal).inspect_err(|_| {
+            self.1.fetch_sub(1, Ordering::Relaxed);
+        })
     }
 }
 
@@ -36,17 +63,20 @@ impl<A: Actor> Mailbox<A> for UnboundedMailbox<A> {
 
     #[inline]
     async fn send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
-        Ok(self.0.send(signal)?)
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
     }
 
     #[inline]
     fn try_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
-        Ok(self.0.send(signal)?)
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
     }
 
     #[inline]
     fn blocking_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
-        Ok(self.0.send(signal)?)
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
     }
 
     #[inline]
@@ -61,7 +91,7 @@ impl<A: Actor> Mailbox<A> for UnboundedMailbox<A> {
 
     #[inline]
     fn downgrade(&self) -> Self::WeakMailbox {
-        WeakUnboundedMailbox(self.0.downgrade())
+        WeakUnboundedMailbox(self.0.downgrade(), self.1.clone())
     }
 
     #[inline]
@@ -73,11 +103,16 @@ impl<A: Actor> Mailbox<A> for UnboundedMailbox<A> {
     fn weak_count(&self) -> usize {
         self.0.weak_count()
     }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.1.load(Ordering::Relaxed)
+    }
 }
 
 impl<A: Actor> Clone for UnboundedMailbox<A> {
     fn clone(&self) -> Self {
-        UnboundedMailbox(self.0.clone())
+        UnboundedMailbox(self.0.clone(), self.1.clone())
     }
 }
 
@@ -90,11 +125,19 @@ impl<A: Actor> fmt::Debug for UnboundedMailbox<A> {
 }
 
 /// An unbounded mailbox receiver.
-pub struct UnboundedMailboxReceiver<A: Actor>(mpsc::UnboundedReceiver<Signal<A>>);
+pub struct UnboundedMailboxReceiver<A: Actor>(mpsc::UnboundedReceiver<Signal<A>>, Arc<AtomicUsize>);
 
 impl<A: Actor> MailboxReceiver<A> for UnboundedMailboxReceiver<A> {
     async fn recv(&mut self) -> Option<Signal<A>> {
-        self.0.recv().await
+        let signal = self.0.recv().await?;
+        self.1.fetch_sub(1, Ordering::Relaxed);
+        Some(signal)
+    }
+
+    fn try_recv(&mut self) -> Option<Signal<A>> {
+        let signal = self.0.try_recv().ok()?;
+        self.1.fetch_sub(1, Ordering::Relaxed);
+        Some(signal)
     }
 }
 
@@ -107,14 +150,16 @@ impl<A: Actor> fmt::Debug for UnboundedMailboxReceiver<A> {
 }
 
 /// A weak unbounded mailbox that does not prevent the actor from being stopped.
-pub struct WeakUnboundedMailbox<A: Actor>(mpsc::WeakUnboundedSender<Signal<A>>);
+pub struct WeakUnboundedMailbox<A: Actor>(mpsc::WeakUnboundedSender<Signal<A>>, Arc<AtomicUsize>);
 
 impl<A: Actor> WeakMailbox for WeakUnboundedMailbox<A> {
     type StrongMailbox = UnboundedMailbox<A>;
 
     #[inline]
     fn upgrade(&self) -> Option<Self::StrongMailbox> {
-        self.0.upgrade().map(UnboundedMailbox)
+        self.0
+            .upgrade()
+            .map(|tx| UnboundedMailbox(tx, self.1.clone()))
     }
 
     #[inline]
@@ -130,7 +175,7 @@ impl<A: Actor> WeakMailbox for WeakUnboundedMailbox<A> {
 
 impl<A: Actor> Clone for WeakUnboundedMailbox<A> {
     fn clone(&self) -> Self {
-        WeakUnboundedMailbox(self.0.clone())
+        WeakUnboundedMailbox(self.0.clone(), self.1.clone())
     }
 }
 
@@ -148,8 +193,7 @@ where
 {
     fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
         async move {
-            self.0
-                .send(Signal::StartupFinished)
+            self.send_signal(Signal::StartupFinished)
                 .map_err(|_| SendError::ActorNotRunning(()))
         }
         .boxed()
@@ -161,8 +205,7 @@ where
         reason: ActorStopReason,
     ) -> BoxFuture<'_, Result<(), SendError>> {
         async move {
-            self.0
-                .send(Signal::LinkDied { id, reason })
+            self.send_signal(Signal::LinkDied { id, reason })
                 .map_err(|_| SendError::ActorNotRunning(()))
         }
         .boxed()
@@ -170,12 +213,15 @@ where
 
     fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
         async move {
-            self.0
-                .send(Signal::Stop)
+            self.send_signal(Signal::Stop { reason: None })
                 .map_err(|_| SendError::ActorNotRunning(()))
         }
         .boxed()
     }
+
+    fn mailbox_len(&self) -> usize {
+        self.len()
+    }
 }
 
 impl<A> SignalMailbox for WeakUnboundedMailbox<A>
@@ -215,4 +261,8 @@ where
         }
         .boxed()
     }
+
+    fn mailbox_len(&self) -> usize {
+        self.1.load(Ordering::Relaxed)
+    }
 }
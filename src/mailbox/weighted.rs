@@ -0,0 +1,463 @@
+//! Weighted mailbox types, which fairly schedule messages across lanes using deficit round robin.
+//!
+//! A weighted mailbox has one or more lanes, each with a weight. Messages are placed into a lane by
+//! [`Message::lane`](crate::message::Message::lane), and lanes take turns being processed, with each lane
+//! processing up to its weight in messages per round. For example, lanes with weights `[3, 1]` process reads and writes
+//! at a 3:1 ratio while both have messages queued, but an idle lane never holds back a busy one.
+//!
+//! Lanes are unbounded, and messages within a lane are processed in the order they were sent.
+//! Startup and link signals skip the lanes, and are processed before any queued messages.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::mailbox::{weighted::WeightedMailbox, Mailbox};
+//! use kameo::message::{Context, Message};
+//! use kameo::Actor;
+//!
+//! struct Store;
+//!
+//! impl Actor for Store {
+//!     type Mailbox = WeightedMailbox<Self>;
+//!
+//!     fn new_mailbox() -> (Self::Mailbox, <Self::Mailbox as Mailbox<Self>>::Receiver) {
+//!         // Reads are processed three times as often as writes
+//!         WeightedMailbox::new([3, 1])
+//!     }
+//! }
+//!
+//! struct Read;
+//! struct Write;
+//!
+//! impl Message<Read> for Store {
+//!     type Reply = ();
+//!
+//!     async fn handle(&mut self, _: Read, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {}
+//! }
+//!
+//! impl Message<Write> for Store {
+//!     type Reply = ();
+//!
+//!     fn lane() -> usize {
+//!         1
+//!     }
+//!
+//!     async fn handle(&mut self, _: Write, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {}
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let store = kameo::spawn(Store);
+//! store.tell(Write).await?;
+//! store.ask(Read).await?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::{
+    fmt,
+    future::poll_fn,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::sync::mpsc;
+
+use crate::{
+    actor::ActorID,
+    error::{ActorStopReason, SendError},
+    Actor,
+};
+
+use super::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox};
+
+/// A signal tagged with the order it was sent in, used to stop gracefully after earlier messages in every lane.
+type Sequenced<A> = (u64, Signal<A>);
+
+struct Lanes<A: Actor> {
+    senders: Box<[mpsc::UnboundedSender<Sequenced<A>>]>,
+    seq: AtomicU64,
+}
+
+/// A weighted mailbox, where messages are fairly scheduled across lanes according to their weights.
+///
+/// See the [module level documentation](self) for more information.
+pub struct WeightedMailbox<A: Actor> {
+    control: mpsc::UnboundedSender<Sequenced<A>>,
+    lanes: Arc<Lanes<A>>,
+    len: Arc<AtomicUsize>,
+}
+
+impl<A: Actor> WeightedMailbox<A> {
+    /// Creates a new weighted mailbox, with a lane for each weight.
+    ///
+    /// Messages are placed into a lane by [`Message::lane`](crate::message::Message::lane),
+    /// where lanes out of range are placed into the last lane.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no weights, or any weight is `0`.
+    pub fn new(weights: impl IntoIterator<Item = usize>) -> (Self, WeightedMailboxReceiver<A>) {
+        let weights: Box<[usize]> = weights.into_iter().collect();
+        assert!(
+            !weights.is_empty(),
+            "a weighted mailbox needs at least one lane"
+        );
+        assert!(
+            weights.iter().all(|weight| *weight > 0),
+            "lane weights must be greater than zero"
+        );
+
+        let (control_tx, control_rx) = mpsc::unbounded_channel();
+        let (senders, receivers): (Vec<_>, Vec<_>) =
+            weights.iter().map(|_| mpsc::unbounded_channel()).unzip();
+        let len = Arc::new(AtomicUsize::new(0));
+        (
+            WeightedMailbox {
+                control: control_tx,
+                lanes: Arc::new(Lanes {
+                    senders: senders.into(),
+                    seq: AtomicU64::new(0),
+                }),
+                len: len.clone(),
+            },
+            WeightedMailboxReceiver {
+                control: control_rx,
+                lanes: receivers.into(),
+                deficit: weights[0],
+                weights,
+                current: 0,
+                stop_at: None,
+                stop_reason: None,
+                len,
+            },
+        )
+    }
+
+    /// Returns the number of lanes in the mailbox.
+    pub fn lanes(&self) -> usize {
+        self.lanes.senders.len()
+    }
+
+    /// Sends a signal to its lane, keeping track of the number of queued signals.
+    fn send_signal(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        let seq = self.lanes.seq.fetch_add(1, Ordering::Relaxed);
+        let tx = match &signal {
+            Signal::Message { message, .. } => {
+                let lane = message.lane().min(self.lanes.senders.len() - 1);
+                &self.lanes.senders[lane]
+            }
+            // Reads have no lane, so they share the first
+            Signal::Read { .. } => &self.lanes.senders[0],
+            _ => &self.control,
+        };
+        self.len.fetch_add(1, Ordering::Relaxed);
+        tx.send((seq, signal)).map_err(|err| {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+            mpsc::error::SendError(err.0 .1)
+        })
+    }
+}
+
+impl<A: Actor> Mailbox<A> for WeightedMailbox<A> {
+    type Receiver = WeightedMailboxReceiver<A>;
+    type WeakMailbox = WeakWeightedMailbox<A>;
+
+    /// Creates a weighted mailbox with a single lane, which processes messages in order.
+    #[inline]
+    fn default_mailbox() -> (Self, Self::Receiver) {
+        WeightedMailbox::new([1])
+    }
+
+    #[inline]
+    async fn send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
+    }
+
+    #[inline]
+    fn try_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
+    }
+
+    #[inline]
+    fn blocking_send<E: 'static>(&self, signal: Signal<A>) -> Result<(), SendError<Signal<A>, E>> {
+        self.send_signal(signal)
+            .map_err(|err| SendError::ActorNotRunning(err.0))
+    }
+
+    #[inline]
+    async fn closed(&self) {
+        self.control.closed().await
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.control.is_closed()
+    }
+
+    #[inline]
+    fn downgrade(&self) -> Self::WeakMailbox {
+        WeakWeightedMailbox {
+            control: self.control.downgrade(),
+            lanes: Arc::downgrade(&self.lanes),
+            len: self.len.clone(),
+        }
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.control.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.control.weak_count()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+}
+
+impl<A: Actor> Clone for WeightedMailbox<A> {
+    fn clone(&self) -> Self {
+        WeightedMailbox {
+            control: self.control.clone(),
+            lanes: self.lanes.clone(),
+            len: self.len.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for WeightedMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedMailbox")
+            .field("lanes", &self.lanes())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A weighted mailbox receiver, which schedules lanes using deficit round robin.
+pub struct WeightedMailboxReceiver<A: Actor> {
+    control: mpsc::UnboundedReceiver<Sequenced<A>>,
+    lanes: Box<[mpsc::UnboundedReceiver<Sequenced<A>>]>,
+    weights: Box<[usize]>,
+    current: usize,
+    deficit: usize,
+    stop_at: Option<u64>,
+    stop_reason: Option<ActorStopReason>,
+    len: Arc<AtomicUsize>,
+}
+
+impl<A: Actor> WeightedMailboxReceiver<A> {
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<Signal<A>>> {
+        let mut closed = false;
+        if self.stop_at.is_none() {
+            match self.control.poll_recv(cx) {
+                Poll::Ready(Some((seq, Signal::Stop { reason }))) => {
+                    // Stop once the messages sent before the stop signal have been processed
+                    self.len.fetch_sub(1, Ordering::Relaxed);
+                    self.stop_at = Some(seq);
+                    self.stop_reason = reason;
+                }
+                Poll::Ready(Some((_, signal))) => {
+                    self.len.fetch_sub(1, Ordering::Relaxed);
+                    return Poll::Ready(Some(signal));
+                }
+                Poll::Ready(None) => closed = true,
+                Poll::Pending => {}
+            }
+        }
+
+        // Each lane is visited at most once, plus the current lane if it has deficit remaining
+        let mut lanes_closed = 0;
+        for _ in 0..=self.lanes.len() {
+            if self.deficit == 0 {
+                self.current = (self.current + 1) % self.lanes.len();
+                self.deficit = self.weights[self.current];
+            }
+
+            match self.lanes[self.current].poll_recv(cx) {
+                Poll::Ready(Some((seq, signal))) => {
+                    self.len.fetch_sub(1, Ordering::Relaxed);
+                    if self.stop_at.is_some_and(|stop_at| seq > stop_at) {
+                        // Messages sent after the stop signal are ignored
+                        self.deficit = 0;
+                        continue;
+                    }
+                    self.deficit -= 1;
+                    return Poll::Ready(Some(signal));
+                }
+                Poll::Ready(None) => {
+                    lanes_closed += 1;
+                    self.deficit = 0;
+                }
+                // An empty lane forfeits the rest of its turn
+                Poll::Pending => self.deficit = 0,
+            }
+        }
+
+        if self.stop_at.take().is_some() {
+            return Poll::Ready(Some(Signal::Stop {
+                reason: self.stop_reason.take(),
+            }));
+        }
+        // Every poll of every lane returned `None`
+        if closed && lanes_closed == self.lanes.len() + 1 {
+            return Poll::Ready(None);
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<A: Actor> MailboxReceiver<A> for WeightedMailboxReceiver<A> {
+    async fn recv(&mut self) -> Option<Signal<A>> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+}
+
+impl<A: Actor> fmt::Debug for WeightedMailboxReceiver<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedMailboxReceiver")
+            .field("weights", &self.weights)
+            .field("current", &self.current)
+            .field("deficit", &self.deficit)
+            .finish()
+    }
+}
+
+/// A weak weighted mailbox that does not prevent the actor from being stopped.
+pub struct WeakWeightedMailbox<A: Actor> {
+    control: mpsc::WeakUnboundedSender<Sequenced<A>>,
+    lanes: Weak<Lanes<A>>,
+    len: Arc<AtomicUsize>,
+}
+
+impl<A: Actor> WeakMailbox for WeakWeightedMailbox<A> {
+    type StrongMailbox = WeightedMailbox<A>;
+
+    #[inline]
+    fn upgrade(&self) -> Option<Self::StrongMailbox> {
+        Some(WeightedMailbox {
+            control: self.control.upgrade()?,
+            lanes: self.lanes.upgrade()?,
+            len: self.len.clone(),
+        })
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.control.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.control.weak_count()
+    }
+}
+
+impl<A: Actor> Clone for WeakWeightedMailbox<A> {
+    fn clone(&self) -> Self {
+        WeakWeightedMailbox {
+            control: self.control.clone(),
+            lanes: self.lanes.clone(),
+            len: self.len.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for WeakWeightedMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakWeightedMailbox")
+            .field("control", &self.control)
+            .finish()
+    }
+}
+
+impl<A> SignalMailbox for WeightedMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.send_signal(Signal::StartupFinished)
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.send_signal(Signal::LinkDied { id, reason })
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.send_signal(Signal::Stop { reason: None })
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<A> SignalMailbox for WeakWeightedMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_startup_finished().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_link_died(id, reason).await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_stop().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+}
@@ -14,14 +14,28 @@
 //! (Command Query Responsibility Segregation) principle and enhancing the clarity and maintainability of actor
 //! interactions. It also provides some performance benefits in that sequential queries can be processed concurrently.
 
-use std::{any, fmt};
+use std::{
+    any,
+    collections::HashMap,
+    fmt, mem, panic,
+    time::{Duration, Instant},
+};
 
 use futures::{future::BoxFuture, Future, FutureExt};
+use tokio::task::{self, JoinHandle};
+use tracing::warn;
 
 use crate::{
-    actor::ActorRef,
+    actor::{
+        ActorID, ActorRef, ActorStats, ExecutionMode, PreparedActor, ScheduledMessageId,
+        CURRENT_DEADLINE, CURRENT_EXECUTION_MODE, CURRENT_REPLY_OPTIONS, CURRENT_SENDER_ID,
+    },
     error::SendError,
-    reply::{BoxReplySender, DelegatedReply, ForwardedReply, Reply, ReplySender},
+    mailbox::{Mailbox, MessageOptions, Signal},
+    reply::{
+        BoxReplySender, DeferredReply, DelegatedReply, ForwardedReply, Reply, ReplyOptions,
+        ReplySender,
+    },
     request::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout},
     Actor,
 };
@@ -38,6 +52,29 @@ pub trait Message<T: Send + 'static>: Actor {
     /// The reply sent back to the message caller.
     type Reply: Reply;
 
+    /// The lane this message is placed into, for actors using a [`WeightedMailbox`].
+    ///
+    /// Other mailboxes have a single lane, and ignore this.
+    ///
+    /// # Default Implementation
+    /// By default, messages are placed into the first lane, `0`.
+    ///
+    /// [`WeightedMailbox`]: crate::mailbox::weighted::WeightedMailbox
+    fn lane() -> usize {
+        0
+    }
+
+    /// An estimate of the size of a message in bytes, checked against the actor's
+    /// [`max_message_size`](Actor::max_message_size) before the message is sent.
+    ///
+    /// Messages owning heap data, such as buffers or collections, should include it in the estimate.
+    ///
+    /// # Default Implementation
+    /// By default, this returns the size of the message type itself, without any heap data it owns.
+    fn size_hint(msg: &T) -> usize {
+        mem::size_of_val(msg)
+    }
+
     /// Handler for this message.
     fn handle(
         &mut self,
@@ -46,6 +83,84 @@ pub trait Message<T: Send + 'static>: Actor {
     ) -> impl Future<Output = Self::Reply> + Send;
 }
 
+/// A read-only message, which is handled with shared access to the actor's state.
+///
+/// Read messages are sent with [`ActorRef::ask_read`], and run concurrently with each other, up to the actor's
+/// [`max_concurrent_reads`](Actor::max_concurrent_reads), while [`Message`]s still run exclusively. Like a read-write
+/// lock, a message is only handled once every read received before it has finished, and reads received after a message
+/// wait for it to finish, so reads always observe the state left by the messages sent before them.
+///
+/// Reads run concurrently within the actor's task rather than in parallel, so they speed up handlers which spend
+/// time waiting, such as on IO or other actors. Since handlers share the actor's state, the actor must be `Sync`.
+/// Reads received while the actor is starting up are handled one at a time once it has started.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::message::{Context, Message, ReadMessage};
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Cache {
+///     value: u32,
+/// }
+///
+/// struct Get;
+/// struct Set(u32);
+///
+/// impl ReadMessage<Get> for Cache {
+///     type Reply = u32;
+///
+///     async fn handle(&self, _: Get, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         // Reads waiting on IO overlap, rather than queueing behind each other
+///         tokio::time::sleep(Duration::from_millis(50)).await;
+///         self.value
+///     }
+/// }
+///
+/// impl Message<Set> for Cache {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, Set(value): Set, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.value = value;
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let cache = kameo::spawn(Cache::default());
+/// cache.wait_startup().await;
+/// cache.tell(Set(1)).await?;
+///
+/// let start = std::time::Instant::now();
+/// let (a, b, c) = tokio::join!(cache.ask_read(Get), cache.ask_read(Get), cache.ask_read(Get));
+/// assert_eq!((a?, b?, c?), (1, 1, 1));
+/// assert!(start.elapsed() < Duration::from_millis(150));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+///
+/// [`ActorRef::ask_read`]: crate::actor::ActorRef::ask_read
+pub trait ReadMessage<T: Send + 'static>: Actor + Sync {
+    /// The reply sent back to the message caller.
+    type Reply: Reply;
+
+    /// An estimate of the size of a message in bytes, see [`Message::size_hint`].
+    ///
+    /// # Default Implementation
+    /// By default, this returns the size of the message type itself, without any heap data it owns.
+    fn size_hint(msg: &T) -> usize {
+        mem::size_of_val(msg)
+    }
+
+    /// Handler for this message.
+    fn handle(
+        &self,
+        msg: T,
+        ctx: Context<'_, Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send;
+}
+
 /// A type for handling streams attached to an actor.
 ///
 /// Actors which implement handling messages of this type can receive and process messages from a stream attached to the actor.
@@ -72,6 +187,7 @@ where
 {
     actor_ref: ActorRef<A>,
     reply: &'r mut Option<ReplySender<R::Value>>,
+    previous: Option<&'r (dyn any::Any + Send + Sync)>,
 }
 
 impl<'r, A, R> Context<'r, A, R>
@@ -82,8 +198,13 @@ where
     pub(crate) fn new(
         actor_ref: ActorRef<A>,
         reply: &'r mut Option<ReplySender<R::Value>>,
+        previous: Option<&'r (dyn any::Any + Send + Sync)>,
     ) -> Self {
-        Context { actor_ref, reply }
+        Context {
+            actor_ref,
+            reply,
+            previous,
+        }
     }
 
     /// Returns the current actor's ref, allowing messages to be sent to itself.
@@ -138,11 +259,760 @@ where
     ///
     /// It is important to ensure that [ReplySender::send] is called to complete the transaction and send the response
     /// back to the requester. Failure to do so could result in the requester waiting indefinitely for a response.
-    #[must_use]
+    #[must_use = "the reply sender must be used to send a reply"]
     pub fn reply_sender(&mut self) -> (DelegatedReply<R::Value>, Option<ReplySender<R::Value>>) {
         (DelegatedReply::new(), self.reply.take())
     }
 
+    /// Replies with the output of a future, which is spawned so the actor can process its next message while the
+    /// reply is computed.
+    ///
+    /// This is useful for replies which involve I/O that doesn't touch the actor's state, such as calling another
+    /// service. The future is `'static`, so any data it needs should be cloned or moved into it. It keeps running if
+    /// the actor stops in the meantime.
+    ///
+    /// The returned [`DelegatedReply`] should be returned by the handler. If the message was sent with `tell`
+    /// and the future resolves to an error, the error is logged, as the actor has already moved on.
+    /// To declare the deferred computation in the handler's reply type instead, use [`DeferredReply`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use kameo::message::{Context, Message};
+    /// use kameo::reply::DelegatedReply;
+    /// use tokio::sync::Notify;
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Gateway {
+    ///     upstream_ready: Arc<Notify>,
+    /// }
+    ///
+    /// struct Fetch;
+    ///
+    /// impl Message<Fetch> for Gateway {
+    ///     type Reply = DelegatedReply<&'static str>;
+    ///
+    ///     async fn handle(&mut self, _: Fetch, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let upstream_ready = self.upstream_ready.clone();
+    ///         ctx.reply_async(async move {
+    ///             upstream_ready.notified().await;
+    ///             "response"
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// struct UpstreamReady;
+    ///
+    /// impl Message<UpstreamReady> for Gateway {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: UpstreamReady, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.upstream_ready.notify_one();
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let gateway = kameo::spawn(Gateway::default());
+    ///
+    /// // The gateway handles the second message while the first reply is still pending
+    /// let (response, ready) = tokio::join!(gateway.ask(Fetch), gateway.ask(UpstreamReady));
+    /// assert_eq!(response?, "response");
+    /// ready?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn reply_async<F>(&mut self, fut: F) -> DelegatedReply<R::Value>
+    where
+        F: Future<Output = R::Value> + Send + 'static,
+        R::Value: Reply,
+    {
+        let (delegated_reply, tx) = self.reply_sender();
+        DeferredReply::new(fut).spawn(tx);
+        delegated_reply
+    }
+
+    /// Spawns a prepared actor linked to the current actor, returning the child's actor ref.
+    ///
+    /// The link is established before the child is spawned, so the current actor is notified even if the child
+    /// stops immediately. Since the child is prepared beforehand, messages can be sent to it with
+    /// [`PreparedActor::actor_ref`] to seed its mailbox before it starts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::prepare;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::Actor;
+    ///
+    /// #[derive(Actor)]
+    /// struct Parent;
+    ///
+    /// #[derive(Actor, Default)]
+    /// struct Child { count: u32 }
+    ///
+    /// struct Increment;
+    ///
+    /// impl Message<Increment> for Child {
+    ///     type Reply = u32;
+    ///
+    ///     async fn handle(&mut self, _: Increment, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.count += 1;
+    ///         self.count
+    ///     }
+    /// }
+    ///
+    /// struct SpawnChild;
+    ///
+    /// impl Message<SpawnChild> for Parent {
+    ///     type Reply = u32;
+    ///
+    ///     async fn handle(&mut self, _: SpawnChild, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let prepared = prepare(Child::default());
+    ///         prepared.actor_ref().tell(Increment).await.unwrap(); // Seed the mailbox before spawning
+    ///         let child = ctx.adopt_child(prepared).await;
+    ///         child.ask(Increment).await.unwrap()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let parent = kameo::spawn(Parent);
+    /// assert_eq!(parent.ask(SpawnChild).await?, 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn adopt_child<C>(&self, prepared: PreparedActor<C>) -> ActorRef<C>
+    where
+        C: Actor,
+    {
+        let child_ref = prepared.actor_ref().clone();
+        self.actor_ref.link(&child_ref).await;
+        prepared.spawn();
+        child_ref
+    }
+
+    /// Stashes a message to be processed later, deferring its reply.
+    ///
+    /// This is useful when an actor receives a message it cannot process in its current state, but
+    /// shouldn't reject it. The message is kept in a stash until [`Context::unstash_all`] is called, typically after
+    /// the actor's state has changed. If the message was sent with `ask`, the caller will receive
+    /// its reply once the message has been unstashed and handled.
+    ///
+    /// The message should usually be the one currently being handled, which is passed back by value.
+    /// The value returned by the handler after stashing is discarded, unless it is an error from a `tell` request.
+    ///
+    /// Messages still stashed when the actor stops are passed to [`Actor::on_undelivered`], or redelivered if
+    /// the mailbox is being transferred with [`ActorRef::transfer_mailbox_to`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor, Default)]
+    /// struct Door { open: bool }
+    ///
+    /// struct Enter;
+    /// struct Open;
+    ///
+    /// impl Message<Enter> for Door {
+    ///     type Reply = &'static str;
+    ///
+    ///     async fn handle(&mut self, msg: Enter, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         if !self.open {
+    ///             ctx.stash(msg); // Wait until the door is open
+    ///             return "stashed";
+    ///         }
+    ///         "entered"
+    ///     }
+    /// }
+    ///
+    /// impl Message<Open> for Door {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Open, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.open = true;
+    ///         ctx.unstash_all();
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let door_ref = kameo::spawn(Door::default());
+    /// let enter = tokio::spawn({
+    ///     let door_ref = door_ref.clone();
+    ///     async move { door_ref.ask(Enter).await }
+    /// });
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    /// door_ref.tell(Open).await?;
+    /// assert_eq!(enter.await??, "entered");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn stash<M>(&mut self, msg: M)
+    where
+        A: Message<M, Reply = R>,
+        M: Send + 'static,
+    {
+        let reply = self.reply.take().map(ReplySender::boxed);
+        self.actor_ref.loop_state.stash.lock().unwrap().push(
+            Box::new(msg),
+            reply,
+            MessageOptions::boxed(self.deadline(), self.reply_options(), self.sender_id()),
+        );
+    }
+
+    /// Returns how the actor is being executed, either in a tokio task or a dedicated thread.
+    ///
+    /// This lets generic code adapt to the actor, such as only using `spawn_blocking` for blocking work
+    /// when running in a tokio task. Actors run directly with [`PreparedActor::run`](crate::actor::PreparedActor::run)
+    /// report [`ExecutionMode::TokioTask`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ExecutionMode;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::Actor;
+    ///
+    /// #[derive(Actor)]
+    /// struct MyActor;
+    ///
+    /// struct CanBlock;
+    ///
+    /// impl Message<CanBlock> for MyActor {
+    ///     type Reply = bool;
+    ///
+    ///     async fn handle(&mut self, _: CanBlock, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.execution_mode() == ExecutionMode::DedicatedThread
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// assert!(!actor_ref.ask(CanBlock).await?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn execution_mode(&self) -> ExecutionMode {
+        CURRENT_EXECUTION_MODE
+            .try_with(|mode| *mode)
+            .unwrap_or(ExecutionMode::TokioTask)
+    }
+
+    /// Runs a blocking closure on Tokio's blocking thread pool, waiting for it to finish.
+    ///
+    /// This allows mostly async actors to perform occasional blocking work, such as file IO or CPU heavy computation,
+    /// without blocking the async runtime or resorting to [`spawn_in_thread`](crate::actor::spawn_in_thread)
+    /// for the whole actor.
+    ///
+    /// The blocking task is tied to the handler: if the actor is killed, or the handler is otherwise cancelled,
+    /// the task is aborted. This is best-effort, since a closure which has already started running
+    /// cannot be interrupted, and will run to completion in the background with its result discarded.
+    ///
+    /// If the closure panics, the panic is resumed in the handler.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Hasher;
+    ///
+    /// struct Hash(Vec<u8>);
+    ///
+    /// impl Message<Hash> for Hasher {
+    ///     type Reply = u64;
+    ///
+    ///     async fn handle(&mut self, Hash(data): Hash, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.spawn_blocking(move || data.iter().map(|byte| *byte as u64).sum()).await
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Hasher);
+    /// assert_eq!(actor_ref.ask(Hash(vec![1, 2, 3])).await?, 6);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn spawn_blocking<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        /// Aborts the blocking task if the handler is dropped before it finishes.
+        struct AbortOnDrop<T>(JoinHandle<T>);
+
+        impl<T> Drop for AbortOnDrop<T> {
+            fn drop(&mut self) {
+                self.0.abort();
+            }
+        }
+
+        let mut handle = AbortOnDrop(task::spawn_blocking(f));
+        match (&mut handle.0).await {
+            Ok(value) => value,
+            // The task is only aborted once the handler is dropped, so the error is always a panic
+            Err(err) => panic::resume_unwind(err.into_panic()),
+        }
+    }
+
+    /// Returns the deadline by which the caller expects a reply, if any.
+    ///
+    /// The deadline is set when the message is sent with an ask request's
+    /// [`reply_timeout`](crate::request::AskRequest::reply_timeout) or `deadline`, and is propagated across actor hops:
+    /// asks sent from within this handler inherit the deadline, shortened by their own reply timeout if sooner.
+    /// Handlers can use it to bail early rather than start work which cannot finish in time.
+    ///
+    /// Returns `None` for tell requests, or asks without a reply timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor)]
+    /// struct Worker;
+    ///
+    /// struct Work;
+    ///
+    /// impl Message<Work> for Worker {
+    ///     type Reply = Option<Duration>;
+    ///
+    ///     async fn handle(&mut self, _: Work, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.deadline().map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let worker = kameo::spawn(Worker);
+    ///
+    /// let remaining = worker.ask(Work).reply_timeout(Duration::from_secs(5)).await?;
+    /// assert!(remaining.unwrap() <= Duration::from_secs(5));
+    ///
+    /// assert_eq!(worker.ask(Work).await?, None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn deadline(&self) -> Option<Instant> {
+        CURRENT_DEADLINE
+            .try_with(|deadline| *deadline)
+            .ok()
+            .flatten()
+    }
+
+    /// Returns whether the caller is still waiting for the reply to this message.
+    ///
+    /// This is `false` once an ask's caller has stopped waiting, such as when its
+    /// [`reply_timeout`](crate::request::AskRequest::reply_timeout) elapsed while the message was queued,
+    /// or the request was dropped. Handlers doing expensive work can check it to skip work whose reply would be
+    /// discarded anyway.
+    ///
+    /// Returns `false` for tell requests, and once the reply sender has been taken with
+    /// [`reply_sender`](Context::reply_sender), which has a [`ReplySender::is_closed`] method of its own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor, Default)]
+    /// struct Worker {
+    ///     skipped: usize,
+    /// }
+    ///
+    /// struct Render;
+    ///
+    /// impl Message<Render> for Worker {
+    ///     type Reply = Option<Vec<u8>>;
+    ///
+    ///     async fn handle(&mut self, _: Render, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         tokio::time::sleep(Duration::from_millis(50)).await;
+    ///         if !ctx.caller_waiting() {
+    ///             self.skipped += 1;
+    ///             return None;
+    ///         }
+    ///         Some(vec![0; 1024])
+    ///     }
+    /// }
+    ///
+    /// struct Skipped;
+    ///
+    /// impl Message<Skipped> for Worker {
+    ///     type Reply = usize;
+    ///
+    ///     async fn handle(&mut self, _: Skipped, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.skipped
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let worker = kameo::spawn(Worker::default());
+    ///
+    /// // The caller gives up before the render finishes, so the work is skipped
+    /// let res = worker.ask(Render).reply_timeout(Duration::from_millis(10)).await;
+    /// assert!(res.is_err());
+    /// assert_eq!(worker.ask(Skipped).await?, 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn caller_waiting(&self) -> bool {
+        self.reply.as_ref().is_some_and(|tx| !tx.is_closed())
+    }
+
+    /// Returns the message of type `M` handled before the current one, if `M` is retained with
+    /// [`ActorRef::retain_previous`].
+    ///
+    /// Returns `None` for the first message handled after `M` was retained, or if `M` isn't the type of the current
+    /// message. See [`ActorRef::retain_previous`] for an example.
+    pub fn previous<M: 'static>(&self) -> Option<&M> {
+        self.previous?.downcast_ref()
+    }
+
+    /// Returns the reply options set by the caller with [`ActorRef::ask_with`].
+    ///
+    /// Streaming replies such as [`Progress`](crate::reply::Progress) should be created with
+    /// [`Progress::with_options`](crate::reply::Progress::with_options) to honour the caller's buffer size.
+    /// Returns the default options for other requests.
+    pub fn reply_options(&self) -> ReplyOptions {
+        CURRENT_REPLY_OPTIONS
+            .try_with(|options| *options)
+            .unwrap_or_default()
+    }
+
+    /// Returns the ID of the actor which sent the current message.
+    ///
+    /// Returns `None` if the message was sent from outside any actor, such as from `main` or a plain tokio task,
+    /// since external senders have no ID. Messages sent by remote actors also have no sender ID.
+    /// Stashed and requeued messages keep the ID of their original sender.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{ActorID, ActorRef};
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Audit;
+    ///
+    /// struct Who;
+    ///
+    /// impl Message<Who> for Audit {
+    ///     type Reply = Option<ActorID>;
+    ///
+    ///     async fn handle(&mut self, _: Who, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.sender_id()
+    ///     }
+    /// }
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Client;
+    ///
+    /// struct Forward(ActorRef<Audit>);
+    ///
+    /// impl Message<Forward> for Client {
+    ///     type Reply = Option<ActorID>;
+    ///
+    ///     async fn handle(&mut self, Forward(audit): Forward, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         audit.ask(Who).await.unwrap()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let audit = kameo::spawn(Audit);
+    /// let client = kameo::spawn(Client);
+    ///
+    /// assert_eq!(audit.ask(Who).await?, None);
+    /// assert_eq!(client.ask(Forward(audit)).await?, Some(client.id()));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn sender_id(&self) -> Option<ActorID> {
+        CURRENT_SENDER_ID.try_with(|id| *id).ok().flatten()
+    }
+
+    /// Looks up a sibling actor registered by name in the [`Scope`](crate::actor::Scope) the current actor was spawned in.
+    ///
+    /// Returns `None` if the actor wasn't spawned in a scope, no actor is registered under the name,
+    /// or the registered actor is not of type `B`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Database;
+    ///
+    /// struct Query;
+    ///
+    /// impl Message<Query> for Database {
+    ///     type Reply = u32;
+    ///
+    ///     async fn handle(&mut self, _: Query, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         42
+    ///     }
+    /// }
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Api;
+    ///
+    /// struct Get;
+    ///
+    /// impl Message<Get> for Api {
+    ///     type Reply = Option<u32>;
+    ///
+    ///     async fn handle(&mut self, _: Get, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let db = ctx.sibling::<Database>("db")?;
+    ///         db.ask(Query).await.ok()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let row = kameo::scope(|s| async move {
+    ///     s.spawn_named("db", Database);
+    ///     let api = s.spawn(Api);
+    ///     api.ask(Get).await
+    /// })
+    /// .await??;
+    ///
+    /// assert_eq!(row, Some(42));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn sibling<B: Actor>(&self, name: &str) -> Option<ActorRef<B>> {
+        crate::actor::sibling(self.actor_ref.id(), name)
+    }
+
+    /// Re-enqueues all stashed messages, to be processed in the order they were stashed.
+    ///
+    /// Unstashed messages are processed immediately after the current handler returns,
+    /// before any other messages in the mailbox.
+    pub fn unstash_all(&self) {
+        self.actor_ref
+            .loop_state
+            .stash
+            .lock()
+            .unwrap()
+            .unstash_all();
+    }
+
+    /// Returns the number of messages currently stashed.
+    pub fn stash_len(&self) -> usize {
+        self.actor_ref.loop_state.stash.lock().unwrap().len()
+    }
+
+    /// Negatively acknowledges a message, either requeueing it or dead-lettering it.
+    ///
+    /// If `requeue` is `true`, the message is sent back to the end of the actor's mailbox to be retried later,
+    /// and the caller receives its reply once the message is eventually handled. If `requeue` is `false`,
+    /// or the mailbox is full or closed, the message is dead-lettered: it is dropped with a warning, and an
+    /// `ask` caller receives an error.
+    ///
+    /// Like [`Context::stash`], the message should usually be the one currently being handled, which is passed back by value.
+    /// The value returned by the handler after nacking is discarded, unless it is an error from a `tell` request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor, Default)]
+    /// struct Worker { attempts: u32 }
+    ///
+    /// struct Job;
+    ///
+    /// impl Message<Job> for Worker {
+    ///     type Reply = u32;
+    ///
+    ///     async fn handle(&mut self, msg: Job, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.attempts += 1;
+    ///         if self.attempts < 3 {
+    ///             ctx.nack(msg, true); // Transient failure, try again later
+    ///             return 0;
+    ///         }
+    ///         self.attempts
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let worker = kameo::spawn(Worker::default());
+    /// assert_eq!(worker.ask(Job).await?, 3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn nack<M>(&mut self, msg: M, requeue: bool)
+    where
+        A: Message<M, Reply = R>,
+        M: Send + 'static,
+    {
+        let id = self.actor_ref.id();
+        let message_type = any::type_name::<M>();
+        let reply = self.reply.take().map(ReplySender::boxed);
+        if requeue {
+            let signal = Signal::Message {
+                message: Box::new(msg),
+                actor_ref: self.actor_ref.clone(),
+                reply,
+                sent_within_actor: true,
+                options: MessageOptions::boxed(
+                    self.deadline(),
+                    self.reply_options(),
+                    self.sender_id(),
+                ),
+            };
+            if self.actor_ref.mailbox().try_send::<()>(signal).is_err() {
+                warn!(%id, %message_type, "failed to requeue message, dead-lettering");
+            }
+        } else {
+            warn!(%id, %message_type, "message dead-lettered");
+        }
+    }
+
+    /// Redelivers a message to the actor after `delay`, for handlers which are not ready to process it yet.
+    ///
+    /// This is useful for actors waiting on an external resource to become available. Unlike
+    /// [`Context::stash`], the message is retried automatically without needing to be unstashed, and unlike
+    /// [`Context::nack`], it isn't redelivered until the delay has elapsed. Other messages are handled in the meantime.
+    ///
+    /// The retry is scheduled with the actor's [`ScheduledMessages`](crate::actor::ScheduledMessages),
+    /// so it's listed as pending and can be cancelled with the returned id. An `ask` caller receives its reply once the
+    /// message is eventually handled, or an error if the retry is cancelled or the actor stops.
+    /// The value returned by the handler after retrying is discarded, unless it is an error from a `tell` request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor, Default)]
+    /// struct Uploader { attempts: u32 }
+    ///
+    /// struct Upload;
+    ///
+    /// impl Message<Upload> for Uploader {
+    ///     type Reply = u32;
+    ///
+    ///     async fn handle(&mut self, msg: Upload, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.attempts += 1;
+    ///         if self.attempts < 3 {
+    ///             ctx.retry_later(msg, Duration::from_millis(10)); // The server isn't reachable yet
+    ///             return 0;
+    ///         }
+    ///         self.attempts
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let uploader = kameo::spawn(Uploader::default());
+    /// assert_eq!(uploader.ask(Upload).await?, 3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn retry_later<M>(&mut self, msg: M, delay: Duration) -> ScheduledMessageId
+    where
+        A: Message<M, Reply = R>,
+        M: Send + 'static,
+    {
+        let reply = self.reply.take().map(ReplySender::boxed);
+        let options =
+            MessageOptions::boxed(self.deadline(), self.reply_options(), self.sender_id());
+        self.actor_ref
+            .scheduled()
+            .schedule(delay, move |actor_ref| async move {
+                let signal = Signal::Message {
+                    message: Box::new(msg),
+                    actor_ref: actor_ref.clone(),
+                    reply,
+                    sent_within_actor: true,
+                    options,
+                };
+                if actor_ref.mailbox().send::<()>(signal).await.is_err() {
+                    warn!(
+                        id = %actor_ref.id(),
+                        message_type = %any::type_name::<M>(),
+                        "failed to retry message, dead-lettering"
+                    );
+                }
+            })
+    }
+
+    /// Returns the stats of each actor linked to the current actor, such as its mailbox length and processed count.
+    ///
+    /// Links are bidirectional, so this includes every linked actor, not just ones spawned by the current actor.
+    /// An actor which was itself spawned with [`spawn_link`](crate::actor::spawn_link) will see its parent, and
+    /// any other actor linked to it, alongside its children.
+    ///
+    /// This is useful for supervisors which want to observe the backpressure of the workers they link to,
+    /// for example to scale a pool of workers up or down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor)]
+    /// struct Supervisor;
+    ///
+    /// #[derive(Actor)]
+    /// struct Worker;
+    ///
+    /// struct QueueDepth;
+    ///
+    /// impl Message<QueueDepth> for Supervisor {
+    ///     type Reply = usize;
+    ///
+    ///     async fn handle(&mut self, _: QueueDepth, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         // The supervisor isn't linked to a parent, so its only links are its workers
+    ///         ctx.link_stats()
+    ///             .await
+    ///             .values()
+    ///             .map(|stats| stats.mailbox_len)
+    ///             .sum()
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let supervisor_ref = kameo::spawn(Supervisor);
+    /// let worker_ref = kameo::actor::spawn_link(&supervisor_ref, Worker).await;
+    /// let queue_depth = supervisor_ref.ask(QueueDepth).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn link_stats(&self) -> HashMap<ActorID, ActorStats> {
+        self.actor_ref
+            .links
+            .lock()
+            .await
+            .iter()
+            .map(|(id, link)| (*id, link.stats()))
+            .collect()
+    }
+
+    /// Attaches a serialized payload to the current message in the actor's recent message trace.
+    ///
+    /// Messages are always recorded by their type name, this allows handlers to additionally record the
+    /// message contents for post-mortem debugging, without requiring messages to be serializable.
+    #[cfg(feature = "message-trace")]
+    pub fn trace_payload(&self, payload: impl Into<Vec<u8>>) {
+        self.actor_ref
+            .message_trace
+            .set_last_payload(payload.into());
+    }
+
     /// Forwards the message to another actor, returning a [ForwardedReply].
     ///
     /// The message will be sent handled by another actor without blocking the current actor.
@@ -197,6 +1067,15 @@ where
 
     /// Casts the type to a `Box<dyn Any>`.
     fn as_any(self: Box<Self>) -> Box<dyn any::Any>;
+
+    /// Returns the type name of the message.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the lane of the message, see [`Message::lane`].
+    fn lane(&self) -> usize;
+
+    /// Returns the size hint of the message, see [`Message::size_hint`].
+    fn size_hint(&self) -> usize;
 }
 
 impl<A, T> DynMessage<A> for T
@@ -211,16 +1090,76 @@ where
         tx: Option<BoxReplySender>,
     ) -> BoxFuture<'_, Option<BoxDebug>> {
         async move {
+            let previous = actor_ref.counters.previous.replace(&*self);
             let mut reply_sender = tx.map(ReplySender::new);
             let ctx: Context<'_, A, <A as Message<T>>::Reply> =
-                Context::new(actor_ref, &mut reply_sender);
+                Context::new(actor_ref, &mut reply_sender, previous.as_deref());
             let reply = Message::handle(state, *self, ctx).await;
-            if let Some(tx) = reply_sender.take() {
-                tx.send(reply.into_value());
-                None
-            } else {
-                reply.into_boxed_err()
-            }
+            reply.send_reply(reply_sender.take())
+        }
+        .boxed()
+    }
+
+    fn as_any(self: Box<Self>) -> Box<dyn any::Any> {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        any::type_name::<T>()
+    }
+
+    fn lane(&self) -> usize {
+        <A as Message<T>>::lane()
+    }
+
+    fn size_hint(&self) -> usize {
+        <A as Message<T>>::size_hint(self)
+    }
+}
+
+/// An object safe read-only message which can be handled by an actor `A`.
+///
+/// This trait is implemented for all types which implement [`ReadMessage`].
+pub trait DynReadMessage<A>
+where
+    Self: Send,
+    A: Actor,
+{
+    /// Handles the dyn message with shared access to the provided actor state.
+    fn handle_dyn_read<'a>(
+        self: Box<Self>,
+        state: &'a A,
+        actor_ref: ActorRef<A>,
+        tx: Option<BoxReplySender>,
+    ) -> BoxFuture<'a, Option<BoxDebug>>;
+
+    /// Casts the type to a `Box<dyn Any>`.
+    fn as_any(self: Box<Self>) -> Box<dyn any::Any>;
+
+    /// Returns the type name of the message.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the size hint of the message, see [`ReadMessage::size_hint`].
+    fn size_hint(&self) -> usize;
+}
+
+impl<A, T> DynReadMessage<A> for T
+where
+    A: Actor + ReadMessage<T>,
+    T: Send + 'static,
+{
+    fn handle_dyn_read<'a>(
+        self: Box<Self>,
+        state: &'a A,
+        actor_ref: ActorRef<A>,
+        tx: Option<BoxReplySender>,
+    ) -> BoxFuture<'a, Option<BoxDebug>> {
+        async move {
+            let mut reply_sender = tx.map(ReplySender::new);
+            let ctx: Context<'_, A, <A as ReadMessage<T>>::Reply> =
+                Context::new(actor_ref, &mut reply_sender, None);
+            let reply = ReadMessage::handle(state, *self, ctx).await;
+            reply.send_reply(reply_sender.take())
         }
         .boxed()
     }
@@ -228,4 +1167,12 @@ where
     fn as_any(self: Box<Self>) -> Box<dyn any::Any> {
         self
     }
+
+    fn type_name(&self) -> &'static str {
+        any::type_name::<T>()
+    }
+
+    fn size_hint(&self) -> usize {
+        <A as ReadMessage<T>>::size_hint(self)
+    }
 }
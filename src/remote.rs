@@ -40,6 +40,9 @@
 //! - [`SwarmFuture`]: A future that holds the response from the actor swarm.
 //! - [`RemoteActor`]: A trait for identifying remote actors via a unique ID.
 //! - [`RemoteMessage`]: A trait for identifying remote messages via a unique ID.
+//! - [`discover`]: Discovers peers on the local network with mDNS, requires the `discovery` feature.
+//! - [`ReconnectPolicy`]: Configures how a [`RemoteActorRef`](crate::actor::RemoteActorRef) retries requests while its peer is unreachable.
+//! - [`Codec`]: A trait for the serialization format of remote messages, with [`MessagePack`] and [`Json`] implementations.
 //!
 //! ### Re-exports
 //!
@@ -54,17 +57,23 @@ use std::{
 
 use _internal::{RemoteMessageFns, RemoteMessageRegistrationID, REMOTE_MESSAGES};
 pub use libp2p::swarm::dial_opts;
+#[cfg(feature = "discovery")]
+pub use libp2p::Multiaddr;
 pub use libp2p::PeerId;
 pub use libp2p_identity::Keypair;
 use once_cell::sync::Lazy;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
 
 use crate::{actor::ActorID, error::RemoteSendError};
 
 #[doc(hidden)]
 pub mod _internal;
+mod codec;
 mod swarm;
+#[cfg(unix)]
+pub mod uds;
 
+pub use codec::*;
 pub use swarm::*;
 
 pub(crate) static REMOTE_REGISTRY: Lazy<Mutex<HashMap<ActorID, Box<dyn any::Any + Send + Sync>>>> =
@@ -119,10 +128,287 @@ pub trait RemoteActor {
 /// Each remote message type must implement this trait and provide a unique identifier string (`REMOTE_ID`).
 /// The unique ID ensures that each message type is recognized correctly during message passing between nodes.
 ///
-/// This trait is typically implemented automatically with the [`#[remote_message]`](crate::remote_message) macro.
+/// This trait is typically implemented automatically with the [`#[remote_message]`](crate::remote_message) macro,
+/// or the [`remote_messages!`](crate::remote_messages) macro.
 pub trait RemoteMessage<M> {
     /// The remote identifier string.
     const REMOTE_ID: &'static str;
+
+    /// The codec used to serialize the message, its reply, and handler errors.
+    ///
+    /// The `#[remote_message]` macro uses [`MessagePack`] unless another codec is specified.
+    type Codec: Codec;
+}
+
+/// Registers the messages an actor handles to be supported with remote messages.
+///
+/// This is an alternative to placing [`#[remote_message]`](crate::remote_message) on each message impl,
+/// listing every remote message of an actor in one place. Each message type is checked at compile time to be
+/// serializable along with its reply, so a missing bound is a build error rather than a failure when the message
+/// is received. Messages are serialized with [`MessagePack`].
+///
+/// The remote ID of each message defaults to its type as written, such as `"Ping"`. Since the ID must match on
+/// every node, a message should be written the same way everywhere it's registered, or given an explicit ID
+/// with `Message = "id"`.
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Context, Message};
+/// use kameo::remote::RemoteMessage;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(kameo::Actor, kameo::RemoteActor)]
+/// struct Pinger;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Ping;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Reset;
+///
+/// impl Message<Ping> for Pinger {
+///     type Reply = String;
+///
+///     async fn handle(&mut self, _: Ping, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         "pong".to_string()
+///     }
+/// }
+///
+/// impl Message<Reset> for Pinger {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, _: Reset, _: Context<'_, Self, Self::Reply>) -> Self::Reply {}
+/// }
+///
+/// kameo::remote_messages!(Pinger: [Ping, Reset = "pinger-reset"]);
+///
+/// assert_eq!(<Pinger as RemoteMessage<Ping>>::REMOTE_ID, "Ping");
+/// assert_eq!(<Pinger as RemoteMessage<Reset>>::REMOTE_ID, "pinger-reset");
+/// ```
+///
+/// Messages which cannot be serialized fail to compile:
+///
+/// ```compile_fail
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor, kameo::RemoteActor)]
+/// struct Pinger;
+///
+/// struct Ping;
+///
+/// impl Message<Ping> for Pinger {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, _: Ping, _: Context<'_, Self, Self::Reply>) -> Self::Reply {}
+/// }
+///
+/// kameo::remote_messages!(Pinger: [Ping]);
+/// ```
+#[macro_export]
+macro_rules! remote_messages {
+    ($actor:ty: [$($msg:ty $(= $id:literal)?),* $(,)?]) => {
+        $(
+            $crate::remote_messages!(@register $actor, $msg, $crate::remote_messages!(@id $msg $(, $id)?));
+        )*
+    };
+    (@id $msg:ty) => {
+        ::std::stringify!($msg)
+    };
+    (@id $msg:ty, $id:literal) => {
+        $id
+    };
+    (@register $actor:ty, $msg:ty, $id:expr) => {
+        #[automatically_derived]
+        impl $crate::remote::RemoteMessage<$msg> for $actor {
+            const REMOTE_ID: &'static str = $id;
+            type Codec = $crate::remote::MessagePack;
+        }
+
+        const _: () = {
+            $crate::remote::_internal::assert_remote_message::<$actor, $msg>();
+
+            #[$crate::remote::_internal::linkme::distributed_slice(
+                $crate::remote::_internal::REMOTE_MESSAGES
+            )]
+            #[linkme(crate = $crate::remote::_internal::linkme)]
+            static REG: (
+                $crate::remote::_internal::RemoteMessageRegistrationID<'static>,
+                $crate::remote::_internal::RemoteMessageFns,
+            ) = (
+                $crate::remote::_internal::RemoteMessageRegistrationID {
+                    actor_remote_id: <$actor as $crate::remote::RemoteActor>::REMOTE_ID,
+                    message_remote_id: <$actor as $crate::remote::RemoteMessage<$msg>>::REMOTE_ID,
+                },
+                $crate::remote::_internal::RemoteMessageFns {
+                    ask: (|actor_id, msg, mailbox_timeout, reply_timeout| {
+                        ::std::boxed::Box::pin($crate::remote::_internal::ask::<$actor, $msg>(
+                            actor_id,
+                            msg,
+                            mailbox_timeout,
+                            reply_timeout,
+                        ))
+                    }) as $crate::remote::_internal::RemoteAskFn,
+                    try_ask: (|actor_id, msg, reply_timeout| {
+                        ::std::boxed::Box::pin($crate::remote::_internal::try_ask::<$actor, $msg>(
+                            actor_id,
+                            msg,
+                            reply_timeout,
+                        ))
+                    }) as $crate::remote::_internal::RemoteTryAskFn,
+                    tell: (|actor_id, msg, mailbox_timeout| {
+                        ::std::boxed::Box::pin($crate::remote::_internal::tell::<$actor, $msg>(
+                            actor_id,
+                            msg,
+                            mailbox_timeout,
+                        ))
+                    }) as $crate::remote::_internal::RemoteTellFn,
+                    try_tell: (|actor_id, msg| {
+                        ::std::boxed::Box::pin($crate::remote::_internal::try_tell::<$actor, $msg>(
+                            actor_id, msg,
+                        ))
+                    }) as $crate::remote::_internal::RemoteTryTellFn,
+                },
+            );
+        };
+    };
+}
+
+/// Returns a stream of peers discovered on the local network, or `None` if the actor swarm has not been bootstrapped.
+///
+/// See [`ActorSwarm::discover`] for more information.
+#[cfg(feature = "discovery")]
+pub fn discover() -> Option<futures::stream::BoxStream<'static, PeerInfo>> {
+    ActorSwarm::get().map(ActorSwarm::discover)
+}
+
+/// Controls how a [`RemoteActorRef`](crate::actor::RemoteActorRef) retries requests while its peer is unreachable.
+///
+/// When dialing the peer fails, the request is retried with exponential backoff, up to `max_retries` times.
+/// While retrying, the request is buffered by the sender, with at most `buffer` requests waiting per actor ref.
+/// Requests which exceed the buffer, or run out of retries, fail with [`RemoteSendError::Disconnected`].
+///
+/// Only requests which were never sent are retried. If the connection closes after a request was sent,
+/// it is not known whether the remote actor received it, so the request fails with
+/// [`RemoteSendError::ConnectionClosed`] rather than risk processing it twice. This means in-flight asks
+/// are never matched with a reply from a different connection.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use kameo::actor::RemoteActorRef;
+/// use kameo::remote::ReconnectPolicy;
+///
+/// # #[derive(kameo::Actor, kameo::RemoteActor)]
+/// # struct MyActor;
+/// #
+/// # tokio_test::block_on(async {
+/// let remote_actor_ref = RemoteActorRef::<MyActor>::lookup("my_actor")
+///     .await?
+///     .unwrap()
+///     .with_reconnect(
+///         ReconnectPolicy::new()
+///             .max_retries(10)
+///             .backoff(Duration::from_millis(50), Duration::from_secs(2))
+///             .buffer(128),
+///     );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    buffer: usize,
+}
+
+impl ReconnectPolicy {
+    /// Creates a new reconnect policy, retrying 5 times with a backoff from 100ms up to 5s,
+    /// and buffering up to 64 requests.
+    pub fn new() -> Self {
+        ReconnectPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            buffer: 64,
+        }
+    }
+
+    /// Sets the maximum number of times a request is retried before failing.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the backoff before the first retry, and the maximum backoff it doubles up to.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sets the maximum number of requests which can wait for a reconnection at once.
+    ///
+    /// A buffer of `0` fails requests immediately when the peer is unreachable.
+    pub fn buffer(mut self, buffer: usize) -> Self {
+        self.buffer = buffer;
+        self
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy::new()
+    }
+}
+
+/// The reconnect state shared by clones of a `RemoteActorRef`.
+#[derive(Debug)]
+pub(crate) struct Reconnect {
+    policy: ReconnectPolicy,
+    pending: Semaphore,
+}
+
+impl Reconnect {
+    pub(crate) fn new(policy: ReconnectPolicy) -> Self {
+        Reconnect {
+            policy,
+            pending: Semaphore::new(policy.buffer),
+        }
+    }
+
+    /// Sends a request until it is delivered, retrying dial failures according to the policy.
+    pub(crate) async fn send<Fut>(&self, mut send: impl FnMut() -> Fut) -> SwarmResp
+    where
+        Fut: std::future::Future<Output = SwarmResp>,
+    {
+        let mut permit = None;
+        let mut backoff = self.policy.initial_backoff;
+        for _ in 0..self.policy.max_retries {
+            match send().await {
+                SwarmResp::OutboundFailure(RemoteSendError::DialFailure) => {}
+                resp => return resp,
+            }
+            if permit.is_none() {
+                match self.pending.try_acquire() {
+                    Ok(acquired) => permit = Some(acquired),
+                    Err(_) => return SwarmResp::OutboundFailure(RemoteSendError::Disconnected),
+                }
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.policy.max_backoff);
+        }
+
+        match send().await {
+            SwarmResp::OutboundFailure(RemoteSendError::DialFailure) => {
+                SwarmResp::OutboundFailure(RemoteSendError::Disconnected)
+            }
+            resp => resp,
+        }
+    }
 }
 
 pub(crate) async fn ask(
@@ -14,7 +14,7 @@ use crate::request::{
 };
 use crate::{Actor, Reply};
 
-use super::REMOTE_REGISTRY;
+use super::{Codec, RemoteActor, RemoteMessage, REMOTE_REGISTRY};
 
 #[linkme::distributed_slice]
 pub static REMOTE_MESSAGES: [(RemoteMessageRegistrationID<'static>, RemoteMessageFns)];
@@ -49,6 +49,17 @@ pub type RemoteTellFn = fn(
 pub type RemoteTryTellFn =
     fn(actor_id: ActorID, msg: Vec<u8>) -> BoxFuture<'static, Result<(), RemoteSendError<Vec<u8>>>>;
 
+/// Fails to compile unless a message and its reply can be sent between nodes, used by
+/// [`remote_messages!`](crate::remote_messages).
+pub const fn assert_remote_message<A, M>()
+where
+    A: Actor + Message<M> + RemoteActor + RemoteMessage<M>,
+    M: Serialize + DeserializeOwned + Send + 'static,
+    <A::Reply as Reply>::Ok: Serialize + DeserializeOwned,
+    <A::Reply as Reply>::Error: Serialize + DeserializeOwned,
+{
+}
+
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct RemoteMessageRegistrationID<'a> {
     pub actor_remote_id: &'a str,
@@ -62,7 +73,7 @@ pub async fn ask<A, M>(
     reply_timeout: Option<Duration>,
 ) -> Result<Vec<u8>, RemoteSendError<Vec<u8>>>
 where
-    A: Actor + Message<M>,
+    A: Actor + Message<M> + RemoteMessage<M>,
     M: DeserializeOwned + Send + 'static,
     <A::Reply as Reply>::Ok: Serialize,
     <A::Reply as Reply>::Error: Serialize,
@@ -83,7 +94,10 @@ where
             .ok_or(RemoteSendError::BadActorType)?
             .clone()
     };
-    let msg: M = rmp_serde::decode::from_slice(&msg)
+    if A::max_message_size().is_some_and(|max| msg.len() > max) {
+        return Err(RemoteSendError::TooLarge);
+    }
+    let msg: M = <A as RemoteMessage<M>>::Codec::decode(&msg)
         .map_err(|err| RemoteSendError::DeserializeMessage(err.to_string()))?;
 
     let res = actor_ref
@@ -92,10 +106,10 @@ where
         .send()
         .await;
     match res {
-        Ok(reply) => Ok(rmp_serde::to_vec_named(&reply)
+        Ok(reply) => Ok(<A as RemoteMessage<M>>::Codec::encode(&reply)
             .map_err(|err| RemoteSendError::SerializeReply(err.to_string()))?),
         Err(err) => Err(RemoteSendError::from(err)
-            .map_err(|err| match rmp_serde::to_vec_named(&err) {
+            .map_err(|err| match <A as RemoteMessage<M>>::Codec::encode(&err) {
                 Ok(payload) => RemoteSendError::HandlerError(payload),
                 Err(err) => RemoteSendError::SerializeHandlerError(err.to_string()),
             })
@@ -109,7 +123,7 @@ pub async fn try_ask<A, M>(
     reply_timeout: Option<Duration>,
 ) -> Result<Vec<u8>, RemoteSendError<Vec<u8>>>
 where
-    A: Actor + Message<M>,
+    A: Actor + Message<M> + RemoteMessage<M>,
     M: DeserializeOwned + Send + 'static,
     <A::Reply as Reply>::Ok: Serialize,
     <A::Reply as Reply>::Error: Serialize,
@@ -133,7 +147,10 @@ where
             .ok_or(RemoteSendError::BadActorType)?
             .clone()
     };
-    let msg: M = rmp_serde::decode::from_slice(&msg)
+    if A::max_message_size().is_some_and(|max| msg.len() > max) {
+        return Err(RemoteSendError::TooLarge);
+    }
+    let msg: M = <A as RemoteMessage<M>>::Codec::decode(&msg)
         .map_err(|err| RemoteSendError::DeserializeMessage(err.to_string()))?;
 
     let res = actor_ref
@@ -142,10 +159,10 @@ where
         .try_send()
         .await;
     match res {
-        Ok(reply) => Ok(rmp_serde::to_vec_named(&reply)
+        Ok(reply) => Ok(<A as RemoteMessage<M>>::Codec::encode(&reply)
             .map_err(|err| RemoteSendError::SerializeReply(err.to_string()))?),
         Err(err) => Err(RemoteSendError::from(err)
-            .map_err(|err| match rmp_serde::to_vec_named(&err) {
+            .map_err(|err| match <A as RemoteMessage<M>>::Codec::encode(&err) {
                 Ok(payload) => RemoteSendError::HandlerError(payload),
                 Err(err) => RemoteSendError::SerializeHandlerError(err.to_string()),
             })
@@ -159,7 +176,7 @@ pub async fn tell<A, M>(
     mailbox_timeout: Option<Duration>,
 ) -> Result<(), RemoteSendError<Vec<u8>>>
 where
-    A: Actor + Message<M>,
+    A: Actor + Message<M> + RemoteMessage<M>,
     M: DeserializeOwned + Send + 'static,
     <A::Reply as Reply>::Error: Serialize,
     for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, MaybeRequestTimeout>:
@@ -174,7 +191,10 @@ where
             .ok_or(RemoteSendError::BadActorType)?
             .clone()
     };
-    let msg: M = rmp_serde::decode::from_slice(&msg)
+    if A::max_message_size().is_some_and(|max| msg.len() > max) {
+        return Err(RemoteSendError::TooLarge);
+    }
+    let msg: M = <A as RemoteMessage<M>>::Codec::decode(&msg)
         .map_err(|err| RemoteSendError::DeserializeMessage(err.to_string()))?;
 
     let res = actor_ref
@@ -185,7 +205,7 @@ where
     match res {
         Ok(()) => Ok(()),
         Err(err) => Err(RemoteSendError::from(err)
-            .map_err(|err| match rmp_serde::to_vec_named(&err) {
+            .map_err(|err| match <A as RemoteMessage<M>>::Codec::encode(&err) {
                 Ok(payload) => RemoteSendError::HandlerError(payload),
                 Err(err) => RemoteSendError::SerializeHandlerError(err.to_string()),
             })
@@ -195,7 +215,7 @@ where
 
 pub async fn try_tell<A, M>(actor_id: ActorID, msg: Vec<u8>) -> Result<(), RemoteSendError<Vec<u8>>>
 where
-    A: Actor + Message<M>,
+    A: Actor + Message<M> + RemoteMessage<M>,
     M: DeserializeOwned + Send + 'static,
     <A::Reply as Reply>::Error: Serialize,
     for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, MaybeRequestTimeout>:
@@ -210,7 +230,10 @@ where
             .ok_or(RemoteSendError::BadActorType)?
             .clone()
     };
-    let msg: M = rmp_serde::decode::from_slice(&msg)
+    if A::max_message_size().is_some_and(|max| msg.len() > max) {
+        return Err(RemoteSendError::TooLarge);
+    }
+    let msg: M = <A as RemoteMessage<M>>::Codec::decode(&msg)
         .map_err(|err| RemoteSendError::DeserializeMessage(err.to_string()))?;
 
     let res = actor_ref
@@ -221,7 +244,7 @@ where
     match res {
         Ok(()) => Ok(()),
         Err(err) => Err(RemoteSendError::from(err)
-            .map_err(|err| match rmp_serde::to_vec_named(&err) {
+            .map_err(|err| match <A as RemoteMessage<M>>::Codec::encode(&err) {
                 Ok(payload) => RemoteSendError::HandlerError(payload),
                 Err(err) => RemoteSendError::SerializeHandlerError(err.to_string()),
             })
@@ -0,0 +1,88 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::BoxError;
+
+/// A serialization format used to encode remote messages, replies, and handler errors.
+///
+/// The codec is selected per message type with the [`RemoteMessage::Codec`](super::RemoteMessage::Codec)
+/// associated type, which can be set with `#[remote_message("id", codec = Json)]`.
+/// Since both nodes share the message type, they always agree on the format.
+///
+/// Built-in codecs:
+/// - [`MessagePack`]: A compact binary format, used by default.
+/// - [`Json`]: A text format, useful for interop with non-Rust services.
+///
+/// # Example
+///
+/// ```
+/// use kameo::remote::{Codec, Json, MessagePack};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Greet {
+///     name: String,
+/// }
+///
+/// let msg = Greet { name: "kameo".to_string() };
+///
+/// let bytes = MessagePack::encode(&msg)?;
+/// assert_eq!(MessagePack::decode::<Greet>(&bytes)?, msg);
+///
+/// let bytes = Json::encode(&msg)?;
+/// assert_eq!(bytes, br#"{"name":"kameo"}"#);
+/// assert_eq!(Json::decode::<Greet>(&bytes)?, msg);
+/// # Ok::<(), kameo::error::BoxError>(())
+/// ```
+pub trait Codec {
+    /// Encodes a value into bytes.
+    fn encode<T>(value: &T) -> Result<Vec<u8>, BoxError>
+    where
+        T: Serialize + ?Sized;
+
+    /// Decodes a value from bytes.
+    fn decode<T>(bytes: &[u8]) -> Result<T, BoxError>
+    where
+        T: DeserializeOwned;
+}
+
+/// The [MessagePack](https://msgpack.org) codec, encoding structs as maps with named fields.
+///
+/// This is the default codec for remote messages.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MessagePack;
+
+impl Codec for MessagePack {
+    fn encode<T>(value: &T) -> Result<Vec<u8>, BoxError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(rmp_serde::to_vec_named(value)?)
+    }
+
+    fn decode<T>(bytes: &[u8]) -> Result<T, BoxError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(rmp_serde::decode::from_slice(bytes)?)
+    }
+}
+
+/// The [JSON](https://www.json.org) codec.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Json;
+
+impl Codec for Json {
+    fn encode<T>(value: &T) -> Result<Vec<u8>, BoxError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn decode<T>(bytes: &[u8]) -> Result<T, BoxError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
@@ -67,6 +67,8 @@ static ACTOR_SWARM: OnceCell<ActorSwarm> = OnceCell::new();
 pub struct ActorSwarm {
     swarm_tx: SwarmSender,
     local_peer_id: Intern<PeerId>,
+    #[cfg(feature = "discovery")]
+    discovered_tx: tokio::sync::broadcast::Sender<PeerInfo>,
 }
 
 impl ActorSwarm {
@@ -123,18 +125,32 @@ impl ActorSwarm {
             .build();
 
         Ok(ACTOR_SWARM.get_or_init(move || {
-            let local_peer_id = Intern::new(swarm.local_peer_id().clone());
+            let local_peer_id = Intern::new(*swarm.local_peer_id());
 
             let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
             let swarm_tx = SwarmSender(cmd_tx);
+            #[cfg(feature = "discovery")]
+            let (discovered_tx, _) = tokio::sync::broadcast::channel(64);
             tokio::spawn({
                 let swarm_tx = swarm_tx.clone();
-                async move { SwarmActor::new(swarm, swarm_tx, cmd_rx).run().await }
+                #[cfg(feature = "discovery")]
+                let discovered_tx = discovered_tx.clone();
+                async move {
+                    #[allow(unused_mut)]
+                    let mut swarm_actor = SwarmActor::new(swarm, swarm_tx, cmd_rx);
+                    #[cfg(feature = "discovery")]
+                    {
+                        swarm_actor.discovered_tx = Some(discovered_tx);
+                    }
+                    swarm_actor.run().await
+                }
             });
 
             ActorSwarm {
                 swarm_tx,
                 local_peer_id,
+                #[cfg(feature = "discovery")]
+                discovered_tx,
             }
         }))
     }
@@ -197,6 +213,45 @@ impl ActorSwarm {
         &self.local_peer_id
     }
 
+    /// Returns a stream of peers discovered on the local network with mDNS.
+    ///
+    /// Discovered peers are added to the routing table, so actors they register can be looked up
+    /// with [`RemoteActorRef::lookup`]. Only peers discovered after subscribing are yielded,
+    /// and a peer may be yielded again if its addresses change.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use kameo::remote::ActorSwarm;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_swarm = ActorSwarm::bootstrap()?;
+    /// actor_swarm.listen_on("/ip4/0.0.0.0/udp/0/quic-v1".parse()?).await?;
+    ///
+    /// let mut peers = actor_swarm.discover();
+    /// while let Some(peer) = peers.next().await {
+    ///     println!("discovered {} at {:?}", peer.peer_id, peer.addresses);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg(feature = "discovery")]
+    pub fn discover(&self) -> futures::stream::BoxStream<'static, PeerInfo> {
+        Box::pin(futures::stream::unfold(
+            self.discovered_tx.subscribe(),
+            |mut rx| async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(peer) => return Some((peer, rx)),
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        ))
+    }
+
     /// Dials a peer using the provided dialing options.
     ///
     /// This method can be used to connect to a known or unknown peer, specified by the options
@@ -352,6 +407,8 @@ struct SwarmActor {
         HashMap<kad::QueryId, oneshot::Sender<Result<kad::PeerRecord, kad::GetRecordError>>>,
     put_queries: HashMap<kad::QueryId, oneshot::Sender<kad::PutRecordResult>>,
     requests: HashMap<OutboundRequestId, oneshot::Sender<SwarmResp>>,
+    #[cfg(feature = "discovery")]
+    discovered_tx: Option<tokio::sync::broadcast::Sender<PeerInfo>>,
 }
 
 impl SwarmActor {
@@ -367,6 +424,8 @@ impl SwarmActor {
             get_queries: HashMap::new(),
             put_queries: HashMap::new(),
             requests: HashMap::new(),
+            #[cfg(feature = "discovery")]
+            discovered_tx: None,
         }
     }
 
@@ -518,12 +577,25 @@ impl SwarmActor {
                 trace!("listening on {address:?}");
             }
             SwarmEvent::Behaviour(BehaviourEvent::Mdns(mdns::Event::Discovered(list))) => {
+                #[cfg(feature = "discovery")]
+                let mut discovered: HashMap<PeerId, Vec<Multiaddr>> = HashMap::new();
                 for (peer_id, multiaddr) in list {
+                    #[cfg(feature = "discovery")]
+                    discovered
+                        .entry(peer_id)
+                        .or_default()
+                        .push(multiaddr.clone());
                     self.swarm
                         .behaviour_mut()
                         .kademlia
                         .add_address(&peer_id, multiaddr);
                 }
+                #[cfg(feature = "discovery")]
+                if let Some(tx) = &self.discovered_tx {
+                    for (peer_id, addresses) in discovered {
+                        let _ = tx.send(PeerInfo { peer_id, addresses });
+                    }
+                }
             }
             SwarmEvent::Behaviour(BehaviourEvent::Kademlia(
                 kad::Event::OutboundQueryProgressed { id, result, .. },
@@ -588,7 +660,7 @@ impl SwarmActor {
                             immediate,
                         )
                         .await;
-                        let _ = tx.send(SwarmCommand::SendAskResponse { result, channel });
+                        tx.send(SwarmCommand::SendAskResponse { result, channel });
                     });
                 }
                 SwarmReq::Tell {
@@ -610,7 +682,7 @@ impl SwarmActor {
                             immediate,
                         )
                         .await;
-                        let _ = tx.send(SwarmCommand::SendTellResponse { result, channel });
+                        tx.send(SwarmCommand::SendTellResponse { result, channel });
                     });
                 }
             },
@@ -750,7 +822,17 @@ impl<'a> ActorRegistration<'a> {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A peer discovered on the local network, see [`ActorSwarm::discover`].
+#[cfg(feature = "discovery")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerInfo {
+    /// The peer's ID.
+    pub peer_id: PeerId,
+    /// The addresses the peer is reachable at.
+    pub addresses: Vec<Multiaddr>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum SwarmReq {
     Ask {
         actor_id: ActorID,
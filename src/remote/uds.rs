@@ -0,0 +1,384 @@
+//! Request/response with registered actors over a Unix domain socket.
+//!
+//! This is a lightweight alternative to the actor swarm for a client process talking to a long-running daemon
+//! on the same machine, such as a CLI tool controlling a system service. No peer discovery or networking is involved:
+//! the daemon serves actors registered by name in a [`UdsRegistry`] with [`serve_uds`], and clients connect with a [`UdsClient`].
+//!
+//! Messages, replies, and handler errors are encoded with the message's [`Codec`], just as with remote actors,
+//! so messages must be registered with the [`#[remote_message]`](crate::remote_message) macro.
+//! Requests on a single connection are processed one at a time, so clients wanting concurrent requests
+//! should open multiple connections.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::message::{Context, Message};
+//! use kameo::remote::uds::{serve_uds, UdsClient, UdsRegistry};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(kameo::Actor, kameo::RemoteActor)]
+//! struct Daemon;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Status;
+//!
+//! #[kameo::remote_message("status")]
+//! impl Message<Status> for Daemon {
+//!     type Reply = String;
+//!
+//!     async fn handle(&mut self, _: Status, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+//!         "running".to_string()
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let path = std::env::temp_dir().join(format!("kameo-uds-{}.sock", std::process::id()));
+//! # let _ = std::fs::remove_file(&path);
+//!
+//! // In the daemon
+//! let mut registry = UdsRegistry::new();
+//! registry.register("daemon", &kameo::spawn(Daemon));
+//! tokio::spawn(serve_uds(path.clone(), registry));
+//! # tokio::task::yield_now().await;
+//!
+//! // In the client
+//! let client = UdsClient::connect(&path).await?;
+//! let status = client.ask::<Daemon, _>("daemon", &Status).await?;
+//! assert_eq!(status, "running");
+//! # std::fs::remove_file(&path)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::{
+    any::Any, borrow::Cow, collections::HashMap, fmt, io, path::Path, sync::Arc, time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{UnixListener, UnixStream},
+    sync::Mutex,
+};
+use tracing::{debug, warn};
+
+use crate::{
+    actor::{ActorID, ActorRef},
+    error::RemoteSendError,
+    message::Message,
+    Actor, Reply,
+};
+
+use super::{Codec, MessagePack, RemoteActor, RemoteMessage, REMOTE_REGISTRY};
+
+/// The maximum size of a single request or response frame.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Actors served over a Unix domain socket by [`serve_uds`], registered by name.
+///
+/// Names are only visible to clients of the socket, and are separate from names registered in the actor swarm.
+#[derive(Default)]
+pub struct UdsRegistry {
+    actors: HashMap<String, RegisteredActor>,
+}
+
+struct RegisteredActor {
+    id: ActorID,
+    actor_remote_id: &'static str,
+    boxed_actor_ref: Box<dyn Fn() -> Box<dyn Any + Send + Sync> + Send + Sync>,
+}
+
+impl UdsRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        UdsRegistry::default()
+    }
+
+    /// Registers an actor under a name, replacing any actor previously registered under it.
+    pub fn register<A>(&mut self, name: impl Into<String>, actor_ref: &ActorRef<A>)
+    where
+        A: Actor + RemoteActor,
+    {
+        let actor_ref = actor_ref.clone();
+        self.actors.insert(
+            name.into(),
+            RegisteredActor {
+                id: actor_ref.id(),
+                actor_remote_id: A::REMOTE_ID,
+                boxed_actor_ref: Box::new(move || Box::new(actor_ref.clone())),
+            },
+        );
+    }
+}
+
+impl fmt::Debug for UdsRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdsRegistry")
+            .field("actors", &self.actors.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum UdsRequest {
+    Ask {
+        name: String,
+        actor_remote_id: Cow<'static, str>,
+        message_remote_id: Cow<'static, str>,
+        payload: Vec<u8>,
+        mailbox_timeout: Option<Duration>,
+        reply_timeout: Option<Duration>,
+    },
+    Tell {
+        name: String,
+        actor_remote_id: Cow<'static, str>,
+        message_remote_id: Cow<'static, str>,
+        payload: Vec<u8>,
+        mailbox_timeout: Option<Duration>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+enum UdsResponse {
+    Ask(Result<Vec<u8>, RemoteSendError<Vec<u8>>>),
+    Tell(Result<(), RemoteSendError<Vec<u8>>>),
+}
+
+/// Binds a Unix domain socket at `path`, and serves the actors in a registry to clients connecting to it
+/// until accepting a connection fails.
+///
+/// The socket is bound when the future is first polled, and fails if a file already exists at `path`.
+/// Each connection is handled in its own task. Registered actors are kept alive while the server is running.
+pub async fn serve_uds(path: impl AsRef<Path>, registry: UdsRegistry) -> io::Result<()> {
+    let listener = UnixListener::bind(path)?;
+    {
+        let mut remote_actors = REMOTE_REGISTRY.lock().await;
+        for actor in registry.actors.values() {
+            remote_actors.insert(actor.id, (actor.boxed_actor_ref)());
+        }
+    }
+    let registry = Arc::new(registry);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, &registry).await {
+                debug!(%err, "unix socket connection closed with an error");
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut stream: UnixStream, registry: &UdsRegistry) -> io::Result<()> {
+    while let Some(frame) = read_frame(&mut stream).await? {
+        let req: UdsRequest = MessagePack::decode(&frame)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let resp = match req {
+            UdsRequest::Ask {
+                name,
+                actor_remote_id,
+                message_remote_id,
+                payload,
+                mailbox_timeout,
+                reply_timeout,
+            } => UdsResponse::Ask(match resolve(registry, &name, &actor_remote_id) {
+                Ok(actor_id) => {
+                    super::ask(
+                        actor_id,
+                        actor_remote_id,
+                        message_remote_id,
+                        payload,
+                        mailbox_timeout,
+                        reply_timeout,
+                        false,
+                    )
+                    .await
+                }
+                Err(err) => Err(err),
+            }),
+            UdsRequest::Tell {
+                name,
+                actor_remote_id,
+                message_remote_id,
+                payload,
+                mailbox_timeout,
+            } => UdsResponse::Tell(match resolve(registry, &name, &actor_remote_id) {
+                Ok(actor_id) => {
+                    super::tell(
+                        actor_id,
+                        actor_remote_id,
+                        message_remote_id,
+                        payload,
+                        mailbox_timeout,
+                        false,
+                    )
+                    .await
+                }
+                Err(err) => Err(err),
+            }),
+        };
+        let frame = MessagePack::encode(&resp).map_err(|err| {
+            warn!(%err, "failed to encode unix socket response");
+            io::Error::new(io::ErrorKind::InvalidData, err)
+        })?;
+        write_frame(&mut stream, &frame).await?;
+    }
+
+    Ok(())
+}
+
+/// Looks up the ID of a registered actor, checking it's the type the client expects.
+fn resolve(
+    registry: &UdsRegistry,
+    name: &str,
+    actor_remote_id: &str,
+) -> Result<ActorID, RemoteSendError<Vec<u8>>> {
+    let actor = registry
+        .actors
+        .get(name)
+        .ok_or(RemoteSendError::ActorNotRunning)?;
+    if actor.actor_remote_id != actor_remote_id {
+        return Err(RemoteSendError::BadActorType);
+    }
+    Ok(actor.id)
+}
+
+/// A client for asking actors served over a Unix domain socket with [`serve_uds`].
+///
+/// Requests are sent one at a time over a single connection. Transport failures are returned as
+/// [`RemoteSendError::Io`] or [`RemoteSendError::ConnectionClosed`].
+#[derive(Debug)]
+pub struct UdsClient {
+    stream: Mutex<UnixStream>,
+}
+
+impl UdsClient {
+    /// Connects to a Unix domain socket served with [`serve_uds`].
+    pub async fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(UdsClient {
+            stream: Mutex::new(UnixStream::connect(path).await?),
+        })
+    }
+
+    /// Sends a message to the actor registered under `name`, waiting for a reply.
+    ///
+    /// Fails with [`RemoteSendError::ActorNotRunning`] if no actor is registered under the name,
+    /// or [`RemoteSendError::BadActorType`] if it is not an `A`.
+    pub async fn ask<A, M>(
+        &self,
+        name: &str,
+        msg: &M,
+    ) -> Result<<A::Reply as Reply>::Ok, RemoteSendError<<A::Reply as Reply>::Error>>
+    where
+        A: Actor + Message<M> + RemoteActor + RemoteMessage<M>,
+        M: Serialize + Send + 'static,
+        <A::Reply as Reply>::Ok: DeserializeOwned,
+        <A::Reply as Reply>::Error: DeserializeOwned,
+    {
+        let req = UdsRequest::Ask {
+            name: name.to_string(),
+            actor_remote_id: Cow::Borrowed(<A as RemoteActor>::REMOTE_ID),
+            message_remote_id: Cow::Borrowed(<A as RemoteMessage<M>>::REMOTE_ID),
+            payload: <A as RemoteMessage<M>>::Codec::encode(msg)
+                .map_err(|err| RemoteSendError::SerializeMessage(err.to_string()))?,
+            mailbox_timeout: None,
+            reply_timeout: None,
+        };
+        match self.request(req).await? {
+            UdsResponse::Ask(Ok(payload)) => <A as RemoteMessage<M>>::Codec::decode(&payload)
+                .map_err(|err| RemoteSendError::DeserializeMessage(err.to_string())),
+            UdsResponse::Ask(Err(err)) => Err(err
+                .map_err(|err| match <A as RemoteMessage<M>>::Codec::decode(&err) {
+                    Ok(err) => RemoteSendError::HandlerError(err),
+                    Err(err) => RemoteSendError::DeserializeHandlerError(err.to_string()),
+                })
+                .flatten()),
+            UdsResponse::Tell(_) => Err(unexpected_response()),
+        }
+    }
+
+    /// Sends a message to the actor registered under `name`, waiting only for it to be queued in the actor's mailbox.
+    pub async fn tell<A, M>(
+        &self,
+        name: &str,
+        msg: &M,
+    ) -> Result<(), RemoteSendError<<A::Reply as Reply>::Error>>
+    where
+        A: Actor + Message<M> + RemoteActor + RemoteMessage<M>,
+        M: Serialize + Send + 'static,
+        <A::Reply as Reply>::Error: DeserializeOwned,
+    {
+        let req = UdsRequest::Tell {
+            name: name.to_string(),
+            actor_remote_id: Cow::Borrowed(<A as RemoteActor>::REMOTE_ID),
+            message_remote_id: Cow::Borrowed(<A as RemoteMessage<M>>::REMOTE_ID),
+            payload: <A as RemoteMessage<M>>::Codec::encode(msg)
+                .map_err(|err| RemoteSendError::SerializeMessage(err.to_string()))?,
+            mailbox_timeout: None,
+        };
+        match self.request(req).await? {
+            UdsResponse::Tell(res) => res.map_err(|err| {
+                err.map_err(|err| match <A as RemoteMessage<M>>::Codec::decode(&err) {
+                    Ok(err) => RemoteSendError::HandlerError(err),
+                    Err(err) => RemoteSendError::DeserializeHandlerError(err.to_string()),
+                })
+                .flatten()
+            }),
+            UdsResponse::Ask(_) => Err(unexpected_response()),
+        }
+    }
+
+    async fn request<E>(&self, req: UdsRequest) -> Result<UdsResponse, RemoteSendError<E>> {
+        let frame = MessagePack::encode(&req)
+            .map_err(|err| RemoteSendError::SerializeMessage(err.to_string()))?;
+        let mut stream = self.stream.lock().await;
+        write_frame(&mut stream, &frame)
+            .await
+            .map_err(|err| RemoteSendError::Io(Some(err)))?;
+        let frame = read_frame(&mut stream)
+            .await
+            .map_err(|err| RemoteSendError::Io(Some(err)))?
+            .ok_or(RemoteSendError::ConnectionClosed)?;
+        MessagePack::decode(&frame)
+            .map_err(|err| RemoteSendError::DeserializeMessage(err.to_string()))
+    }
+}
+
+fn unexpected_response<E>() -> RemoteSendError<E> {
+    RemoteSendError::Io(Some(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "unexpected response type",
+    )))
+}
+
+/// Reads a length prefixed frame, or returns `None` if the stream was closed between frames.
+async fn read_frame(stream: &mut UnixStream) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0; 4];
+    match stream.read_exact(&mut len).await {
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame exceeds maximum length",
+        ));
+    }
+    let mut frame = vec![0; len];
+    stream.read_exact(&mut frame).await?;
+    Ok(Some(frame))
+}
+
+async fn write_frame(stream: &mut UnixStream, frame: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(frame.len())
+        .ok()
+        .filter(|len| *len as usize <= MAX_FRAME_LEN)
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "frame exceeds maximum length")
+        })?;
+    stream.write_all(&len.to_be_bytes()).await?;
+    stream.write_all(frame).await
+}
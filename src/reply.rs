@@ -10,13 +10,15 @@
 //! It is implemented for a variety of common types, facilitating easy adoption and use.
 //! Special attention is given to the `Result` and [`DelegatedReply`] types:
 //! - Implementations for `Result` allow errors returned by actor handlers to be communicated back as
-//! [`SendError::HandlerError`], integrating closely with Rust’s error handling patterns.
+//!   [`SendError::HandlerError`], integrating closely with Rust’s error handling patterns.
 //! - The `DelegatedReply` type signifies that the actual reply will be managed by another part of the system,
-//! supporting asynchronous and decoupled communication workflows.
+//!   supporting asynchronous and decoupled communication workflows.
+//! - The [`DeferredReply`] type resolves the reply from a future spawned by the framework, freeing the actor
+//!   to process its next message while the reply is pending.
 //! - Importantly, when messages are sent asynchronously with [`tell`](crate::actor::ActorRef::tell) and an error is returned by the actor
-//! without a direct means for the caller to handle it (due to the absence of a reply expectation), the error is treated
-//! as a panic within the actor. This behavior will trigger the actor's [`on_panic`](crate::actor::Actor::on_panic) hook, which may result in the actor
-//! being restarted or stopped based on the [Actor](crate::Actor) implementation (which stops the actor by default).
+//!   without a direct means for the caller to handle it (due to the absence of a reply expectation), the error is treated
+//!   as a panic within the actor. This behavior will trigger the actor's [`on_panic`](crate::actor::Actor::on_panic) hook, which may result in the actor
+//!   being restarted or stopped based on the [Actor](crate::Actor) implementation (which stops the actor by default).
 //!
 //! The `Reply` trait, by encompassing a broad range of types and defining specific behaviors for error handling,
 //! ensures that actors can manage their communication responsibilities efficiently and effectively.
@@ -40,8 +42,10 @@ use std::{
     thread::Thread,
 };
 
-use futures::Future;
-use tokio::sync::oneshot;
+use futures::{future::BoxFuture, Future, FutureExt};
+use tokio::sync::{mpsc, oneshot, Semaphore};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::error;
 
 use crate::{
     error::{BoxSendError, SendError},
@@ -92,6 +96,21 @@ pub trait Reply: Send + 'static {
     ///
     /// In almost all cases, this will simply return itself.
     fn into_value(self) -> Self::Value;
+
+    /// Sends the reply to the caller if there is one, otherwise returns the boxed error for an unhandled `tell`.
+    #[doc(hidden)]
+    fn send_reply(self, tx: Option<ReplySender<Self::Value>>) -> Option<BoxDebug>
+    where
+        Self: Sized,
+    {
+        match tx {
+            Some(tx) => {
+                tx.send(self.into_value());
+                None
+            }
+            None => self.into_boxed_err(),
+        }
+    }
 }
 
 /// A marker type indicating that the reply to a message will be handled elsewhere.
@@ -135,6 +154,126 @@ where
     }
 }
 
+/// A reply which is resolved by a future, without blocking the actor from processing its next message.
+///
+/// The framework spawns the future once the handler returns, and sends its resolved value back to the caller.
+/// This is useful for actors which kick off async work per message, such as I/O, as the actor is free to
+/// handle other messages in the meantime. Since the future is `'static`, it cannot borrow the actor's state,
+/// so any data it needs should be cloned or moved into it.
+///
+/// If the message was sent with `tell` and the future resolves to an error, the error is logged,
+/// as the actor has already moved on to other messages.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::Actor;
+/// use kameo::message::{Context, Message};
+/// use kameo::reply::DeferredReply;
+///
+/// #[derive(Actor)]
+/// struct Fetcher;
+///
+/// struct Fetch(u64);
+///
+/// impl Message<Fetch> for Fetcher {
+///     type Reply = DeferredReply<u64>;
+///
+///     async fn handle(&mut self, Fetch(n): Fetch, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         DeferredReply::new(async move {
+///             tokio::time::sleep(Duration::from_millis(10)).await;
+///             n * 2
+///         })
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let fetcher = kameo::spawn(Fetcher);
+/// let (a, b) = tokio::join!(fetcher.ask(Fetch(1)), fetcher.ask(Fetch(2)));
+/// assert_eq!((a?, b?), (2, 4));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[must_use = "the deferred reply should be returned by the handler"]
+pub struct DeferredReply<R> {
+    fut: BoxFuture<'static, R>,
+}
+
+impl<R> DeferredReply<R> {
+    /// Creates a deferred reply, which resolves to the output of the future.
+    pub fn new<F>(fut: F) -> Self
+    where
+        F: Future<Output = R> + Send + 'static,
+    {
+        DeferredReply { fut: fut.boxed() }
+    }
+
+    /// Spawns the future, sending its output to the caller, or logging it if it's an error and there is no caller.
+    pub(crate) fn spawn(self, tx: Option<ReplySender<R>>)
+    where
+        R: Reply,
+    {
+        crate::actor::spawn_task(async move {
+            let reply = self.fut.await;
+            match tx {
+                Some(tx) => tx.send(reply),
+                None => {
+                    if let Some(err) = reply.into_boxed_err() {
+                        error!(?err, "deferred reply resolved to an unhandled error");
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl<R> fmt::Debug for DeferredReply<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeferredReply").finish_non_exhaustive()
+    }
+}
+
+impl<R> Reply for DeferredReply<R>
+where
+    R: Reply,
+{
+    type Ok = R::Ok;
+    type Error = R::Error;
+    type Value = R::Value;
+
+    fn to_result(self) -> Result<Self::Ok, Self::Error> {
+        unimplemented!(
+            "a DeferredReply cannot be converted to a result, and is resolved by the actor"
+        )
+    }
+
+    fn into_boxed_err(self) -> Option<BoxDebug> {
+        None
+    }
+
+    fn into_value(self) -> Self::Value {
+        unimplemented!(
+            "a DeferredReply cannot be converted to a value, and is resolved by the actor"
+        )
+    }
+
+    fn send_reply(self, tx: Option<ReplySender<Self::Value>>) -> Option<BoxDebug> {
+        DeferredReply::new(self.fut.map(Reply::into_value)).spawn(tx);
+        None
+    }
+}
+
+/// Options for the reply channel of an ask request, set with [`ActorRef::ask_with`](crate::actor::ActorRef::ask_with).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ReplyOptions {
+    /// The number of items buffered by streaming replies, such as [`Progress`] updates.
+    ///
+    /// Defaults to `None`, which is unbounded.
+    pub buffer: Option<usize>,
+}
+
 /// A mechanism for sending replies back to the original requester in a message exchange.
 ///
 /// `ReplySender` encapsulates the functionality to send a response back to whereever
@@ -154,7 +293,6 @@ where
 ///
 /// The `ReplySender` provides a clear and straightforward interface for completing the message handling cycle,
 /// facilitating efficient and organized communication within the system.
-
 #[must_use = "the receiver expects a reply to be sent"]
 pub struct ReplySender<R: ?Sized> {
     tx: BoxReplySender,
@@ -174,6 +312,13 @@ impl<R> ReplySender<R> {
         self.tx
     }
 
+    /// Returns whether the requester has stopped waiting for the reply, such as after its reply timeout elapsed.
+    ///
+    /// A reply sent once this returns `true` is discarded.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
     /// Sends a reply using the current `ReplySender`.
     ///
     /// Consumes the `ReplySender`, sending the specified reply to the original
@@ -213,6 +358,194 @@ impl<R: ?Sized> fmt::Debug for ReplySender<R> {
     }
 }
 
+/// A reply which streams progress updates to the caller before resolving to a final value.
+///
+/// `Progress` is useful for long running operations such as uploads or processing jobs, where the caller
+/// wants to observe interim progress (eg. a percentage) and eventually receive the final result from a single
+/// [`ask`](crate::actor::ActorRef::ask).
+///
+/// A `Progress` is created with [`Progress::new`], which also returns a [`ProgressSender`] used by the handler
+/// to send updates and the final value. Typically the `Progress` is sent back to the caller immediately
+/// using a [`ReplySender`], allowing the handler to continue working while the caller observes updates.
+///
+/// Dropping the progress stream on the caller side does not cancel the operation;
+/// later updates are simply discarded, and the final value can still be awaited.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::message::{Context, Message};
+/// use kameo::reply::{DelegatedReply, Progress};
+/// use futures::StreamExt;
+///
+/// #[derive(Actor)]
+/// struct Uploader;
+///
+/// struct Upload;
+///
+/// impl Message<Upload> for Uploader {
+///     type Reply = DelegatedReply<Progress<u8, &'static str>>;
+///
+///     async fn handle(&mut self, _msg: Upload, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         let (delegated, reply_sender) = ctx.reply_sender();
+///         let (progress, tx) = Progress::new();
+///         if let Some(reply_sender) = reply_sender {
+///             reply_sender.send(progress);
+///         }
+///
+///         for percent in [0, 50, 100] {
+///             tx.update(percent);
+///         }
+///         tx.finish("uploaded");
+///
+///         delegated
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Uploader);
+/// let (updates, result) = actor_ref.ask(Upload).await?.into_parts();
+/// assert_eq!(updates.collect::<Vec<_>>().await, vec![0, 50, 100]);
+/// assert_eq!(result.await, Some("uploaded"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct Progress<P, R> {
+    updates: mpsc::Receiver<P>,
+    result: oneshot::Receiver<R>,
+}
+
+impl<P, R> Progress<P, R> {
+    /// Creates a new progress reply, along with the sender used to report progress and the final value.
+    ///
+    /// The progress updates are unbounded.
+    pub fn new() -> (Self, ProgressSender<P, R>) {
+        Self::with_options(ReplyOptions::default())
+    }
+
+    /// Creates a new progress reply with the caller's reply options, typically from
+    /// [`Context::reply_options`](crate::message::Context::reply_options).
+    ///
+    /// If the options set a buffer size, at most that many updates are buffered for the caller.
+    /// When the buffer is full, [`ProgressSender::send`] waits for the caller to catch up,
+    /// while [`ProgressSender::update`] discards the update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer size is `0`.
+    pub fn with_options(options: ReplyOptions) -> (Self, ProgressSender<P, R>) {
+        let (updates_tx, updates_rx) =
+            mpsc::channel(options.buffer.unwrap_or(Semaphore::MAX_PERMITS));
+        let (result_tx, result_rx) = oneshot::channel();
+        (
+            Progress {
+                updates: updates_rx,
+                result: result_rx,
+            },
+            ProgressSender {
+                updates: updates_tx,
+                result: result_tx,
+            },
+        )
+    }
+
+    /// Splits the progress into a stream of updates, and a future resolving to the final value.
+    ///
+    /// The future resolves to `None` if the [`ProgressSender`] was dropped without calling [`ProgressSender::finish`],
+    /// such as if the actor panicked or was stopped during the operation.
+    ///
+    /// The stream may be dropped at any time without affecting the operation.
+    pub fn into_parts(self) -> (ReceiverStream<P>, impl Future<Output = Option<R>> + Send)
+    where
+        R: Send,
+    {
+        (ReceiverStream::new(self.updates), async move {
+            self.result.await.ok()
+        })
+    }
+
+    /// Ignores any progress updates, waiting only for the final value.
+    pub async fn result(self) -> Option<R> {
+        self.result.await.ok()
+    }
+}
+
+impl<P, R> fmt::Debug for Progress<P, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("updates", &self.updates)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, R> Reply for Progress<P, R>
+where
+    P: Send + 'static,
+    R: Send + 'static,
+{
+    type Ok = Self;
+    type Error = crate::error::Infallible;
+    type Value = Self;
+
+    fn to_result(self) -> Result<Self, crate::error::Infallible> {
+        Ok(self)
+    }
+
+    fn into_boxed_err(self) -> Option<BoxDebug> {
+        None
+    }
+
+    #[inline]
+    fn into_value(self) -> Self::Value {
+        self
+    }
+}
+
+/// The sending half of a [`Progress`] reply, used by a handler to report progress and the final value.
+///
+/// Progress updates are silently discarded if the caller is no longer listening.
+#[must_use = "the caller expects the final value to be sent with `finish`"]
+pub struct ProgressSender<P, R> {
+    updates: mpsc::Sender<P>,
+    result: oneshot::Sender<R>,
+}
+
+impl<P, R> ProgressSender<P, R> {
+    /// Sends a progress update to the caller without blocking.
+    ///
+    /// If the caller has dropped the progress stream, or the buffer is full, the update is discarded.
+    pub fn update(&self, progress: P) {
+        let _ = self.updates.try_send(progress);
+    }
+
+    /// Sends a progress update to the caller, waiting for capacity if the buffer is full.
+    ///
+    /// If the caller has dropped the progress stream, the update is discarded.
+    pub async fn send(&self, progress: P) {
+        let _ = self.updates.send(progress).await;
+    }
+
+    /// Sends the final value to the caller, closing the progress stream.
+    pub fn finish(self, result: R) {
+        let _ = self.result.send(result);
+    }
+
+    /// Returns `true` if the caller is no longer waiting for the final value.
+    pub fn is_closed(&self) -> bool {
+        self.result.is_closed()
+    }
+}
+
+impl<P, R> fmt::Debug for ProgressSender<P, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressSender")
+            .field("updates", &self.updates)
+            .field("is_closed", &self.is_closed())
+            .finish()
+    }
+}
+
 impl<T, E> Reply for Result<T, E>
 where
     T: Send + 'static,
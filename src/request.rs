@@ -47,6 +47,11 @@ use std::time::Duration;
 use futures::Future;
 
 mod ask;
+mod gather;
+mod pipeline;
+mod race;
+#[cfg(feature = "tower")]
+mod service;
 mod tell;
 
 #[cfg(feature = "remote")]
@@ -55,7 +60,12 @@ pub use ask::RemoteAskRequest;
 #[cfg(feature = "remote")]
 pub use tell::RemoteTellRequest;
 
-pub use ask::{AskRequest, LocalAskRequest};
+pub use ask::{AskRequest, AskTiming, LocalAskRequest, TimedAskRequest};
+pub use gather::gather;
+pub(crate) use pipeline::{ask_pipeline, PipelineReply};
+pub use race::race;
+#[cfg(feature = "tower")]
+pub use service::ActorService;
 pub use tell::{LocalTellRequest, TellRequest};
 
 use crate::{error::SendError, reply::ReplySender, Reply};
@@ -138,6 +148,7 @@ pub trait TryBlockingMessageSend {
 /// Trait representing the ability to send a message with the reply being sent back to a channel.
 pub trait ForwardMessageSend<R: Reply, M> {
     /// Sends a message with the reply being sent back to a channel.
+    #[allow(clippy::type_complexity)]
     fn forward(
         self,
         tx: ReplySender<R::Value>,
@@ -147,6 +158,7 @@ pub trait ForwardMessageSend<R: Reply, M> {
 /// Trait representing the ability to send a message with the reply being sent back to a channel synchronously.
 pub trait ForwardMessageSendSync<R: Reply, M> {
     /// Sends a message synchronously with the reply being sent back to a channel.
+    #[allow(clippy::type_complexity)]
     fn forward_sync(
         self,
         tx: ReplySender<R::Value>,
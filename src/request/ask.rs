@@ -1,16 +1,28 @@
 use futures::{future::BoxFuture, FutureExt};
-use std::{future::IntoFuture, marker::PhantomData, time::Duration};
-use tokio::{sync::oneshot, time::timeout};
+use std::{
+    future::{Future, IntoFuture},
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    task::{self, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::timeout,
+};
 
 #[cfg(feature = "remote")]
-use crate::remote::{ActorSwarm, RemoteActor, RemoteMessage, SwarmCommand, SwarmReq, SwarmResp};
+use crate::remote::{Codec, RemoteActor, RemoteMessage, SwarmReq, SwarmResp};
 
 use crate::{
     actor,
     error::{self, SendError},
-    mailbox::{bounded::BoundedMailbox, unbounded::UnboundedMailbox, Mailbox, Signal},
+    mailbox::{
+        bounded::BoundedMailbox, unbounded::UnboundedMailbox, Mailbox, MessageOptions, Signal,
+    },
     message::{BoxReply, Message},
-    reply::ReplySender,
+    reply::{ReplyOptions, ReplySender},
     Actor, Reply,
 };
 
@@ -20,6 +32,10 @@ use super::{
 };
 
 /// A request to send a message to an actor, waiting for a reply.
+///
+/// When the reply is a `Result`, the request resolves to its `Ok` value, and a handler's error is returned as
+/// [`SendError::HandlerError`] alongside the errors from sending, so call sites match on a single `Result`
+/// rather than a nested one. See [`SendError`] for an example.
 #[allow(missing_debug_implementations)]
 pub struct AskRequest<L, Mb, M, Tm, Tr> {
     location: L,
@@ -36,7 +52,94 @@ where
 {
     mailbox: &'a Mb,
     signal: Signal<A>,
+    rx: ReplyReceiver,
+}
+
+/// The receiver for an ask's reply, counted in [`ActorRef::pending_replies`](actor::ActorRef::pending_replies)
+/// until it is dropped.
+pub(crate) struct ReplyReceiver {
     rx: oneshot::Receiver<Result<BoxReply, error::BoxSendError>>,
+    _pending: actor::PendingReply,
+}
+
+impl ReplyReceiver {
+    #[inline]
+    fn blocking_recv(
+        self,
+    ) -> Result<Result<BoxReply, error::BoxSendError>, oneshot::error::RecvError> {
+        self.rx.blocking_recv()
+    }
+}
+
+impl Future for ReplyReceiver {
+    type Output = Result<Result<BoxReply, error::BoxSendError>, oneshot::error::RecvError>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx).poll(cx)
+    }
+}
+
+/// An ask request which returns how long the message was queued and handled along with the reply,
+/// created with [`AskRequest::with_timing`].
+#[allow(missing_debug_implementations)]
+pub struct TimedAskRequest<R> {
+    request: R,
+    handling_started: Arc<OnceLock<tokio::time::Instant>>,
+}
+
+/// How long an ask waited in the actor's mailbox and was handled for, see [`AskRequest::with_timing`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AskTiming {
+    /// The time from sending the request until the actor started handling the message.
+    pub queued: Duration,
+    /// The time from the actor starting to handle the message until the reply was received.
+    pub handled: Duration,
+}
+
+impl<R> MessageSend for TimedAskRequest<R>
+where
+    R: MessageSend + Send,
+    R::Ok: Send,
+{
+    type Ok = (R::Ok, AskTiming);
+    type Error = R::Error;
+
+    async fn send(self) -> Result<Self::Ok, Self::Error> {
+        let sent = tokio::time::Instant::now();
+        let reply = self.request.send().await?;
+        let replied = tokio::time::Instant::now();
+        // Replies are only sent once the message is being handled, so this is always set
+        let started = self.handling_started.get().copied().unwrap_or(replied);
+        let timing = AskTiming {
+            queued: started.saturating_duration_since(sent),
+            handled: replied.saturating_duration_since(started),
+        };
+        Ok((reply, timing))
+    }
+}
+
+impl<'a, A, M, Tm, Tr> IntoFuture
+    for TimedAskRequest<AskRequest<LocalAskRequest<'a, A, A::Mailbox>, A::Mailbox, M, Tm, Tr>>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    Tm: Send + 'static,
+    Tr: Send + 'static,
+    AskRequest<LocalAskRequest<'a, A, A::Mailbox>, A::Mailbox, M, Tm, Tr>: MessageSend<
+        Ok = <A::Reply as Reply>::Ok,
+        Error = error::SendError<M, <A::Reply as Reply>::Error>,
+    >,
+{
+    type Output = Result<
+        (<A::Reply as Reply>::Ok, AskTiming),
+        error::SendError<M, <A::Reply as Reply>::Error>,
+    >;
+    type IntoFuture = BoxFuture<'a, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        MessageSend::send(self).boxed()
+    }
 }
 
 /// A request to a remote actor.
@@ -77,14 +180,37 @@ where
                     actor_ref: actor_ref.clone(),
                     reply: Some(reply),
                     sent_within_actor: actor_ref.is_current(),
+                    options: MessageOptions::boxed(
+                        actor::CURRENT_DEADLINE
+                            .try_with(|deadline| *deadline)
+                            .ok()
+                            .flatten(),
+                        ReplyOptions::default(),
+                        actor::CURRENT_ACTOR_ID.try_with(Clone::clone).ok(),
+                    ),
+                },
+                rx: ReplyReceiver {
+                    rx,
+                    _pending: actor_ref.pending_reply(),
                 },
-                rx,
             },
             mailbox_timeout: WithoutRequestTimeout,
             reply_timeout: WithoutRequestTimeout,
             phantom: PhantomData,
         }
     }
+
+    /// Splits the request into its signal, and the receiver for the reply.
+    #[inline]
+    pub(crate) fn into_signal(self) -> (Signal<A>, ReplyReceiver) {
+        (self.location.signal, self.location.rx)
+    }
+
+    #[inline]
+    pub(crate) fn with_reply_options(mut self, options: ReplyOptions) -> Self {
+        self.location.signal = self.location.signal.with_reply_options(options);
+        self
+    }
 }
 
 #[cfg(feature = "remote")]
@@ -142,6 +268,207 @@ impl<L, Mb, M, Tm, Tr> AskRequest<L, Mb, M, Tm, Tr> {
     }
 }
 
+impl<'a, A, Mb, M, Tm, Tr> AskRequest<LocalAskRequest<'a, A, Mb>, Mb, M, Tm, Tr>
+where
+    A: Actor<Mailbox = Mb>,
+{
+    /// Sets an absolute deadline for waiting for a reply from the actor.
+    ///
+    /// This is equivalent to a [`reply_timeout`](AskRequest::reply_timeout) of the time remaining until `deadline`,
+    /// and is convenient when forwarding a handler's [`Context::deadline`](crate::message::Context::deadline)
+    /// to another actor. Since asks sent from within a handler keep the sooner of the two deadlines,
+    /// the inherited deadline is propagated unchanged rather than recomputed at each hop.
+    ///
+    /// A deadline in the past times out as soon as the message is sent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use kameo::actor::ActorRef;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Backend;
+    ///
+    /// struct Query;
+    ///
+    /// impl Message<Query> for Backend {
+    ///     type Reply = Option<Instant>;
+    ///
+    ///     async fn handle(&mut self, _: Query, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.deadline()
+    ///     }
+    /// }
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Frontend {
+    ///     backend: ActorRef<Backend>,
+    /// }
+    ///
+    /// impl Message<Query> for Frontend {
+    ///     type Reply = Option<Instant>;
+    ///
+    ///     async fn handle(&mut self, msg: Query, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let deadline = ctx.deadline()?;
+    ///         self.backend.ask(msg).deadline(deadline).await.ok()?
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let backend = kameo::spawn(Backend);
+    /// let frontend = kameo::spawn(Frontend { backend });
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// assert_eq!(frontend.ask(Query).deadline(deadline).await?, Some(deadline));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn deadline(
+        mut self,
+        deadline: Instant,
+    ) -> AskRequest<LocalAskRequest<'a, A, Mb>, Mb, M, Tm, WithRequestTimeout> {
+        self.location.signal = self.location.signal.with_deadline(deadline);
+        self.reply_timeout(
+            deadline.saturating_duration_since(tokio::time::Instant::now().into_std()),
+        )
+    }
+
+    /// Handles the message without waiting behind messages queued while the actor is starting up.
+    ///
+    /// Messages received before the actor has finished starting up are normally held back until
+    /// startup completes, and then handled after any messages the actor sent itself with
+    /// [`tell_self_priority`](crate::actor::ActorRef::tell_self_priority) during `on_start`.
+    /// With this option the message is handled as soon as it's received instead, which is useful for
+    /// liveness probes which should be answered as early as possible.
+    ///
+    /// The handler never runs concurrently with [`on_start`](Actor::on_start), since the actor is only
+    /// borrowed by one at a time, but it may run before the priority messages from `on_start` have been
+    /// handled. It should not rely on any state those messages are expected to set up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Worker {
+    ///     handled: usize,
+    /// }
+    ///
+    /// struct Work;
+    /// struct Ping;
+    ///
+    /// impl Message<Work> for Worker {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Work, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.handled += 1;
+    ///     }
+    /// }
+    ///
+    /// impl Message<Ping> for Worker {
+    ///     type Reply = usize;
+    ///
+    ///     async fn handle(&mut self, _: Ping, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.handled
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared = kameo::actor::prepare(Worker::default());
+    /// let actor_ref = prepared.actor_ref_owned();
+    /// actor_ref.tell(Work).await?;
+    ///
+    /// let probe = tokio::spawn({
+    ///     let actor_ref = actor_ref.clone();
+    ///     async move { actor_ref.ask(Ping).skip_startup_gate().await }
+    /// });
+    /// tokio::task::yield_now().await;
+    /// prepared.spawn();
+    ///
+    /// // The probe overtakes the work sent before the actor started
+    /// assert_eq!(probe.await??, 0);
+    /// assert_eq!(actor_ref.ask(Ping).await?, 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn skip_startup_gate(mut self) -> Self {
+        self.location.signal = self.location.signal.with_skip_startup_gate();
+        self
+    }
+
+    /// Measures how long the message waited in the actor's mailbox and how long it took to be handled,
+    /// returning an [`AskTiming`] along with the reply.
+    ///
+    /// The time queued is measured from when the request is sent, so it includes waiting for mailbox capacity,
+    /// until the actor starts handling the message. The time handled is measured from then until the reply is
+    /// received, which includes any time taken by a [delegated reply](crate::message::Context::reply_sender).
+    ///
+    /// Timing is opt in, so asks sent without it don't pay for the timestamps. It's only returned when the request
+    /// is awaited or sent with [`MessageSend::send`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Worker;
+    ///
+    /// struct Work;
+    ///
+    /// impl Message<Work> for Worker {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Work, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         tokio::time::sleep(Duration::from_millis(10)).await;
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Worker);
+    ///
+    /// let ((), timing) = actor_ref.ask(Work).with_timing().await?;
+    /// assert!(timing.handled >= Duration::from_millis(10));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn with_timing(mut self) -> TimedAskRequest<Self> {
+        let handling_started = Arc::new(OnceLock::new());
+        self.location.signal = self.location.signal.with_timing(handling_started.clone());
+        TimedAskRequest {
+            request: self,
+            handling_started,
+        }
+    }
+}
+
+#[cfg(feature = "remote")]
+impl<'a, A, Mb, M, Tm, Tr> AskRequest<RemoteAskRequest<'a, A, M>, Mb, M, Tm, Tr>
+where
+    A: Actor,
+{
+    /// Sets an absolute deadline for waiting for a reply from the actor.
+    ///
+    /// This is equivalent to a [`reply_timeout`](AskRequest::reply_timeout) of the time remaining until `deadline`.
+    #[inline]
+    pub fn deadline(
+        self,
+        deadline: Instant,
+    ) -> AskRequest<RemoteAskRequest<'a, A, M>, Mb, M, Tm, WithRequestTimeout> {
+        self.reply_timeout(
+            deadline.saturating_duration_since(tokio::time::Instant::now().into_std()),
+        )
+    }
+}
+
 #[cfg(feature = "remote")]
 impl<L, Mb, M, Tm, Tr> AskRequest<L, Mb, M, Tm, Tr> {
     #[inline]
@@ -220,6 +547,12 @@ macro_rules! impl_message_trait {
             #[inline]
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
                 let $req = self;
+                if $req.location.signal.is_too_large() {
+                    return Err(error::SendError::TooLarge($req.location.signal.downcast_message().unwrap()));
+                }
+                if $req.location.signal.is_overloaded() {
+                    return Err(error::SendError::Overloaded($req.location.signal.downcast_message().unwrap()));
+                }
                 $($body)*
             }
         }
@@ -243,6 +576,12 @@ macro_rules! impl_message_trait {
             #[inline]
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
                 let $req = self;
+                if $req.location.signal.is_too_large() {
+                    return Err(error::SendError::TooLarge($req.location.signal.downcast_message().unwrap()));
+                }
+                if $req.location.signal.is_overloaded() {
+                    return Err(error::SendError::Overloaded($req.location.signal.downcast_message().unwrap()));
+                }
                 $($body)*
             }
         }
@@ -337,7 +676,7 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.send(req.location.signal).await?;
+        req.location.mailbox.send_signal(req.location.signal.with_reply_timeout(req.reply_timeout.0)).await?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
             Ok(val) => Ok(*val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
@@ -372,7 +711,7 @@ impl_message_trait!(
         req.location
             .mailbox
             .0
-            .send_timeout(req.location.signal, req.mailbox_timeout.0)
+            .send_timeout(req.location.signal.with_reply_timeout(req.reply_timeout.0), req.mailbox_timeout.0)
             .await?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
             Ok(val) => Ok(*val.downcast().unwrap()),
@@ -388,7 +727,7 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.send(req.location.signal)?;
+        req.location.mailbox.send_signal(req.location.signal.with_reply_timeout(req.reply_timeout.0))?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
             Ok(val) => Ok(*val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
@@ -559,7 +898,7 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.try_send(req.location.signal)?;
+        req.location.mailbox.0.try_send(req.location.signal.with_reply_timeout(req.reply_timeout.0))?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
             Ok(val) => Ok(*val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
@@ -574,7 +913,7 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.send(req.location.signal)?;
+        req.location.mailbox.send_signal(req.location.signal.with_reply_timeout(req.reply_timeout.0))?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
             Ok(val) => Ok(*val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
@@ -744,6 +1083,12 @@ macro_rules! impl_forward_message {
                 -> Result<(), SendError<(M, ReplySender<<A::Reply as Reply>::Value>), <A::Reply as Reply>::Error>>
             {
                 let mut $req = self;
+                if $req.location.signal.is_too_large() {
+                    return Err(SendError::TooLarge(($req.location.signal.downcast_message().unwrap(), $tx)));
+                }
+                if $req.location.signal.is_overloaded() {
+                    return Err(SendError::Overloaded(($req.location.signal.downcast_message().unwrap(), $tx)));
+                }
                 $($body)*
             }
         }
@@ -762,17 +1107,29 @@ impl_forward_message!(
 
         req.location
             .mailbox
-            .0
-            .send(req.location.signal)
+            .send_signal(req.location.signal)
             .await
-            .map_err(|err| match err.0 {
-                Signal::Message {
-                    message, mut reply, ..
-                } => SendError::ActorNotRunning((
-                    message.as_any().downcast::<M>().ok().map(|v| *v).unwrap(),
-                    ReplySender::new(reply.take().unwrap()),
-                )),
-                _ => unreachable!("ask requests only support messages"),
+            .map_err(|err| {
+                let (signal, full) = match err {
+                    mpsc::error::TrySendError::Full(signal) => (signal, true),
+                    mpsc::error::TrySendError::Closed(signal) => (signal, false),
+                };
+                match signal {
+                    Signal::Message {
+                        message, mut reply, ..
+                    } => {
+                        let msg = (
+                            message.as_any().downcast::<M>().ok().map(|v| *v).unwrap(),
+                            ReplySender::new(reply.take().unwrap()),
+                        );
+                        if full {
+                            SendError::MailboxFull(msg)
+                        } else {
+                            SendError::ActorNotRunning(msg)
+                        }
+                    }
+                    _ => unreachable!("ask requests only support messages"),
+                }
             })
     }
 );
@@ -847,6 +1204,12 @@ macro_rules! impl_forward_message_sync {
                 -> Result<(), SendError<(M, ReplySender<<A::Reply as Reply>::Value>), <A::Reply as Reply>::Error>>
             {
                 let mut $req = self;
+                if $req.location.signal.is_too_large() {
+                    return Err(SendError::TooLarge(($req.location.signal.downcast_message().unwrap(), $tx)));
+                }
+                if $req.location.signal.is_overloaded() {
+                    return Err(SendError::Overloaded(($req.location.signal.downcast_message().unwrap(), $tx)));
+                }
                 $($body)*
             }
         }
@@ -897,31 +1260,25 @@ where
     use std::borrow::Cow;
 
     let actor_id = actor_ref.id();
-    let (reply_tx, reply_rx) = oneshot::channel();
-    actor_ref.send_to_swarm(SwarmCommand::Req {
-        peer_id: actor_id
-            .peer_id_intern()
-            .cloned()
-            .unwrap_or_else(|| ActorSwarm::get().unwrap().local_peer_id_intern().clone()),
-        req: SwarmReq::Ask {
+    let resp = actor_ref
+        .request_swarm(SwarmReq::Ask {
             actor_id,
             actor_remote_id: Cow::Borrowed(<A as RemoteActor>::REMOTE_ID),
             message_remote_id: Cow::Borrowed(<A as RemoteMessage<M>>::REMOTE_ID),
-            payload: rmp_serde::to_vec_named(msg)
+            payload: <A as RemoteMessage<M>>::Codec::encode(msg)
                 .map_err(|err| error::RemoteSendError::SerializeMessage(err.to_string()))?,
             mailbox_timeout,
             reply_timeout,
             immediate,
-        },
-        reply: reply_tx,
-    });
+        })
+        .await;
 
-    match reply_rx.await.unwrap() {
+    match resp {
         SwarmResp::Ask(res) => match res {
-            Ok(payload) => Ok(rmp_serde::decode::from_slice(&payload)
+            Ok(payload) => Ok(<A as RemoteMessage<M>>::Codec::decode(&payload)
                 .map_err(|err| error::RemoteSendError::DeserializeMessage(err.to_string()))?),
             Err(err) => Err(err
-                .map_err(|err| match rmp_serde::decode::from_slice(&err) {
+                .map_err(|err| match <A as RemoteMessage<M>>::Codec::decode(&err) {
                     Ok(err) => error::RemoteSendError::HandlerError(err),
                     Err(err) => error::RemoteSendError::DeserializeHandlerError(err.to_string()),
                 })
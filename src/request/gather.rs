@@ -0,0 +1,81 @@
+use futures::{stream, StreamExt};
+
+use crate::{actor::ActorRef, error::SendError, message::Message, Actor, Reply};
+
+use super::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout};
+
+/// Sends an ask request to each actor, with at most `concurrency` requests in flight at once.
+///
+/// The message for each actor is created by calling `msg_fn` with its actor ref. Results are returned in the
+/// same order as `refs`, regardless of the order in which the actors reply.
+///
+/// Capping the number of simultaneous requests is useful for scatter-gather patterns, where an unbounded fan-out
+/// could overwhelm a downstream resource. A `concurrency` of `0` is treated as `1`.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(Actor)]
+/// struct Square;
+///
+/// struct Compute(u64);
+///
+/// impl Message<Compute> for Square {
+///     type Reply = u64;
+///
+///     async fn handle(&mut self, Compute(n): Compute, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         n * n
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let workers: Vec<_> = (0..8).map(|_| kameo::spawn(Square)).collect();
+///
+/// let mut n = 0;
+/// let results = kameo::gather(&workers, |_| { n += 1; Compute(n) }, 2).await;
+/// let squares: Vec<u64> = results.into_iter().collect::<Result<_, _>>()?;
+/// assert_eq!(squares, [1, 4, 9, 16, 25, 36, 49, 64]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub async fn gather<'a, A, M, I, F>(
+    refs: I,
+    mut msg_fn: F,
+    concurrency: usize,
+) -> Vec<Result<<A::Reply as Reply>::Ok, SendError<M, <A::Reply as Reply>::Error>>>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    I: IntoIterator<Item = &'a ActorRef<A>>,
+    F: FnMut(&ActorRef<A>) -> M,
+    AskRequest<
+        LocalAskRequest<'a, A, A::Mailbox>,
+        A::Mailbox,
+        M,
+        WithoutRequestTimeout,
+        WithoutRequestTimeout,
+    >: MessageSend<Ok = <A::Reply as Reply>::Ok, Error = SendError<M, <A::Reply as Reply>::Error>>,
+{
+    let requests: Vec<_> = refs
+        .into_iter()
+        .enumerate()
+        .map(|(i, actor_ref)| {
+            let req = actor_ref.ask(msg_fn(actor_ref));
+            async move { (i, req.send().await) }
+        })
+        .collect();
+
+    let mut results: Vec<_> = (0..requests.len()).map(|_| None).collect();
+    let mut replies = stream::iter(requests).buffer_unordered(concurrency.max(1));
+    while let Some((i, res)) = replies.next().await {
+        results[i] = Some(res);
+    }
+
+    results
+        .into_iter()
+        .map(|res| res.expect("every request yields a result"))
+        .collect()
+}
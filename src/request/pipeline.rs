@@ -0,0 +1,96 @@
+use futures::{
+    future::{self, BoxFuture},
+    stream::{self, FuturesOrdered},
+    FutureExt, Stream, StreamExt,
+};
+
+use crate::{
+    actor::ActorRef,
+    error::SendError,
+    mailbox::Mailbox,
+    message::{BoxReply, Message},
+    Actor, Reply,
+};
+
+use super::AskRequest;
+
+pub(crate) type PipelineReply<A, M> = Result<
+    <<A as Message<M>>::Reply as Reply>::Ok,
+    SendError<M, <<A as Message<M>>::Reply as Reply>::Error>,
+>;
+
+struct Pipeline<A, M, I>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+{
+    actor_ref: ActorRef<A>,
+    msgs: Option<I>,
+    pending: FuturesOrdered<BoxFuture<'static, PipelineReply<A, M>>>,
+}
+
+/// Sends each message to the actor in order, yielding the replies in the same order.
+///
+/// See [`ActorRef::ask_pipeline`] for more information.
+pub(crate) fn ask_pipeline<A, M, I>(
+    actor_ref: ActorRef<A>,
+    msgs: I,
+) -> impl Stream<Item = PipelineReply<A, M>> + Send + 'static
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    I: Iterator<Item = M> + Send + 'static,
+{
+    let pipeline = Pipeline {
+        actor_ref,
+        msgs: Some(msgs),
+        pending: FuturesOrdered::new(),
+    };
+
+    stream::unfold(pipeline, |mut pipeline| async move {
+        loop {
+            // Replies which have already arrived are yielded before sending more messages
+            if let Some(Some(reply)) = pipeline.pending.next().now_or_never() {
+                return Some((reply, pipeline));
+            }
+
+            match pipeline.msgs.as_mut().and_then(Iterator::next) {
+                Some(msg) => {
+                    let reply = send(&pipeline.actor_ref, msg).await;
+                    pipeline.pending.push_back(reply);
+                }
+                None => {
+                    pipeline.msgs = None;
+                    let reply = pipeline.pending.next().await?;
+                    return Some((reply, pipeline));
+                }
+            }
+        }
+    })
+}
+
+async fn send<A, M>(actor_ref: &ActorRef<A>, msg: M) -> BoxFuture<'static, PipelineReply<A, M>>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+{
+    let (signal, rx) = AskRequest::new(actor_ref, msg).into_signal();
+    if signal.is_too_large() {
+        let err = SendError::TooLarge(signal.downcast_message().unwrap());
+        return future::ready(Err(err)).boxed();
+    }
+    if signal.is_overloaded() {
+        let err = SendError::Overloaded(signal.downcast_message().unwrap());
+        return future::ready(Err(err)).boxed();
+    }
+    if let Err(err) = actor_ref.mailbox().send(signal).await {
+        let err = err.map_msg(|signal| signal.downcast_message().unwrap());
+        return future::ready(Err(err)).boxed();
+    }
+
+    rx.map(|res| match res? {
+        Ok(val) => Ok(*BoxReply::downcast(val).unwrap()),
+        Err(err) => Err(err.downcast()),
+    })
+    .boxed()
+}
@@ -0,0 +1,91 @@
+use futures::{stream::FuturesUnordered, StreamExt};
+
+use crate::{
+    actor::{ActorID, ActorRef},
+    error::SendError,
+    message::Message,
+    Actor, Reply,
+};
+
+use super::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout};
+
+/// Sends an ask request to each actor at once, returning the first successful reply along with the id of the actor
+/// which sent it.
+///
+/// The message for each actor is created by calling `msg_fn` with its actor ref. Sending the same request to several
+/// replicas and taking the fastest reply, also known as hedging, reduces tail latency when backends respond at
+/// variable speeds.
+///
+/// Once a reply succeeds, the remaining requests are dropped. Their actors still handle the message, but their replies
+/// are discarded without any errors being logged. If every request fails, the errors are returned in the order they
+/// occurred, which is empty if `refs` is empty.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::Actor;
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(Actor)]
+/// struct Replica {
+///     latency: Duration,
+/// }
+///
+/// struct Get;
+///
+/// impl Message<Get> for Replica {
+///     type Reply = u64;
+///
+///     async fn handle(&mut self, _: Get, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         tokio::time::sleep(self.latency).await;
+///         42
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let slow = kameo::spawn(Replica { latency: Duration::from_secs(5) });
+/// let fast = kameo::spawn(Replica { latency: Duration::from_millis(1) });
+///
+/// let (value, id) = kameo::race([&slow, &fast], |_| Get).await.unwrap();
+/// assert_eq!(value, 42);
+/// assert_eq!(id, fast.id());
+/// # });
+/// ```
+pub async fn race<'a, A, M, I, F>(
+    refs: I,
+    mut msg_fn: F,
+) -> Result<(<A::Reply as Reply>::Ok, ActorID), Vec<SendError<M, <A::Reply as Reply>::Error>>>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    I: IntoIterator<Item = &'a ActorRef<A>>,
+    F: FnMut(&ActorRef<A>) -> M,
+    AskRequest<
+        LocalAskRequest<'a, A, A::Mailbox>,
+        A::Mailbox,
+        M,
+        WithoutRequestTimeout,
+        WithoutRequestTimeout,
+    >: MessageSend<Ok = <A::Reply as Reply>::Ok, Error = SendError<M, <A::Reply as Reply>::Error>>,
+{
+    let mut requests: FuturesUnordered<_> = refs
+        .into_iter()
+        .map(|actor_ref| {
+            let id = actor_ref.id();
+            let req = actor_ref.ask(msg_fn(actor_ref));
+            async move { (id, req.send().await) }
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    while let Some((id, res)) = requests.next().await {
+        match res {
+            Ok(reply) => return Ok((reply, id)),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    Err(errors)
+}
@@ -0,0 +1,214 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    task::{Context, Poll},
+};
+
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt,
+};
+use tokio_util::sync::PollSender;
+use tower_service::Service;
+
+use crate::{
+    actor::ActorRef,
+    error::SendError,
+    mailbox::{bounded::BoundedMailbox, unbounded::UnboundedMailbox, Signal},
+    message::Message,
+    Actor, Reply,
+};
+
+use super::{ask::ReplyReceiver, AskRequest};
+
+type ServiceFuture<A, M> = BoxFuture<
+    'static,
+    Result<
+        <<A as Message<M>>::Reply as Reply>::Ok,
+        SendError<M, <<A as Message<M>>::Reply as Reply>::Error>,
+    >,
+>;
+
+/// A [`tower_service::Service`] which handles requests by sending them to an actor with `ask`.
+///
+/// This allows an actor to be used as a request handler behind `tower` middleware, such as timeouts, retries,
+/// and load shedding. It is created with [`ActorRef::into_service`].
+///
+/// For actors with a [`BoundedMailbox`], `poll_ready` reserves a slot in the mailbox, and is pending while the
+/// mailbox is full. Actors with an [`UnboundedMailbox`] are always ready. If the actor is no longer running,
+/// `poll_ready` fails with [`SendError::ActorStopped`].
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Context, Message};
+/// use tower_service::Service;
+///
+/// #[derive(kameo::Actor)]
+/// struct Greeter;
+///
+/// struct Greet(String);
+///
+/// impl Message<Greet> for Greeter {
+///     type Reply = String;
+///
+///     async fn handle(&mut self, Greet(name): Greet, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         format!("Hello, {name}!")
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let mut service = kameo::spawn(Greeter).into_service();
+/// futures::future::poll_fn(|cx| service.poll_ready(cx)).await?;
+/// assert_eq!(service.call(Greet("kameo".to_string())).await?, "Hello, kameo!");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct ActorService<A: Actor, M, Mb = <A as Actor>::Mailbox> {
+    actor_ref: ActorRef<A>,
+    sender: Option<PollSender<Signal<A>>>,
+    phantom: PhantomData<fn(M) -> Mb>,
+}
+
+impl<A: Actor, M, Mb> ActorService<A, M, Mb> {
+    pub(crate) fn new(actor_ref: ActorRef<A>) -> Self {
+        ActorService {
+            actor_ref,
+            sender: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the actor the service sends requests to.
+    pub fn actor_ref(&self) -> &ActorRef<A> {
+        &self.actor_ref
+    }
+}
+
+impl<A, M> Service<M> for ActorService<A, M, BoundedMailbox<A>>
+where
+    A: Actor<Mailbox = BoundedMailbox<A>> + Message<M>,
+    M: Send + 'static,
+{
+    type Response = <A::Reply as Reply>::Ok;
+    type Error = SendError<M, <A::Reply as Reply>::Error>;
+    type Future = ServiceFuture<A, M>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let mailbox = &self.actor_ref.mailbox().0;
+        self.sender
+            .get_or_insert_with(|| PollSender::new(mailbox.clone()))
+            .poll_reserve(cx)
+            .map_err(|_| SendError::ActorStopped)
+    }
+
+    fn call(&mut self, msg: M) -> Self::Future {
+        let (signal, rx) = AskRequest::new(&self.actor_ref, msg).into_signal();
+        let sender = self
+            .sender
+            .as_mut()
+            .expect("`call` called without first calling `poll_ready`");
+        if signal.is_too_large() {
+            sender.abort_send();
+            return too_large(signal);
+        }
+        if signal.is_overloaded() {
+            sender.abort_send();
+            return overloaded(signal);
+        }
+        match sender.send_item(signal) {
+            Ok(()) => recv_reply::<A, M>(rx),
+            Err(err) => not_running(err.into_inner().unwrap()),
+        }
+    }
+}
+
+impl<A, M> Service<M> for ActorService<A, M, UnboundedMailbox<A>>
+where
+    A: Actor<Mailbox = UnboundedMailbox<A>> + Message<M>,
+    M: Send + 'static,
+{
+    type Response = <A::Reply as Reply>::Ok;
+    type Error = SendError<M, <A::Reply as Reply>::Error>;
+    type Future = ServiceFuture<A, M>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.actor_ref.mailbox().0.is_closed() {
+            Poll::Ready(Err(SendError::ActorStopped))
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn call(&mut self, msg: M) -> Self::Future {
+        let (signal, rx) = AskRequest::new(&self.actor_ref, msg).into_signal();
+        if signal.is_too_large() {
+            return too_large(signal);
+        }
+        if signal.is_overloaded() {
+            return overloaded(signal);
+        }
+        match self.actor_ref.mailbox().send_signal(signal) {
+            Ok(()) => recv_reply::<A, M>(rx),
+            Err(err) => not_running(err.0),
+        }
+    }
+}
+
+fn recv_reply<A, M>(rx: ReplyReceiver) -> ServiceFuture<A, M>
+where
+    A: Message<M>,
+    M: Send + 'static,
+{
+    async move {
+        match rx.await? {
+            Ok(val) => Ok(*val.downcast().unwrap()),
+            Err(err) => Err(err.downcast()),
+        }
+    }
+    .boxed()
+}
+
+fn not_running<A, M>(signal: Signal<A>) -> ServiceFuture<A, M>
+where
+    A: Message<M>,
+    M: Send + 'static,
+{
+    future::ready(Err(SendError::ActorNotRunning(
+        signal.downcast_message().unwrap(),
+    )))
+    .boxed()
+}
+
+fn too_large<A, M>(signal: Signal<A>) -> ServiceFuture<A, M>
+where
+    A: Message<M>,
+    M: Send + 'static,
+{
+    future::ready(Err(SendError::TooLarge(signal.downcast_message().unwrap()))).boxed()
+}
+
+fn overloaded<A, M>(signal: Signal<A>) -> ServiceFuture<A, M>
+where
+    A: Message<M>,
+    M: Send + 'static,
+{
+    future::ready(Err(SendError::Overloaded(
+        signal.downcast_message().unwrap(),
+    )))
+    .boxed()
+}
+
+impl<A: Actor, M, Mb> Clone for ActorService<A, M, Mb> {
+    fn clone(&self) -> Self {
+        ActorService::new(self.actor_ref.clone())
+    }
+}
+
+impl<A: Actor, M, Mb> fmt::Debug for ActorService<A, M, Mb> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActorService")
+            .field("actor_ref", &self.actor_ref)
+            .finish_non_exhaustive()
+    }
+}
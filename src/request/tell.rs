@@ -8,8 +8,11 @@ use crate::remote;
 
 use crate::{
     actor, error,
-    mailbox::{bounded::BoundedMailbox, unbounded::UnboundedMailbox, Mailbox, Signal},
+    mailbox::{
+        bounded::BoundedMailbox, unbounded::UnboundedMailbox, Mailbox, MessageOptions, Signal,
+    },
     message::Message,
+    reply::ReplyOptions,
     Actor, Reply,
 };
 
@@ -68,6 +71,11 @@ where
                     actor_ref: actor_ref.clone(),
                     reply: None,
                     sent_within_actor: actor_ref.is_current(),
+                    options: MessageOptions::boxed(
+                        None,
+                        ReplyOptions::default(),
+                        actor::CURRENT_ACTOR_ID.try_with(Clone::clone).ok(),
+                    ),
                 },
             },
             timeout: WithoutRequestTimeout,
@@ -109,6 +117,52 @@ where
     }
 }
 
+impl<'a, A, M, T> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, T>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, T>:
+        MessageSend<Ok = (), Error = error::SendError<M, <A::Reply as Reply>::Error>>,
+{
+    /// Sends the message, resolving once it has been accepted into the actor's mailbox.
+    ///
+    /// For bounded mailboxes, this waits for any backpressure, so a producer awaiting the receipt
+    /// will not outpace the actor. Unlike an ask request, this does **not** wait for the message to be handled,
+    /// and any error returned by the handler is not reported here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::Actor;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor)]
+    /// struct MyActor;
+    ///
+    /// struct Msg;
+    ///
+    /// impl Message<Msg> for MyActor {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _: Msg, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {}
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    ///
+    /// // The message is in the mailbox, but may not have been handled yet
+    /// actor_ref.tell(Msg).send_with_receipt().await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub async fn send_with_receipt(
+        self,
+    ) -> Result<(), error::SendError<M, <A::Reply as Reply>::Error>> {
+        MessageSend::send(self).await
+    }
+}
+
 #[cfg(feature = "remote")]
 impl<L, Mb, M, T> TellRequest<L, Mb, M, T> {
     #[inline]
@@ -176,6 +230,12 @@ macro_rules! impl_message_trait {
             #[inline]
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
                 let $req = self;
+                if $req.location.signal.is_too_large() {
+                    return Err(error::SendError::TooLarge($req.location.signal.downcast_message().unwrap()));
+                }
+                if $req.location.signal.is_overloaded() {
+                    return Err(error::SendError::Overloaded($req.location.signal.downcast_message().unwrap()));
+                }
                 $($body)*
             }
         }
@@ -198,6 +258,12 @@ macro_rules! impl_message_trait {
             #[inline]
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
                 let $req = self;
+                if $req.location.signal.is_too_large() {
+                    return Err(error::SendError::TooLarge($req.location.signal.downcast_message().unwrap()));
+                }
+                if $req.location.signal.is_overloaded() {
+                    return Err(error::SendError::Overloaded($req.location.signal.downcast_message().unwrap()));
+                }
                 $($body)*
             }
         }
@@ -295,7 +361,7 @@ impl_message_trait!(
     |req| {
         match req.timeout {
             MaybeRequestTimeout::NoTimeout => {
-                req.location.mailbox.0.send(req.location.signal).await?;
+                req.location.mailbox.send_signal(req.location.signal).await?;
             }
             MaybeRequestTimeout::Timeout(timeout) => {
                 req.location
@@ -341,7 +407,7 @@ impl_message_trait!(
     UnboundedMailbox,
     WithoutRequestTimeout,
     |req| {
-        req.location.mailbox.0.send(req.location.signal)?;
+        req.location.mailbox.send_signal(req.location.signal)?;
         Ok(())
     }
 );
@@ -432,29 +498,23 @@ where
     use std::borrow::Cow;
 
     let actor_id = actor_ref.id();
-    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
-    actor_ref.send_to_swarm(SwarmCommand::Req {
-        peer_id: actor_id
-            .peer_id_intern()
-            .cloned()
-            .unwrap_or_else(|| *ActorSwarm::get().unwrap().local_peer_id_intern()),
-        req: SwarmReq::Tell {
+    let resp = actor_ref
+        .request_swarm(SwarmReq::Tell {
             actor_id,
             actor_remote_id: Cow::Borrowed(<A as RemoteActor>::REMOTE_ID),
             message_remote_id: Cow::Borrowed(<A as RemoteMessage<M>>::REMOTE_ID),
-            payload: rmp_serde::to_vec_named(msg)
+            payload: <A as RemoteMessage<M>>::Codec::encode(msg)
                 .map_err(|err| error::RemoteSendError::SerializeMessage(err.to_string()))?,
             mailbox_timeout,
             immediate,
-        },
-        reply: reply_tx,
-    });
+        })
+        .await;
 
-    match reply_rx.await.unwrap() {
+    match resp {
         SwarmResp::Tell(res) => match res {
             Ok(()) => Ok(()),
             Err(err) => Err(err
-                .map_err(|err| match rmp_serde::decode::from_slice(&err) {
+                .map_err(|err| match <A as RemoteMessage<M>>::Codec::decode(&err) {
                     Ok(err) => error::RemoteSendError::HandlerError(err),
                     Err(err) => error::RemoteSendError::DeserializeHandlerError(err.to_string()),
                 })
@@ -0,0 +1,308 @@
+//! Utilities for testing code which sends messages to actors.
+//!
+//! The [`MockActor`] records every message it receives, and replies with canned values, allowing code which sends
+//! messages to be tested in isolation from the real actor. Code under test should be generic over the actor type,
+//! such as accepting an `ActorRef<A>` where `A: Message<M>`, so it can be given an `ActorRef<MockActor<M>>`.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::actor::ActorRef;
+//! use kameo::message::Message;
+//! use kameo::test::MockActor;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Order { id: u32 }
+//!
+//! // The code under test
+//! async fn place_orders<A: Message<Order, Reply = bool>>(actor_ref: &ActorRef<A>) -> u32 {
+//!     let mut accepted = 0;
+//!     for id in 0..3 {
+//!         if actor_ref.ask(Order { id }).await.unwrap() {
+//!             accepted += 1;
+//!         }
+//!     }
+//!     accepted
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let mock = MockActor::with_reply(|order: &Order| order.id != 1);
+//! let actor_ref = kameo::spawn(mock.clone());
+//!
+//! assert_eq!(place_orders(&actor_ref).await, 2);
+//! assert_eq!(
+//!     *mock.received(),
+//!     vec![Order { id: 0 }, Order { id: 1 }, Order { id: 2 }]
+//! );
+//! # });
+//! ```
+//!
+//! # Virtual time
+//!
+//! With the `test-util` feature enabled, time dependent actors such as those using intervals, timeouts, or
+//! [scheduled messages](crate::actor::ScheduledMessages) can be tested without real sleeps, by pausing tokio's clock
+//! and moving it forward with [`advance_time`]. All of kameo's timers use [`tokio::time`], so they respect paused time.
+//! Message deadlines are also based on tokio's clock, so deadlines passed to
+//! [`AskRequest::deadline`](crate::request::AskRequest::deadline) should be created from
+//! [`tokio::time::Instant::now`] in these tests.
+//!
+//! # Teardown
+//!
+//! Actors still running when a test returns are dropped as its runtime shuts down, in the middle of whatever they
+//! were doing. Handlers may be cut off halfway, [`Actor::on_stop`] never runs, and code
+//! relying on the runtime, such as a threaded actor, may panic, which makes test output confusing.
+//!
+//! The recommended pattern is to stop the actors a test spawned before it returns. With the `test-util` feature
+//! enabled, wrapping the test body in [`with_teardown`] does this, stopping every actor spawned within it gracefully
+//! and waiting for them. Unlike [`shutdown_all`](crate::actor::shutdown_all), it doesn't affect actors spawned by
+//! other tests running in parallel.
+
+#[cfg(feature = "test-util")]
+use std::collections::HashSet;
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    actor::ActorID,
+    mailbox::unbounded::UnboundedMailbox,
+    message::{Context, Message},
+    Actor, Reply,
+};
+
+/// An actor which records the messages it receives, and replies with canned values.
+///
+/// A mock actor is cheap to clone, with clones sharing the same received messages. A clone is typically kept to
+/// inspect the messages with [`MockActor::received`], while the original is spawned.
+pub struct MockActor<M, R = ()> {
+    received: Arc<Mutex<Vec<M>>>,
+    reply: Arc<dyn Fn(&M) -> R + Send + Sync>,
+}
+
+impl<M, R> MockActor<M, R> {
+    /// Creates a mock actor which replies with the default value of the reply type.
+    pub fn new() -> Self
+    where
+        R: Default,
+    {
+        MockActor::with_reply(|_| R::default())
+    }
+
+    /// Creates a mock actor which replies with the value returned by `f` for each message.
+    pub fn with_reply<F>(f: F) -> Self
+    where
+        F: Fn(&M) -> R + Send + Sync + 'static,
+    {
+        MockActor {
+            received: Arc::default(),
+            reply: Arc::new(f),
+        }
+    }
+
+    /// Returns the messages received so far, in the order they were handled.
+    ///
+    /// The messages are locked until the guard is dropped, so it should not be held across an `.await`.
+    pub fn received(&self) -> MutexGuard<'_, Vec<M>> {
+        self.received.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<M, R: Default> Default for MockActor<M, R> {
+    fn default() -> Self {
+        MockActor::new()
+    }
+}
+
+impl<M, R> Clone for MockActor<M, R> {
+    fn clone(&self) -> Self {
+        MockActor {
+            received: self.received.clone(),
+            reply: self.reply.clone(),
+        }
+    }
+}
+
+impl<M, R> fmt::Debug for MockActor<M, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockActor")
+            .field("received", &self.received().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M, R> Actor for MockActor<M, R>
+where
+    M: Send + 'static,
+    R: Send + 'static,
+{
+    type Mailbox = UnboundedMailbox<Self>;
+
+    fn name() -> &'static str {
+        "MockActor"
+    }
+}
+
+/// Advances tokio's paused clock by `duration`, then lets the tasks woken by it run before returning.
+///
+/// Timers which elapse fire in order, and the messages they send, such as those scheduled with
+/// [`ScheduledMessages::tell_after`](crate::actor::ScheduledMessages::tell_after), are delivered to their actors.
+/// The runtime is yielded to a fixed number of times to let this work settle, so work which keeps spawning new work
+/// may not have finished, but asking an actor afterwards always observes the messages delivered before it.
+///
+/// The clock must be paused, either with [`tokio::time::pause`] or by starting the runtime paused, and the runtime
+/// must use the current thread scheduler. See [`tokio::time::advance`] for more information.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Reminders(Vec<&'static str>);
+///
+/// impl Message<&'static str> for Reminders {
+///     type Reply = Vec<&'static str>;
+///
+///     async fn handle(&mut self, msg: &'static str, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.0.push(msg);
+///         self.0.clone()
+///     }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().start_paused(true).build().unwrap().block_on(async {
+/// let actor_ref = kameo::spawn(Reminders::default());
+/// actor_ref.scheduled().tell_after("standup", Duration::from_secs(60 * 60));
+///
+/// kameo::test::advance_time(Duration::from_secs(60 * 60)).await;
+/// assert_eq!(actor_ref.ask("check").await?, ["standup", "check"]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[cfg(feature = "test-util")]
+pub async fn advance_time(duration: std::time::Duration) {
+    tokio::time::advance(duration).await;
+    // Each step, from a timer firing to its message being handled, needs its own turn of the runtime
+    for _ in 0..SETTLE_YIELDS {
+        tokio::task::yield_now().await;
+    }
+}
+
+/// How many times [`advance_time`] yields to the runtime after advancing the clock.
+#[cfg(feature = "test-util")]
+const SETTLE_YIELDS: usize = 16;
+
+#[cfg(feature = "test-util")]
+tokio::task_local! {
+    /// The actors spawned within [`with_teardown`], shared with the actors it spawns.
+    static TEARDOWN_ACTORS: Arc<Mutex<HashSet<ActorID>>>;
+}
+
+/// Runs a test body, then gracefully stops every actor spawned within it, waiting up to `timeout` for them to stop.
+///
+/// Actors spawned by those actors are stopped too, so a whole tree of actors started by the test is torn down before
+/// the runtime shuts down. Actors which don't stop within the timeout are killed, as with
+/// [`shutdown_all`](crate::actor::shutdown_all). If the body panics, the actors are stopped before the panic is
+/// resumed, so the panic isn't followed by noise from actors being dropped.
+///
+/// Only actors spawned from the body's task, or from the actors it spawned, are tracked. Actors spawned from a
+/// separately spawned tokio task are not.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct Worker;
+///
+/// # tokio_test::block_on(async {
+/// let worker = kameo::test::with_teardown(Duration::from_secs(5), async {
+///     let worker = kameo::spawn(Worker);
+///     // ... the test ...
+///     worker
+/// })
+/// .await;
+///
+/// assert!(!worker.is_alive());
+/// # });
+/// ```
+#[cfg(feature = "test-util")]
+pub async fn with_teardown<F: Future>(timeout: std::time::Duration, body: F) -> F::Output {
+    use futures::FutureExt;
+
+    let spawned = Arc::<Mutex<HashSet<ActorID>>>::default();
+    let res = TEARDOWN_ACTORS
+        .scope(
+            spawned.clone(),
+            std::panic::AssertUnwindSafe(body).catch_unwind(),
+        )
+        .await;
+
+    let spawned = std::mem::take(&mut *spawned.lock().unwrap_or_else(PoisonError::into_inner));
+    crate::actor::shutdown_where(timeout, |id| spawned.contains(id)).await;
+    res.unwrap_or_else(|err| std::panic::resume_unwind(err))
+}
+
+/// The [`with_teardown`] an actor is spawned within, which its task inherits so the actors it spawns are tracked too.
+#[derive(Debug)]
+pub(crate) struct Teardown {
+    #[cfg(feature = "test-util")]
+    spawned: Option<Arc<Mutex<HashSet<ActorID>>>>,
+}
+
+impl Teardown {
+    pub(crate) fn current() -> Self {
+        Teardown {
+            #[cfg(feature = "test-util")]
+            spawned: TEARDOWN_ACTORS.try_with(Arc::clone).ok(),
+        }
+    }
+
+    #[cfg(feature = "test-util")]
+    pub(crate) fn scope<F: Future>(self, fut: F) -> impl Future<Output = F::Output> {
+        use futures::future::Either;
+
+        match self.spawned {
+            Some(spawned) => Either::Left(TEARDOWN_ACTORS.scope(spawned, fut)),
+            None => Either::Right(fut),
+        }
+    }
+
+    #[cfg(not(feature = "test-util"))]
+    pub(crate) fn scope<F: Future>(self, fut: F) -> F {
+        fut
+    }
+}
+
+/// Records an actor as spawned within the current [`with_teardown`], if any.
+pub(crate) fn record_spawned(id: ActorID) {
+    #[cfg(feature = "test-util")]
+    let _ = TEARDOWN_ACTORS.try_with(|spawned| {
+        spawned
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id)
+    });
+    #[cfg(not(feature = "test-util"))]
+    let _ = id;
+}
+
+impl<M, R> Message<M> for MockActor<M, R>
+where
+    M: Send + 'static,
+    R: Reply,
+{
+    type Reply = R;
+
+    async fn handle(&mut self, msg: M, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+        let reply = (self.reply)(&msg);
+        self.received().push(msg);
+        reply
+    }
+}